@@ -1,20 +1,47 @@
 // GENERAL
 pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;    // Number of Lamports in one SOL
+pub const SOL_DECIMALS: u8 = 9;                     // Decimals of native SOL stakes
+pub const MAX_OUTCOMES: u8 = 8;                     // Max number of outcomes for a single facet
+pub const MAX_SCALAR_POINTS: u16 = 101;             // Max number of distinct values which can be voted for on a scalar facet
 
 // WAGERS
 pub const MAX_ALLOWED_TIMEOUT: i64 = 14*24*60*60*1_000; // 2 weeks (ms)
 pub const MIN_ALLOWED_TIMEOUT: i64 = 24*60*60*1_000;    // 1 day (ms)
 pub const MAX_WAGERS: u16 = 10_000;                     // Max number of people placing wagers
-pub const TREASURY_AUTHORITY: &str = "treasuryauthpubkey";
 pub const TREASURY_PROGRAM_ID: &str = "2q146K97ZLyEdhD6SyY1G3EbbvLE6ttPjV5rG9jsQDDL";
+pub const MAX_EXIT_FEE: u16 = 1_000;                    // Max fee kept from a withdrawn wager: 10% (basis points)
+pub const BASIS_POINTS: u64 = 10_000;                   // Basis points in a whole
+pub const MAX_SNIPING_EXTENSION: i64 = 24*60*60*1_000;  // Max total extension of the betting deadline from late bets: 1 day (ms)
+pub const MAX_TIME_WEIGHT: u64 = 30_000;                // Max weight of a stake placed at the very start of betting: 3x (basis points)
+pub const MAX_UNDERDOG_BONUS: u64 = BASIS_POINTS*100/PERCENTAGE_WINNINGS_KEPT;  // Max multiplier on underdog winnings, so the bonus never exceeds the fee (basis points)
+
+// LMSR
+pub const MAX_LMSR_LIQUIDITY: u64 = 1_000*LAMPORTS_PER_SOL;    // Max liquidity parameter of a market maker, bounding the treasury's subsidy
+pub const MIN_LMSR_LIQUIDITY: u64 = LAMPORTS_PER_SOL/100;       // Min liquidity parameter, so that the cap on shares below still allows a useful book
+pub const MAX_LMSR_EXPONENT: u64 = 700;                         // Max shares of any outcome as a multiple of the liquidity, keeping exp(q/b) within f64 range
 
 // VOTING
 pub const MAX_VOTE_AMOUNT: u64 = 100*LAMPORTS_PER_SOL;                                      // Max number of votes per voter
 pub const MIN_VOTE_AMOUNT: u64 = 1_000_000;                                                 // Min number of votes per voter
 pub const VOTE_THRESHOLD: u16 = 1_000;                                                      // Max number of votes in a poll
 pub const VOTING_TOKENS_PROGRAM_ID: &str = "8MrQHajcffRco93T4kR5FiLnrCYA7nj1yYXoauHRdg5d";  // 
-pub const VOTING_TOKENS_MINT_ID: &str = "mintpubkey";                                       // 
+pub const VOTING_TOKENS_MINT_ID: &str = "EckPjWPUpkvdWSJmuUMcPfkg2V2oX62VFm4E8Bt5GaWX";  // 
+
+// APPEALS
+pub const APPEAL_WINDOW: i64 = 24*60*60;                        // Time after a poll closes during which it can be appealed: 1 day (s)
+pub const MIN_APPEAL_BOND: u64 = 10*LAMPORTS_PER_SOL;           // Min bond to be posted in order to lodge an appeal
+pub const APPEAL_VOTE_THRESHOLD: u16 = 2*VOTE_THRESHOLD;        // Max number of votes in an appeal poll
+pub const APPEAL_MIN_VOTE_AMOUNT: u64 = 10*MIN_VOTE_AMOUNT;     // Min number of votes per appeal voter
+
+// EVIDENCE
+pub const MAX_EVIDENCE_URI_LENGTH: u16 = 200;   // Max length of the URI pointing to a voter's evidence (bytes)
+
+// SCORING
+pub const MAX_SCORE: u16 = 10_000;              // Score of content judged entirely authentic (basis points)
+pub const DEFAULT_FACET_WEIGHT: u16 = 1;        // Weight given to each facet in the composite score unless configured
+pub const SCORE_HISTORY_LENGTH: u16 = 16;       // Number of settlements kept in the score history
 
 // CONSOLIDATION
 pub const PERCENTAGE_WINNINGS_KEPT: u64 = 95;   // How much of the winnings pot is received by the winning bettors (%)
 pub const DIV_BUFFER: u64 = 1_000_000;          // Buffer for arithmetic with uints
+pub const MAX_BATCH_SETTLEMENTS: u8 = 8;        // Max number of bettors or voters a keeper can settle in one call
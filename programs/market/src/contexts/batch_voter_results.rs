@@ -0,0 +1,235 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token::{Mint, Token, TokenAccount}
+};
+
+use treasury::{
+    cpi::{accounts::VotingTokenCustody, return_voting_tokens},
+    program::TreasuryProgram,
+    self,
+    Treasury,
+};
+use voting_tokens::{
+    cpi::{accounts::MintTokensTo, mint_tokens_to},
+    self,
+    program::VotingTokens,
+};
+
+use crate::constants::{TREASURY_PROGRAM_ID, VOTING_TOKENS_MINT_ID, VOTING_TOKENS_PROGRAM_ID};
+use crate::error::{AppealError, FacetError, MintError, ResultsError, TokenError, TreasuryError, VotingError};
+use crate::states::{Appeal, Market, MarketParams, MarketState, Poll, Voter};
+use crate::utils::functions::{batch_entry_pending, batch_size, calc_winnings_from_votes, final_outcome};
+
+// Each voter is passed in the remaining accounts as [voter, wallet, voting token account]
+const VOTER_ACCOUNTS: usize = 3;
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams)]
+pub struct BatchVoterResults<'info_bv> {
+    #[account(mut)]
+    pub keeper: Signer<'info_bv>,   // Anyone settling on the voters' behalf, who pays for any accounts created along the way
+    #[account(
+        seeds = [b"market", params.authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_bv, Market>,
+    #[account(
+        mut,
+        seeds = [b"poll", params.authensus_token.as_ref(), params.facet.to_string().as_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info_bv, Poll>,
+    #[account(
+        mut,
+        seeds = [b"appeal", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub appeal: Option<Account<'info_bv, Appeal>>,                      // Only required if the poll was appealed
+    #[account(mut)]
+    pub treasury_voting_token_account: Account<'info_bv, TokenAccount>, // The treasury PDA's ATA, initialised with the treasury
+    #[account(mut)]
+    pub treasury: Account<'info_bv, Treasury>,
+    pub treasury_program: Program<'info_bv, TreasuryProgram>,
+    pub voting_tokens_program: Program<'info_bv, VotingTokens>,
+    pub associated_token_program: Program<'info_bv, AssociatedToken>,
+    #[account(mut)]
+    pub mint: Account<'info_bv, Mint>,
+    pub system_program: Program<'info_bv, System>,
+    pub token_program: Program<'info_bv, Token>,
+    pub rent: Sysvar<'info_bv, Rent>,
+}
+
+impl<'info_bv> BatchVoterResults<'info_bv> {
+
+    pub fn settle_voters(
+        &mut self,
+        params: &MarketParams,
+        voter_accounts: &'info_bv [AccountInfo<'info_bv>],
+    ) -> Result<u8> {
+
+        let mint_pk: Pubkey = Pubkey::from_str(VOTING_TOKENS_MINT_ID).unwrap();
+        let mint_program_pk: Pubkey = Pubkey::from_str(VOTING_TOKENS_PROGRAM_ID).unwrap();
+
+        let treasury_ata: Pubkey = get_associated_token_address_with_program_id(
+            &self.treasury.key(),
+            &mint_pk,
+            &mint_program_pk,
+        );
+
+        // Requirements:                                                                |   Implemented:
+        //  - Market should now be in the consolidation state                           |       √
+        //  - Any appeal account should be supplied                                     |       √
+        //  - Market should contain the given facet                                     |       √
+        //  - The token must be the same as that which instantiated the market          |       √
+        //  - Mint PK needs to be correct                                               |       √
+        //  - Treasury Program needs to be correct                                      |       √
+        //  - Voting Tokens Program needs to be correct                                 |       √
+        //  - treasury_voting_token_account should be derivable from the treasury PDA   |       √
+        require!(self.market.state == MarketState::Consolidating, ResultsError::VotingNotFinished);
+        require!(!self.poll.appealed || self.appeal.is_some(), AppealError::AppealAccountMissing);
+        require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
+        require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
+        require!(self.mint.key() == mint_pk, MintError::NotTheRightMintPK);
+        require!(self.treasury_program.key().to_string() == TREASURY_PROGRAM_ID, TreasuryError::NotTheRightTreasuryProgramPK);
+        require!(self.voting_tokens_program.key().to_string() == VOTING_TOKENS_PROGRAM_ID, MintError::NotTheRightMintProgramPK);
+        require!(treasury_ata == self.treasury_voting_token_account.key(), VotingError::IncorrectTreasuryATA);
+
+        batch_size(voter_accounts.len(), VOTER_ACCOUNTS)?;
+
+        let outcome: Option<u8> = final_outcome(&self.poll, self.appeal.as_deref());
+        let mut settled: u8 = 0;
+
+        for accounts in voter_accounts.chunks(VOTER_ACCOUNTS) {
+
+            // Account::try_from checks both the owner and the discriminator
+            let voter: Account<Voter> = Account::try_from(&accounts[0])?;
+            let voting_token_account: Account<TokenAccount> = Account::try_from(&accounts[2])?;
+            let wallet: Pubkey = accounts[1].key();
+
+            // Appeal voters are recorded in the appeal poll rather than the original one
+            let (voters, voters_consolidated): (&Option<Vec<Pubkey>>, &Option<Vec<Pubkey>>) = match (voter.appeal, self.appeal.as_ref()) {
+                (true, Some(appeal)) => (&appeal.voters, &appeal.voters_consolidated),
+                (true, None) => return Err(anchor_lang::error!(AppealError::AppealAccountMissing)),
+                (false, _) => (&self.poll.voters, &self.poll.voters_consolidated),
+            };
+
+            // Requirements:                                                    |   Implemented:
+            //  - Voter account should belong to the wallet in this market-facet|       √
+            //  - Voting token account should be the wallet's own               |       √
+            require!(voter.pk == wallet && voter.market == params.authensus_token && voter.facet == params.facet, ResultsError::NotAVoter);
+            require!(voting_token_account.owner == wallet && voting_token_account.mint == mint_pk, VotingError::IncorrectATA);
+
+            if !batch_entry_pending(&wallet, voters, voters_consolidated) {
+                continue
+            }
+
+            self.add_to_consolidated(wallet, voter.appeal)?;
+
+            match outcome {
+                // In the case of a tie everyone gets their voting tokens back out of the treasury's custody
+                None => self.return_votes(accounts, voter.amount)?,
+                Some(outcome) => {
+                    let winnings: u64 = calc_winnings_from_votes(
+                        outcome,
+                        voter.outcome,
+                        voter.amount,
+                    );
+
+                    // Winners get their votes back along with minted winnings, as in voter_results
+                    if winnings > 0 {
+                        self.return_votes(accounts, voter.amount)?;
+                        self.mint_winnings(accounts, winnings)?;
+                    }
+                },
+            }
+
+            settled += 1;
+        }
+
+        Ok(settled)
+
+    }
+
+    fn return_votes(
+        &self,
+        accounts: &'info_bv [AccountInfo<'info_bv>],
+        amount: u64,
+    ) -> Result<()> {
+
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = VotingTokenCustody {
+            signer: self.market.to_account_info(),
+            treasury: self.treasury.to_account_info(),
+            mint: self.mint.to_account_info(),
+            treasury_voting_token_account: self.treasury_voting_token_account.to_account_info(),
+            voting_token_account: Some(accounts[2].clone()),
+            token_program: self.token_program.to_account_info(),
+            associated_token_program: self.associated_token_program.to_account_info(),
+        };
+
+        // The market signs as its PDA, which the treasury accepts in place of its authority
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        return_voting_tokens(cpi_ctx, Some(self.market.signer()), amount)
+
+    }
+
+    fn mint_winnings(
+        &self,
+        accounts: &'info_bv [AccountInfo<'info_bv>],
+        winnings: u64,
+    ) -> Result<()> {
+
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = MintTokensTo {
+            payer: self.keeper.to_account_info(),
+            caller: self.market.to_account_info(),
+            owner: accounts[1].clone(),
+            mint: self.mint.to_account_info(),
+            recipient: accounts[2].clone(),     // Checked above as the wallet's voting token account
+            associated_token_program: self.associated_token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+        };
+
+        // The market signs as its PDA, since only a market can mint winnings into another account
+        let cpi_ctx = CpiContext::new_with_signer(self.voting_tokens_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        mint_tokens_to(cpi_ctx, self.market.token, self.market.bump, winnings)
+
+    }
+
+    fn add_to_consolidated(&mut self, voter: Pubkey, appeal: bool) -> Result<()> {
+
+        let voters_consolidated: &mut Option<Vec<Pubkey>> = match (appeal, self.appeal.as_mut()) {
+            (true, Some(appeal)) => &mut appeal.voters_consolidated,
+            (true, None) => return Err(anchor_lang::error!(AppealError::AppealAccountMissing)),
+            (false, _) => &mut self.poll.voters_consolidated,
+        };
+
+        match voters_consolidated.as_mut() {
+            Some(consolidated) => consolidated.push(voter),
+            None => *voters_consolidated = Some(Vec::from([voter])),
+        }
+
+        Ok(())
+
+    }
+
+}
@@ -0,0 +1,372 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, TransferChecked}
+};
+
+use treasury::{
+    cpi::{accounts::Reimburse, reimburse},
+    program::TreasuryProgram,
+    self,
+    LedgerPurpose,
+    Treasury,
+};
+use voting_tokens::{
+    cpi::{accounts::MintTokensTo, mint_tokens_to},
+    self,
+    program::VotingTokens,
+};
+
+use crate::constants::{APPEAL_WINDOW, PERCENTAGE_WINNINGS_KEPT, TREASURY_PROGRAM_ID, VOTE_THRESHOLD, VOTING_TOKENS_MINT_ID, VOTING_TOKENS_PROGRAM_ID};
+use crate::error::{AppealError, FacetError, MintError, ResultsError, StakeError, TokenError, TreasuryError};
+use crate::states::{Appeal, Bettor, Escrow, Market, MarketParams, MarketState, Poll};
+use crate::utils::functions::{batch_entry_pending, batch_size, compute_returns, final_outcome, forfeited_bond_share, round_claims, scale_decimals, stake_accounts_condition, Stakes};
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams)]
+pub struct BatchWagerResults<'info_bw> {
+    #[account(mut)]
+    pub keeper: Signer<'info_bw>,   // Anyone settling on the bettors' behalf, who pays for any accounts created along the way
+    #[account(
+        mut,
+        seeds = [b"market", params.authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_bw, Market>,
+    #[account(
+        mut,
+        seeds = [b"escrow", params.authensus_token.as_ref(), params.facet.to_string().as_bytes()],
+        bump,
+    )]
+    pub escrow: Account<'info_bw, Escrow>,
+    #[account(
+        seeds = [b"poll", params.authensus_token.as_ref(), params.facet.to_string().as_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info_bw, Poll>,
+    #[account(
+        seeds = [b"appeal", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub appeal: Option<Account<'info_bw, Appeal>>,  // Only required if the poll was appealed
+    #[account(mut)]
+    pub mint: Account<'info_bw, Mint>,
+    #[account(mut)]
+    pub treasury: Account<'info_bw, Treasury>,
+    pub stake_mint: Option<Account<'info_bw, Mint>>,    // The vault is also only required for markets denominated in an SPL token
+    #[account(
+        mut,
+        seeds = [b"vault", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: Option<Account<'info_bw, TokenAccount>>,
+    pub treasury_program: Program<'info_bw, TreasuryProgram>,
+    /// CHECK: Checked by the treasury program, which tracks what the round owes
+    #[account(
+        mut,
+        seeds = [b"payable", market.key().as_ref(), market.round.to_le_bytes().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub payable: UncheckedAccount<'info_bw>,
+    /// CHECK: Checked by the treasury program, which keeps the round's share of its balance
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), LedgerPurpose::Stakes.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub stakes_ledger: UncheckedAccount<'info_bw>,
+    /// CHECK: Checked by the treasury program, which keeps the round's appeal bonds
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), LedgerPurpose::Bonds.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub bonds_ledger: UncheckedAccount<'info_bw>,
+    pub voting_tokens_program: Program<'info_bw, VotingTokens>,
+    pub system_program: Program<'info_bw, System>,
+    pub token_program: Program<'info_bw, Token>,
+    pub associated_token_program: Program<'info_bw, AssociatedToken>,
+    pub rent: Sysvar<'info_bw, Rent>,
+    // Each bettor is passed in the remaining accounts as [bettor, wallet, voting token ATA, IOU], followed by their stake account in a token market
+}
+
+impl<'info_bw> BatchWagerResults<'info_bw> {
+
+    pub fn settle_bettors(
+        &mut self,
+        params: &MarketParams,
+        bettor_accounts: &'info_bw [AccountInfo<'info_bw>],
+    ) -> Result<u8> {
+
+        let mint_pk: Pubkey = Pubkey::from_str(VOTING_TOKENS_MINT_ID).unwrap();
+
+        let time: i64 = Clock::get()?.unix_timestamp;
+
+        let appeal_resolved: bool = match self.appeal.as_ref() {
+            Some(appeal) => appeal.resolved && appeal.round == self.market.round,
+            None => false,
+        };
+
+        let group: usize = match self.market.stake_mint {
+            Some(_) => 5,
+            None => 4,
+        };
+
+        // Requirements:                                                        |   Implemented:
+        //  - Voting is finished                                                |       √
+        //  - The appeal window has passed without an appeal                    |       √
+        //  - Any appeal has been resolved                                      |       √
+        //  - Market should contain the given facet                             |       √
+        //  - The token must be the same as that which instantiated the market  |       √
+        //  - Mint account ID needs to be correct                               |       √
+        //  - Treasury Program needs to be correct                              |       √
+        //  - Voting Tokens Program needs to be correct                         |       √
+        require!(self.poll.total_outcomes.iter().sum::<u64>() >= VOTE_THRESHOLD.into(), ResultsError::VotingNotFinished);
+        require!(self.poll.appealed || self.poll.closed_at + APPEAL_WINDOW < time, AppealError::AppealWindowOpen);
+        require!(!self.poll.appealed || appeal_resolved, AppealError::AppealNotResolved);
+        require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
+        require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
+        require!(self.mint.key() == mint_pk, MintError::NotTheRightMintPK);
+        require!(self.treasury_program.key().to_string() == TREASURY_PROGRAM_ID, TreasuryError::NotTheRightTreasuryProgramPK);
+        require!(self.voting_tokens_program.key().to_string() == VOTING_TOKENS_PROGRAM_ID, MintError::NotTheRightMintProgramPK);
+
+        batch_size(bettor_accounts.len(), group)?;
+
+        // Change the market state if necessary
+        if self.market.state == MarketState::Voting {
+            self.market.state = MarketState::Consolidating;
+        }
+
+        let outcome: Option<u8> = final_outcome(&self.poll, self.appeal.as_deref());
+        let mut settled: u8 = 0;
+
+        for accounts in bettor_accounts.chunks(group) {
+
+            let wallet: Pubkey = accounts[1].key();
+
+            // A bettor who has withdrawn or tokenised their position since may no longer have a bettor account
+            if !batch_entry_pending(&wallet, &self.escrow.bettors, &self.escrow.bettors_consolidated) {
+                continue
+            }
+
+            // Account::try_from checks both the owner and the discriminator
+            let bettor: Account<Bettor> = Account::try_from(&accounts[0])?;
+
+            let stake_account: Option<Account<TokenAccount>> = match accounts.get(4) {
+                Some(stake_account) => Some(Account::try_from(stake_account)?),
+                None => None,
+            };
+
+            let stake_condition: bool = stake_accounts_condition(
+                self.market.stake_mint,
+                self.stake_mint.as_ref(),
+                self.vault.as_ref(),
+                self.escrow.key(),
+                stake_account.as_ref(),
+                wallet,
+            );
+
+            // Requirements:                                                        |   Implemented:
+            //  - Bettor account should belong to the wallet in this market-facet   |       √
+            //  - Stake accounts should be provided for a token market              |       √
+            require!(bettor.pk == wallet && bettor.market == params.authensus_token && bettor.facet == params.facet, ResultsError::NotABettor);
+            require!(stake_condition, StakeError::StakeAccountsMissing);
+
+            self.add_to_consolidated(wallet);
+            self.settle_bettor(params, outcome, &bettor, accounts)?;
+
+            settled += 1;
+        }
+
+        Ok(settled)
+
+    }
+
+    fn settle_bettor(
+        &self,
+        params: &MarketParams,
+        outcome: Option<u8>,
+        bettor: &Bettor,
+        accounts: &'info_bw [AccountInfo<'info_bw>],
+    ) -> Result<()> {
+
+        let outcome: u8 = match outcome {
+            Some(outcome) => outcome,
+            None => {
+                let total_bets = bettor.tot_outcomes.iter().sum::<u64>() + bettor.tot_underdog;
+
+                return match self.market.stake_mint {
+                    Some(_) => self.return_token_wager(params, &accounts[4], total_bets),
+                    None => self.reimburse_sol_wager(params, accounts, LedgerPurpose::Stakes, total_bets),
+                }
+            },
+        };
+
+        let (bet_returned, winnings_pre) = compute_returns(
+            outcome,
+            &Stakes {
+                outcomes: &self.escrow.tot_outcomes,
+                underdog: self.escrow.tot_underdog,
+                weighted_outcomes: &self.escrow.weighted_outcomes,
+                weighted_underdog: self.escrow.weighted_underdog,
+            },
+            &Stakes {
+                outcomes: &bettor.tot_outcomes,
+                underdog: bettor.tot_underdog,
+                weighted_outcomes: &bettor.weighted_outcomes,
+                weighted_underdog: bettor.weighted_underdog,
+            },
+            self.market.config.underdog_bonus.into(),
+        );
+
+        if bet_returned == 0 {
+            return Ok(())
+        }
+
+        // Voting tokens are minted in their own decimals, whatever the stake denomination
+        let winnings: u64 = scale_decimals(
+            (PERCENTAGE_WINNINGS_KEPT*winnings_pre)/100,
+            self.market.stake_decimals,
+            self.mint.decimals,
+        );
+
+        // Reimburse bets, along with a share of any forfeited appeal bond (which is always in SOL)
+        let bond_share: u64 = forfeited_bond_share(&self.poll, self.appeal.as_deref(), &self.escrow, outcome, bet_returned);
+
+        match self.market.stake_mint {
+            Some(_) => self.return_token_wager(params, &accounts[4], bet_returned)?,
+            None => self.reimburse_sol_wager(params, accounts, LedgerPurpose::Stakes, bet_returned)?,
+        }
+
+        if bond_share > 0 {
+            self.reimburse_sol_wager(params, accounts, LedgerPurpose::Bonds, bond_share)?;
+        }
+
+        self.mint_voting_tokens_to_winner(accounts, winnings)
+
+    }
+
+    fn return_token_wager(
+        &self,
+        params: &MarketParams,
+        stake_account: &AccountInfo<'info_bw>,
+        amount: u64,
+    ) -> Result<()> {
+
+        let facet: String = params.facet.to_string();
+        let seeds: &[&[u8]] = &[
+            b"escrow",
+            params.authensus_token.as_ref(),
+            facet.as_bytes(),
+            &[self.escrow.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        // The stake accounts have already been checked against the market's stake mint and the bettor
+        let accounts = TransferChecked {
+            from: self.vault.as_ref().unwrap().to_account_info(),
+            mint: self.stake_mint.as_ref().unwrap().to_account_info(),
+            to: stake_account.clone(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+
+        token::transfer_checked(cpi_ctx, amount, self.market.stake_decimals)
+
+    }
+
+    fn reimburse_sol_wager(
+        &self,
+        params: &MarketParams,
+        accounts: &'info_bw [AccountInfo<'info_bw>],
+        purpose: LedgerPurpose,
+        amount: u64,
+    ) -> Result<()> {
+
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = Reimburse {
+            payer: self.keeper.to_account_info(),
+            caller: self.market.to_account_info(),
+            coparty: accounts[1].clone(),           // The bettor's wallet, which need not sign
+            treasury: self.treasury.to_account_info(),
+            payable: self.payable.to_account_info(),
+            ledger: match purpose {
+                LedgerPurpose::Bonds => self.bonds_ledger.to_account_info(),
+                _ => self.stakes_ledger.to_account_info(),
+            },
+            iou: accounts[3].clone(),
+            system_program: self.system_program.to_account_info(),
+        };
+
+        // Claims on the round are shared out fairly if it runs short
+        let claims: u64 = round_claims(&self.poll, self.appeal.as_deref(), &self.escrow, purpose);
+
+        // The market signs as its PDA, so payouts are limited to what the round owes
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        reimburse(
+            cpi_ctx,
+            self.market.signer(),
+            self.market.round,
+            params.facet.to_string(),
+            purpose,
+            amount,
+            claims,
+        )
+
+    }
+
+    fn mint_voting_tokens_to_winner(
+        &self,
+        accounts: &'info_bw [AccountInfo<'info_bw>],
+        winnings: u64,
+    ) -> Result<()> {
+
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = MintTokensTo {
+            payer: self.keeper.to_account_info(),
+            caller: self.market.to_account_info(),
+            owner: accounts[1].clone(),
+            mint: self.mint.to_account_info(),
+            recipient: accounts[2].clone(),     // Checked by the voting tokens program as the wallet's ATA
+            associated_token_program: self.associated_token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+        };
+
+        // The market signs as its PDA, since only a market can mint winnings into another account
+        let cpi_ctx = CpiContext::new_with_signer(self.voting_tokens_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        mint_tokens_to(cpi_ctx, self.market.token, self.market.bump, winnings)
+
+    }
+
+    fn add_to_consolidated(&mut self, bettor: Pubkey) {
+
+        match self.escrow.bettors_consolidated.as_mut() {
+            Some(consolidated) => consolidated.push(bettor),
+            None => self.escrow.bettors_consolidated = Some(Vec::from([bettor])),
+        }
+
+    }
+
+}
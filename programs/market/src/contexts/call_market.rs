@@ -1,9 +1,19 @@
 use anchor_lang::prelude::*;
 
-use crate::states::{Escrow, Market, MarketParams, MarketState, Poll};
-use crate::constants::TREASURY_AUTHORITY;
-use crate::error::{FacetError, MarketError, ResultsError, TreasuryError};
-use crate::utils::functions::vec_eq;
+use treasury::{
+    cpi::{accounts::CollectFee, collect_fee},
+    program::TreasuryProgram,
+    self,
+    LedgerPurpose,
+    Payable,
+    Treasury,
+    TreasuryAccount,
+};
+
+use crate::states::{Appeal, ContentScore, Escrow, FacetScore, Market, MarketParams, MarketState, Poll, ScoreRecord, Verdict};
+use crate::constants::TREASURY_PROGRAM_ID;
+use crate::error::{AppealError, FacetError, MarketError, ResultsError, TreasuryError};
+use crate::utils::functions::{calc_facet_score, calc_scalar_score, calc_weighted_score, final_outcome, final_value, forfeited_bond, push_score_record, vec_eq};
 
 #[derive(Accounts)]
 #[instruction(params: MarketParams)]
@@ -28,37 +38,110 @@ pub struct CallMarket<'info_c> {
         bump,
     )]
     pub escrow: Account<'info_c, Escrow>,
+    #[account(
+        seeds = [b"appeal", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub appeal: Option<Account<'info_c, Appeal>>,  // Only required if the poll was appealed
+    #[account(
+        init,
+        space = 8 + Verdict::INIT_SPACE,
+        payer = admin,
+        seeds = [b"verdict", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub verdict: Account<'info_c, Verdict>,
+    #[account(
+        mut,
+        seeds = [b"score", params.authensus_token.as_ref()],
+        bump,
+    )]
+    pub content_score: Account<'info_c, ContentScore>,
+    #[account(mut)]
+    pub treasury: Account<'info_c, Treasury>,      // Should already be initialised
+    pub treasury_program: Program<'info_c, TreasuryProgram>,
+    /// CHECK: Checked by the treasury program, which tracks what the round owes
+    #[account(
+        mut,
+        seeds = [b"payable", market.key().as_ref(), market.round.to_le_bytes().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub payable: Option<UncheckedAccount<'info_c>>,     // The remaining accounts are only required for markets staked in SOL
+    /// CHECK: Checked by the treasury program, which keeps the round's stakes
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), LedgerPurpose::Stakes.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub stakes_ledger: Option<UncheckedAccount<'info_c>>,
+    /// CHECK: Created and checked by the treasury program, which keeps the stakes left behind by losing bettors
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), LedgerPurpose::Fees.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub fee_ledger: Option<UncheckedAccount<'info_c>>,
+    pub system_program: Program<'info_c, System>,
 }
 
 impl<'info_c> CallMarket<'info_c> {
 
     pub fn end(
         &mut self,
+        bumps: &CallMarketBumps,
         params: &MarketParams,
     ) -> Result<()> {
 
-        if self.escrow.bettors.is_none() || self.escrow.bettors_consolidated.is_none() || self.poll.voters.is_none() || self.poll.voters_consolidated.is_none() {
+        if self.escrow.bettors.is_none() || self.poll.voters.is_none() || self.poll.voters_consolidated.is_none() {
             return Err(anchor_lang::error!(ResultsError::NotAllBetsConsolidated))
         }
 
-        let bet_consolidation: bool = vec_eq(self.escrow.bettors.clone().as_mut().unwrap(), self.escrow.bettors_consolidated.clone().as_mut().unwrap());
+        // Bettors who have withdrawn or tokenised their whole position are no longer in the escrow, which may leave it empty
+        let bet_consolidation: bool = vec_eq(self.escrow.bettors.clone().as_mut().unwrap(), &mut self.escrow.bettors_consolidated.clone().unwrap_or_default());
         let vote_consolidation: bool = vec_eq(self.poll.voters.clone().as_mut().unwrap(), self.poll.voters_consolidated.clone().as_mut().unwrap());
 
+        let appeal_vote_consolidation: bool = match (self.poll.appealed, self.appeal.as_ref()) {
+            (true, Some(appeal)) => appeal.resolved && vec_eq(&mut appeal.voters.clone().unwrap_or_default(), &mut appeal.voters_consolidated.clone().unwrap_or_default()),
+            (true, None) => return Err(anchor_lang::error!(AppealError::AppealAccountMissing)),
+            (false, _) => true,
+        };
+
+        let ledger_condition: bool = self.market.stake_mint.is_some()
+            || (self.payable.is_some() && self.stakes_ledger.is_some() && self.fee_ledger.is_some());
+
         // Requirements:                                                        |   Implemented:
         //  - Market State should be Consolidating                              |       √
-        //  - escrow and poll should have the same market, which is this market |       √
+        //  - escrow and poll should belong to this market's token              |       √
         //  - escrow and poll should have the same facet                        |       √
         //  - escrow/poll facet should be in the market facets vec              |       √
         //  - SOL has been reimbursed as necessary                              |       √
         //  - Tokens have been reimbursed as necessary                          |       √
+        //  - Any appeal has been resolved and its voters reimbursed            |       √
         //  - Admin should be the treasury authority                            |       √
+        //  - Treasury Program needs to be correct                              |       √
+        //  - Ledger accounts are supplied for a market staked in SOL           |       √
         require!(self.market.state == MarketState::Consolidating, MarketError::MarketInWrongState);
-        require!(self.market.key() == self.escrow.market && self.market.key() == self.poll.market && self.market.token == params.authensus_token, MarketError::NotTheSameMarket);
+        require!(self.escrow.market == params.authensus_token && self.poll.market == params.authensus_token && self.market.token == params.authensus_token, MarketError::NotTheSameMarket);
         require!(self.escrow.facet == self.poll.facet && self.escrow.facet == params.facet, FacetError::NotTheSameFacet);
         require!(self.market.facets.contains(&self.escrow.facet), FacetError::FacetNotInMarket);
         require!(bet_consolidation, ResultsError::NotAllBetsConsolidated);
         require!(vote_consolidation, ResultsError::NotAllVotesConsolidated);
-        require!(self.admin.key().to_string() == TREASURY_AUTHORITY, TreasuryError::WrongTreasuryAuthority);
+        require!(appeal_vote_consolidation, ResultsError::NotAllVotesConsolidated);
+        require!(self.admin.key() == self.treasury.authority, TreasuryError::WrongTreasuryAuthority);
+        require!(self.treasury_program.key().to_string() == TREASURY_PROGRAM_ID, TreasuryError::NotTheRightTreasuryProgramPK);
+        require!(ledger_condition, TreasuryError::LedgerAccountsMissing);
+
+        // Record the outcome of the round before the escrow and poll are emptied
+        self.record_verdict(bumps)?;
+        self.update_content_score()?;
+
+        // Every bettor has been paid, so whatever is left in a SOL round's stakes was lost, and the treasury keeps it
+        if self.market.stake_mint.is_none() {
+            self.sweep_losing_stakes(params)?;
+        }
 
         // Set market inactive
         self.market.state = MarketState::Inactive;
@@ -66,17 +149,155 @@ impl<'info_c> CallMarket<'info_c> {
         // Empty escrow
         self.escrow.bettors = None;
         self.escrow.bettors_consolidated = None;
-        self.escrow.tot_for = 0_u64;
-        self.escrow.tot_against = 0_u64;
+        self.escrow.tot_outcomes = vec![0; params.facet.outcomes().into()];
         self.escrow.tot_underdog = 0_u64;
+        self.escrow.weighted_outcomes = vec![0; params.facet.outcomes().into()];
+        self.escrow.weighted_underdog = 0_u64;
 
         // Empty poll
         self.poll.voters = None;
         self.poll.voters_consolidated = None;
-        self.poll.total_for = 0_u64;
-        self.poll.total_against = 0_u64;
+        self.poll.total_outcomes = vec![0; params.facet.outcomes().into()];
+        self.poll.value_weights = vec![0; params.facet.scalar_range().map_or(0, |range| range.points())];
+        self.poll.closed_at = 0_i64;
+        self.poll.appealed = false;
 
         Ok(())
     }
 
+    fn record_verdict(
+        &mut self,
+        bumps: &CallMarketBumps,
+    ) -> Result<()> {
+
+        let settled_at: i64 = Clock::get()?.unix_timestamp;
+        let betting_closed_at: i64 = self.market.betting_deadline();
+
+        let appeal: Option<&Appeal> = match self.poll.appealed {
+            true => self.appeal.as_deref(),
+            false => None,
+        };
+
+        let (appeal_total_outcomes, overturned, voting_closed_at) = match appeal {
+            Some(appeal) => (appeal.total_outcomes.clone(), appeal.overturned, appeal.closed_at),
+            None => (Vec::new(), false, self.poll.closed_at),
+        };
+
+        let outcome: Option<u8> = final_outcome(&self.poll, appeal);
+        let value: Option<u16> = final_value(&self.poll, appeal);
+        let forfeited_bond: u64 = forfeited_bond(&self.poll, appeal, &self.escrow);
+
+        self.verdict.set_inner(
+            Verdict {
+                bump: bumps.verdict,                                           // u8
+                market: self.escrow.market,                                    // Pubkey
+                facet: self.escrow.facet.clone(),                              // Facet
+                round: self.market.round,                                      // u16
+                outcome,                                                       // Option<u8>
+                value,                                                         // Option<u16>
+                total_outcomes: self.poll.total_outcomes.clone(),              // Vec<u64>
+                appealed: self.poll.appealed,                                  // bool
+                overturned,                                                    // bool
+                forfeited_bond,                                                // u64
+                appeal_total_outcomes,                                         // Vec<u64>
+                pool_outcomes: self.escrow.tot_outcomes.clone(),               // Vec<u64>
+                pool_underdog: self.escrow.tot_underdog,                       // u64
+                pool_weighted_outcomes: self.escrow.weighted_outcomes.clone(), // Vec<u64>
+                pool_weighted_underdog: self.escrow.weighted_underdog,         // u64
+                start_time: self.market.start_time,                            // i64
+                betting_closed_at,                                             // i64
+                voting_closed_at,                                              // i64
+                settled_at,                                                    // i64
+            }
+        );
+
+        Ok(())
+
+    }
+
+    fn sweep_losing_stakes(&self, params: &MarketParams) -> Result<()> {
+
+        let payable: AccountInfo<'info_c> = self.payable.as_ref().unwrap().to_account_info();
+        let stakes_ledger: AccountInfo<'info_c> = self.stakes_ledger.as_ref().unwrap().to_account_info();
+
+        // Only read here, so the treasury program's changes are not written over when this instruction exits.
+        // Claims the round could not pay in full are owed by IOUs, and no longer count against what it has left
+        let remaining: u64 = Payable::try_deserialize(&mut &payable.try_borrow_data()?[..])?.remaining();
+        let amount: u64 = TreasuryAccount::try_deserialize(&mut &stakes_ledger.try_borrow_data()?[..])?.balance.min(remaining);
+
+        if amount == 0 {
+            return Ok(())
+        }
+
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = CollectFee {
+            payer: self.admin.to_account_info(),
+            caller: self.market.to_account_info(),
+            treasury: self.treasury.to_account_info(),
+            payable,
+            ledger: stakes_ledger,
+            fee_ledger: self.fee_ledger.as_ref().unwrap().to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        collect_fee(cpi_ctx, self.market.signer(), self.market.round, params.facet.to_string(), LedgerPurpose::Stakes, amount)
+
+    }
+
+    fn update_content_score(&mut self) -> Result<()> {
+
+        // Scalar facets are scored by their resolved value; otherwise the appeal poll has the larger jury,
+        // so its tallies take precedence when present
+        let facet_score: u16 = match (self.verdict.facet.scalar_range(), self.verdict.value) {
+            (Some(range), Some(value)) => calc_scalar_score(&range, value),
+            _ => match self.verdict.appealed {
+                true => calc_facet_score(&self.verdict.appeal_total_outcomes),
+                false => calc_facet_score(&self.verdict.total_outcomes),
+            },
+        };
+
+        let facet_scores: &mut Vec<FacetScore> = &mut self.content_score.facet_scores;
+
+        match facet_scores.iter_mut().find(|s| s.facet == self.verdict.facet) {
+            Some(existing) => {
+                existing.round = self.verdict.round;
+                existing.score = facet_score;
+            },
+            None => facet_scores.push(
+                FacetScore {
+                    facet: self.verdict.facet.clone(),  // Facet
+                    round: self.verdict.round,          // u16
+                    score: facet_score,                 // u16
+                }
+            ),
+        }
+
+        let score: u16 = calc_weighted_score(&self.content_score.weights, &self.content_score.facet_scores);
+
+        let record: ScoreRecord = ScoreRecord {
+            facet: self.verdict.facet.clone(),  // Facet
+            round: self.verdict.round,          // u16
+            facet_score,                        // u16
+            score,                              // u16
+            timestamp: self.verdict.settled_at, // i64
+        };
+
+        let head: u16 = self.content_score.head;
+
+        self.content_score.head = push_score_record(&mut self.content_score.history, head, record);
+        self.content_score.score = score;
+        self.content_score.updated_at = self.verdict.settled_at;
+
+        Ok(())
+
+    }
+
 }
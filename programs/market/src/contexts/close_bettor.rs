@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Bettor, Escrow, MarketParams};
+use crate::error::CloseError;
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams)]
+pub struct CloseBettor<'info_cb> {
+    #[account(mut)]
+    pub signer: Signer<'info_cb>,
+    #[account(
+        seeds = [b"escrow", params.authensus_token.as_ref(), params.facet.to_string().as_bytes()],
+        bump,
+    )]
+    pub escrow: Account<'info_cb, Escrow>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"bettor", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), signer.key().as_ref()],
+        bump,
+    )]
+    pub bettor: Account<'info_cb, Bettor>,  // Paid for by the bettor when they first bet
+}
+
+impl<'info_cb> CloseBettor<'info_cb> {
+
+    pub fn close(&mut self) -> Result<()> {
+
+        let signer: Pubkey = self.signer.key();
+
+        // The escrow is emptied by call_market, after which every bettor has already been consolidated
+        let pending: bool = self.escrow.bettors.as_ref().is_some_and(|bettors| bettors.contains(&signer))
+            && !self.escrow.bettors_consolidated.as_ref().is_some_and(|consolidated| consolidated.contains(&signer));
+
+        // Requirements:                                                    |   Implemented:
+        //  - Bettor should have no bets waiting to be consolidated         |       √
+        require!(!pending, CloseError::BettorNotConsolidated);
+
+        Ok(())
+
+    }
+
+}
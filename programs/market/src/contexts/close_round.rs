@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Appeal, Escrow, Market, MarketParams, MarketState, Poll, Verdict};
+use crate::error::{AppealError, CloseError};
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams)]
+pub struct CloseRound<'info_cr> {
+    pub signer: Signer<'info_cr>,
+    /// CHECK: Only receives the rent, and must be the account which paid it
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info_cr>,
+    #[account(
+        seeds = [b"market", params.authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_cr, Market>,
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [b"escrow", params.authensus_token.as_ref(), params.facet.to_string().as_bytes()],
+        bump,
+    )]
+    pub escrow: Account<'info_cr, Escrow>,
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [b"poll", params.authensus_token.as_ref(), params.facet.to_string().as_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info_cr, Poll>,
+    #[account(
+        seeds = [b"verdict", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub verdict: Option<Account<'info_cr, Verdict>>,    // Only exists once the round has been called
+    #[account(
+        mut,
+        seeds = [b"appeal", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub appeal: Option<Account<'info_cr, Appeal>>,  // Only required if the poll was appealed
+    /// CHECK: Only receives the appeal's rent, and must be the appellant who paid it
+    #[account(mut)]
+    pub appellant: Option<UncheckedAccount<'info_cr>>,
+}
+
+impl<'info_cr> CloseRound<'info_cr> {
+
+    pub fn close(&mut self) -> Result<()> {
+
+        // call_market leaves the market inactive with an empty escrow and poll
+        let called_condition: bool = self.market.state == MarketState::Inactive
+            && self.escrow.bettors.is_none()
+            && self.poll.voters.is_none()
+            && self.verdict.is_some();
+
+        // Any tokenised stakes stay in the round's vault, for which redeem_outcome_tokens signs without the escrow account
+        // Requirements:                                                    |   Implemented:
+        //  - Market should have been called, emptying the escrow and poll  |       √
+        //  - Rent should be returned to the account which paid it          |       √
+        //  - An appeal's rent should be returned to its appellant          |       √
+        require!(called_condition, CloseError::RoundNotCalled);
+        require!(self.rent_payer.key() == self.escrow.rent_payer, CloseError::WrongRentPayer);
+
+        // The appeal is no longer read once the round is called, so its rent goes back to the appellant with the rest
+        let appealed: bool = self.verdict.as_ref().is_some_and(|verdict| verdict.appealed);
+
+        match (appealed, self.appeal.as_ref(), self.appellant.as_ref()) {
+            (true, Some(appeal), Some(appellant)) => {
+                require!(appellant.key() == appeal.appellant, AppealError::WrongAppellant);
+
+                appeal.close(appellant.to_account_info())
+            },
+            (true, _, _) => err!(AppealError::AppealAccountMissing),
+            (false, _, _) => Ok(()),
+        }
+
+    }
+
+}
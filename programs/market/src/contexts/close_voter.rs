@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Appeal, Market, MarketParams, Poll, Voter};
+use crate::error::{AppealError, CloseError};
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams)]
+pub struct CloseVoter<'info_cvr> {
+    #[account(mut)]
+    pub signer: Signer<'info_cvr>,
+    #[account(
+        seeds = [b"market", params.authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_cvr, Market>,
+    #[account(
+        seeds = [b"poll", params.authensus_token.as_ref(), params.facet.to_string().as_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info_cvr, Poll>,
+    #[account(
+        seeds = [b"appeal", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub appeal: Option<Account<'info_cvr, Appeal>>,    // Only required if the voter voted in an appeal which is still open
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"voter", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), signer.key().as_ref()],
+        bump,
+    )]
+    pub voter: Account<'info_cvr, Voter>,  // Paid for by the voter when they first voted
+}
+
+impl<'info_cvr> CloseVoter<'info_cvr> {
+
+    pub fn close(&mut self) -> Result<()> {
+
+        let signer: Pubkey = self.signer.key();
+
+        // Appeal voters are recorded in the appeal poll rather than the original one, until call_market
+        // clears the appeal from the poll once every appeal vote has been consolidated
+        let (voters, voters_consolidated): (&Option<Vec<Pubkey>>, &Option<Vec<Pubkey>>) = match (self.voter.appeal, self.poll.appealed, self.appeal.as_ref()) {
+            (true, true, Some(appeal)) => (&appeal.voters, &appeal.voters_consolidated),
+            (true, true, None) => return Err(anchor_lang::error!(AppealError::AppealAccountMissing)),
+            (true, false, _) => (&None, &None),
+            (false, _, _) => (&self.poll.voters, &self.poll.voters_consolidated),
+        };
+
+        // The poll is emptied by call_market, after which every voter has already been consolidated
+        let pending: bool = voters.as_ref().is_some_and(|voters| voters.contains(&signer))
+            && !voters_consolidated.as_ref().is_some_and(|consolidated| consolidated.contains(&signer));
+
+        // Requirements:                                                    |   Implemented:
+        //  - Voter should have no votes waiting to be consolidated         |       √
+        require!(!pending, CloseError::VoterNotConsolidated);
+
+        Ok(())
+
+    }
+
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use treasury::{
+    self,
+    Treasury,
+};
+
+use crate::states::{Market, MarketConfig, MarketState};
+use crate::error::{InitError, MarketError, TokenError, TreasuryError};
+use crate::utils::functions::check_market_config;
+
+#[derive(Accounts)]
+#[instruction(authensus_token: Pubkey)]
+pub struct ConfigureMarket<'info_cm> {
+    #[account(mut)]
+    pub admin: Signer<'info_cm>,
+    #[account(
+        mut,
+        seeds = [b"market", authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_cm, Market>,
+    pub treasury: Account<'info_cm, Treasury>,     // Should already be initialised
+}
+
+impl<'info_cm> ConfigureMarket<'info_cm> {
+
+    pub fn set_config(
+        &mut self,
+        authensus_token: Pubkey,
+        config: MarketConfig,
+    ) -> Result<()> {
+
+        let state_condition: bool = self.market.state == MarketState::Initialised || self.market.state == MarketState::Inactive;
+
+        // Requirements:                                                        |   Implemented:
+        //  - The token must be the same as that which instantiated the market  |       √
+        //  - Admin should be the treasury authority                            |       √
+        //  - No round of the market should be in progress                      |       √
+        //  - Config should be within limits                                   |       √
+        //  - Outcome tokens need a market denominated in an SPL token          |       √
+        require!(self.market.token == authensus_token, TokenError::NotTheSameToken);
+        require!(self.admin.key() == self.treasury.authority, TreasuryError::WrongTreasuryAuthority);
+        require!(state_condition, MarketError::MarketInWrongState);
+
+        check_market_config(&config)?;
+
+        require!(!config.outcome_tokens || self.market.stake_mint.is_some(), InitError::OutcomeTokensNeedStakeMint);
+
+        self.market.config = config;
+
+        Ok(())
+
+    }
+
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{LmsrPool, MarketParams};
+use crate::utils::functions::lmsr_prices;
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams, round: u16)]
+pub struct GetLmsrPrices<'info_lp> {
+    #[account(
+        seeds = [b"lmsr", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pool: Account<'info_lp, LmsrPool>,
+}
+
+impl<'info_lp> GetLmsrPrices<'info_lp> {
+
+    pub fn get_prices(&self) -> Result<Vec<u16>> {
+
+        // Read-only; Anchor sets the serialised prices as the return data for CPI callers
+        Ok(lmsr_prices(&self.pool.shares, self.pool.liquidity))
+
+    }
+
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{BetType, Escrow, Market, MarketParams, MarketState, Quote};
+use crate::error::{BettingError, FacetError, TokenError};
+use crate::utils::functions::{quote_bet, time_weight, Stakes};
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams)]
+pub struct GetQuote<'info_q> {
+    #[account(
+        seeds = [b"market", params.authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_q, Market>,
+    #[account(
+        seeds = [b"escrow", params.authensus_token.as_ref(), params.facet.to_string().as_bytes()],
+        bump,
+    )]
+    pub escrow: Account<'info_q, Escrow>,
+}
+
+impl<'info_q> GetQuote<'info_q> {
+
+    pub fn get_quote(
+        &self,
+        params: &MarketParams,
+        amount: u64,
+        outcome: u8,
+        bet_type: BetType,
+    ) -> Result<Quote> {
+
+        // Requirements:                                                        |   Implemented:
+        //  - Market should be in a betting state                               |       √
+        //  - Market should contain the given facet                             |       √
+        //  - The outcome must exist for the given facet                        |       √
+        //  - The token must be the same as that which instantiated the market  |       √
+        require!(self.market.state == MarketState::Betting, BettingError::MarketNotInBettingState);
+        require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
+        require!(outcome < params.facet.outcomes(), FacetError::OutcomeOutOfRange);
+        require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
+
+        let time: i64 = Clock::get()?.unix_timestamp;
+
+        // Read-only; Anchor sets the serialised quote as the return data for CPI callers
+        Ok(quote_bet(
+            outcome,
+            amount,
+            time_weight(&self.market, time),
+            self.market.config.underdog_bonus.into(),
+            bet_type,
+            &Stakes {
+                outcomes: &self.escrow.tot_outcomes,
+                underdog: self.escrow.tot_underdog,
+                weighted_outcomes: &self.escrow.weighted_outcomes,
+                weighted_underdog: self.escrow.weighted_underdog,
+            },
+        ))
+
+    }
+
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{MarketParams, Verdict};
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams, round: u16)]
+pub struct GetVerdict<'info_gv> {
+    #[account(
+        seeds = [b"verdict", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub verdict: Account<'info_gv, Verdict>,
+}
+
+impl<'info_gv> GetVerdict<'info_gv> {
+
+    pub fn get_verdict(&self) -> Result<Verdict> {
+
+        // Read-only; Anchor sets the serialised verdict as the return data for CPI callers
+        Ok(self.verdict.clone().into_inner())
+
+    }
+
+}
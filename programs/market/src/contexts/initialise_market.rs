@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
 
-use crate::states::{Facet, Market, MarketState};
-use crate::constants::{MIN_ALLOWED_TIMEOUT, MAX_ALLOWED_TIMEOUT};
+use crate::states::{ContentScore, Facet, FacetWeight, Market, MarketConfig, MarketState};
+use crate::constants::{DEFAULT_FACET_WEIGHT, SOL_DECIMALS, MIN_ALLOWED_TIMEOUT, MAX_ALLOWED_TIMEOUT};
 use crate::error::InitError;
+use crate::utils::functions::check_market_config;
 
 #[derive(Accounts)]
 #[instruction(authensus_token: Pubkey)]
@@ -17,6 +19,15 @@ pub struct InitialiseMarket<'info_i> {
         bump,
     )]
     pub market: Account<'info_i, Market>,
+    #[account(
+        init,
+        space = 8 + ContentScore::INIT_SPACE,
+        payer = admin,
+        seeds = [b"score", authensus_token.as_ref()],
+        bump,
+    )]
+    pub content_score: Account<'info_i, ContentScore>,
+    pub stake_mint: Option<Account<'info_i, Mint>>,     // Only provided for markets denominated in an SPL token
     pub system_program: Program<'info_i, System>,
 }
 
@@ -28,16 +39,45 @@ impl<'info_i> InitialiseMarket<'info_i> {
         token: Pubkey,
         facets: Vec<Facet>,
         timeout: i64,
+        config: MarketConfig,
     ) -> Result<()> {
         
-        // Requirements:            |   Implemented:
-        //  - At least one facet    |       √
-        //  - Timeout not too large |       √
-        //  - Timeout not too small |       √
-        require!(facets.len() >= 1, InitError::NoFacetsProvided);
+        // Requirements:                        |   Implemented:
+        //  - At least one facet                |       √
+        //  - Timeout not too large             |       √
+        //  - Timeout not too small             |       √
+        //  - Config within limits              |       √
+        //  - Outcome tokens need a stake mint  |       √
+        require!(!facets.is_empty(), InitError::NoFacetsProvided);
         require!(timeout <= MAX_ALLOWED_TIMEOUT, InitError::TimeoutTooLarge);
         require!(timeout >= MIN_ALLOWED_TIMEOUT, InitError::TimeoutTooSmall);
 
+        check_market_config(&config)?;
+
+        require!(!config.outcome_tokens || self.stake_mint.is_some(), InitError::OutcomeTokensNeedStakeMint);
+
+        // Every facet counts equally towards the composite score until configured otherwise
+        let weights: Vec<FacetWeight> = facets.iter()
+            .map(|facet| FacetWeight { facet: facet.clone(), weight: DEFAULT_FACET_WEIGHT })
+            .collect();
+
+        self.content_score.set_inner(
+            ContentScore {
+                bump: bumps.content_score,          // u8
+                token,                              // Pubkey
+                weights,                            // Vec<FacetWeight>
+                facet_scores: Vec::new(),           // Vec<FacetScore>
+                score: 0_u16,                       // u16
+                history: Vec::new(),                // Vec<ScoreRecord>
+                head: 0_u16,                        // u16
+                updated_at: 0_i64,                  // i64
+            }
+        );
+
+        // Markets are denominated in native SOL unless a stake mint is provided
+        let stake_mint: Option<Pubkey> = self.stake_mint.as_ref().map(|mint| mint.key());
+        let stake_decimals: u8 = self.stake_mint.as_ref().map_or(SOL_DECIMALS, |mint| mint.decimals);
+
         self.market.set_inner(
             Market {
                 bump: bumps.market,                 // u8
@@ -45,8 +85,12 @@ impl<'info_i> InitialiseMarket<'info_i> {
                 facets,                             // Vec<Facet>
                 start_time: 0_i64,                  // i64
                 timeout,                            // i64
+                extension: 0_i64,                   // i64
                 state: MarketState::Initialised,    // MarketState
                 round: 0_u16,                       // u16
+                config,                             // MarketConfig
+                stake_mint,                         // Option<Pubkey>
+                stake_decimals,                     // u8
             }
         );
 
@@ -5,6 +5,25 @@ pub mod vote;
 pub mod wager_results;
 pub mod voter_results;
 pub mod call_market;
+pub mod open_appeal;
+pub mod resolve_appeal;
+pub mod get_verdict;
+pub mod set_score_weights;
+pub mod configure_market;
+pub mod withdraw_wager;
+pub mod get_quote;
+pub mod tokenise_position;
+pub mod redeem_outcome_tokens;
+pub mod open_lmsr;
+pub mod trade_lmsr;
+pub mod redeem_lmsr;
+pub mod sweep_lmsr;
+pub mod get_lmsr_prices;
+pub mod close_bettor;
+pub mod close_voter;
+pub mod close_round;
+pub mod batch_wager_results;
+pub mod batch_voter_results;
 
 pub use initialise_market::*;
 pub use start_market::*;
@@ -13,3 +32,22 @@ pub use vote::*;
 pub use wager_results::*;
 pub use voter_results::*;
 pub use call_market::*;
+pub use open_appeal::*;
+pub use resolve_appeal::*;
+pub use get_verdict::*;
+pub use set_score_weights::*;
+pub use configure_market::*;
+pub use withdraw_wager::*;
+pub use get_quote::*;
+pub use tokenise_position::*;
+pub use redeem_outcome_tokens::*;
+pub use open_lmsr::*;
+pub use trade_lmsr::*;
+pub use redeem_lmsr::*;
+pub use sweep_lmsr::*;
+pub use get_lmsr_prices::*;
+pub use close_bettor::*;
+pub use close_voter::*;
+pub use close_round::*;
+pub use batch_wager_results::*;
+pub use batch_voter_results::*;
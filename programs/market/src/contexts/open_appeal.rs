@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+
+use treasury::{
+    cpi::{accounts::Accrue, accrue},
+    program::TreasuryProgram,
+    self,
+    LedgerPurpose,
+    Treasury,
+};
+
+use crate::states::{Appeal, Market, MarketParams, Poll};
+use crate::constants::{APPEAL_WINDOW, MIN_APPEAL_BOND, VOTE_THRESHOLD};
+use crate::error::{AppealError, BettingError, FacetError, TokenError, TreasuryError};
+use crate::utils::functions::poll_outcome;
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams)]
+pub struct OpenAppeal<'info_a> {
+    #[account(mut)]
+    pub treasury_auth: Signer<'info_a>,
+    #[account(mut)]
+    pub signer: Signer<'info_a>,
+    #[account(
+        seeds = [b"market", params.authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_a, Market>,
+    #[account(
+        mut,
+        seeds = [b"poll", params.authensus_token.as_ref(), params.facet.to_string().as_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info_a, Poll>,
+    #[account(
+        init_if_needed,
+        space = 8 + Appeal::INIT_SPACE,
+        payer = signer,
+        seeds = [b"appeal", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub appeal: Account<'info_a, Appeal>,
+    #[account(mut)]
+    pub treasury: Account<'info_a, Treasury>,       // Should already be initialised
+    /// CHECK: Created and checked by the treasury program, which tracks what the round owes
+    #[account(
+        mut,
+        seeds = [b"payable", market.key().as_ref(), market.round.to_le_bytes().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub payable: UncheckedAccount<'info_a>,
+    /// CHECK: Created and checked by the treasury program, which keeps the round's share of its balance
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), LedgerPurpose::Bonds.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub ledger: UncheckedAccount<'info_a>,
+    pub treasury_program: Program<'info_a, TreasuryProgram>,
+    pub system_program: Program<'info_a, System>,
+}
+
+impl<'info_a> OpenAppeal<'info_a> {
+
+    pub fn open_appeal(
+        &mut self,
+        bumps: &OpenAppealBumps,
+        params: &MarketParams,
+        bond: u64,
+        outcome: u8,
+    ) -> Result<()> {
+
+        let time: i64 = Clock::get()?.unix_timestamp;
+
+        let poll_outcome: Option<u8> = poll_outcome(&params.facet, &self.poll.total_outcomes, &self.poll.value_weights);
+
+        // Requirements:                                                        |   Implemented:
+        //  - Market should contain the given facet                             |       √
+        //  - The outcome must exist for the given facet                        |       √
+        //  - The token must be the same as that which instantiated the market  |       √
+        //  - Treasury authority should be the same as treasury_auth            |       √
+        //  - The poll must have reached its threshold                          |       √
+        //  - The poll must not have been tied                                  |       √
+        //  - The appeal must argue for a different outcome to the poll         |       √
+        //  - The poll must not already have been appealed                      |       √
+        //  - The appeal window must still be open                              |       √
+        //  - The bond must be at least the minimum                             |       √
+        //  - Appellant should have sufficient funds to post the bond           |       √
+        require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
+        require!(outcome < params.facet.outcomes(), FacetError::OutcomeOutOfRange);
+        require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
+        require!(self.treasury_auth.key() == self.treasury.authority, TreasuryError::TreasuryAuthoritiesDontMatch);
+        require!(self.poll.total_outcomes.iter().sum::<u64>() >= VOTE_THRESHOLD.into(), AppealError::PollNotClosed);
+        require!(poll_outcome.is_some(), AppealError::CannotAppealTie);
+        require!(poll_outcome != Some(outcome), AppealError::SameOutcomeAsPoll);
+        require!(!self.poll.appealed, AppealError::AlreadyAppealed);
+        require!(self.poll.closed_at + APPEAL_WINDOW >= time, AppealError::AppealWindowClosed);
+        require!(bond >= MIN_APPEAL_BOND, AppealError::BondTooLow);
+        require!(self.signer.get_lamports() > bond, BettingError::InsufficientFunds);
+
+        self.accrue_payable(params, LedgerPurpose::Bonds, bond)?;
+
+        let outcomes: usize = params.facet.outcomes().into();
+        let points: usize = params.facet.scalar_range().map_or(0, |range| range.points());
+
+        self.appeal.set_inner(
+            Appeal {
+                bump: bumps.appeal,                // u8
+                market: params.authensus_token,    // Pubkey
+                facet: params.facet.clone(),       // Facet
+                round: self.market.round,          // u16
+                appellant: self.signer.key(),      // Pubkey
+                bond,                              // u64
+                outcome,                           // u8
+                opened_at: time,                   // i64
+                closed_at: 0_i64,                  // i64
+                voters: None,                      // Option<Vec<Pubkey>>
+                voters_consolidated: None,         // Option<Vec<Pubkey>>
+                total_outcomes: vec![0; outcomes], // Vec<u64>
+                value_weights: vec![0; points],    // Vec<u64>
+                resolved: false,                   // bool
+                overturned: false,                 // bool
+            }
+        );
+
+        self.poll.appealed = true;
+
+        Ok(())
+
+    }
+
+    fn accrue_payable(
+        &self,
+        params: &MarketParams,
+        purpose: LedgerPurpose,
+        amount: u64,
+    ) -> Result<()> {
+
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = Accrue {
+            payer: self.signer.to_account_info(),
+            caller: self.market.to_account_info(),
+            treasury: self.treasury.to_account_info(),
+            payable: self.payable.to_account_info(),
+            ledger: self.ledger.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        };
+
+        // The treasury takes the lamports and owes them back out to the round's participants
+        // The market signs as its PDA, so only this program can add to what the round owes
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        accrue(cpi_ctx, self.market.signer(), self.market.round, params.facet.to_string(), purpose, amount)
+
+    }
+
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+
+use treasury::{
+    cpi::{accounts::Subsidise, fund_subsidy},
+    program::TreasuryProgram,
+    self,
+    LedgerPurpose,
+    Treasury,
+};
+
+use crate::states::{LmsrPool, Market, MarketParams, MarketState};
+use crate::constants::{MAX_LMSR_LIQUIDITY, MIN_LMSR_LIQUIDITY};
+use crate::error::{BettingError, FacetError, LmsrError, TokenError, TreasuryError};
+use crate::utils::functions::lmsr_subsidy;
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams)]
+pub struct OpenLmsr<'info_ol> {
+    #[account(mut)]
+    pub treasury_auth: Signer<'info_ol>,
+    #[account(
+        seeds = [b"market", params.authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_ol, Market>,
+    #[account(
+        init,
+        space = 8 + LmsrPool::INIT_SPACE,
+        payer = treasury_auth,
+        seeds = [b"lmsr", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pool: Account<'info_ol, LmsrPool>,
+    #[account(mut)]
+    pub treasury: Account<'info_ol, Treasury>,      // Should already be initialised
+    /// CHECK: Checked by the treasury program, which lends the subsidy out of its reserve
+    #[account(
+        mut,
+        seeds = [b"ledger".as_ref(), b"reserve".as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub reserve: UncheckedAccount<'info_ol>,
+    /// CHECK: Created and checked by the treasury program, which records the subsidy against the round
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), LedgerPurpose::Subsidy.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub subsidy_ledger: UncheckedAccount<'info_ol>,
+    pub treasury_program: Program<'info_ol, TreasuryProgram>,
+    pub system_program: Program<'info_ol, System>,
+}
+
+impl<'info_ol> OpenLmsr<'info_ol> {
+
+    pub fn open(
+        &mut self,
+        bumps: &OpenLmsrBumps,
+        params: &MarketParams,
+        liquidity: u64,
+    ) -> Result<()> {
+
+        let time: i64 = Clock::get()?.unix_timestamp;
+
+        // Requirements:                                                        |   Implemented:
+        //  - Market should be in a betting state and not yet timed out         |       √
+        //  - Market should contain the given facet                             |       √
+        //  - The token must be the same as that which instantiated the market  |       √
+        //  - Market should be denominated in SOL                               |       √
+        //  - Liquidity should be within limits                                 |       √
+        //  - Treasury authority should be the same as treasury_auth            |       √
+        require!(self.market.state == MarketState::Betting && self.market.betting_deadline() >= time, BettingError::MarketNotInBettingState);
+        require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
+        require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
+        require!(self.market.stake_mint.is_none(), LmsrError::NotASolMarket);
+        require!((MIN_LMSR_LIQUIDITY..=MAX_LMSR_LIQUIDITY).contains(&liquidity), LmsrError::InvalidLiquidity);
+        require!(self.treasury_auth.key() == self.treasury.authority, TreasuryError::TreasuryAuthoritiesDontMatch);
+
+        // The treasury covers the maker's worst-case loss up front
+        let subsidy: u64 = lmsr_subsidy(liquidity, params.facet.outcomes());
+
+        self.seed_pool(params, subsidy)?;
+
+        self.pool.set_inner(
+            LmsrPool {
+                bump: bumps.pool,                                       // u8
+                market: params.authensus_token,                         // Pubkey
+                facet: params.facet.clone(),                            // Facet
+                round: self.market.round,                               // u16
+                liquidity,                                              // u64
+                subsidy,                                                // u64
+                shares: vec![0; params.facet.outcomes().into()],        // Vec<u64>
+                opened_at: time,                                        // i64
+            }
+        );
+
+        Ok(())
+
+    }
+
+    fn seed_pool(&self, params: &MarketParams, amount: u64) -> Result<()> {
+
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = Subsidise {
+            payer: self.treasury_auth.to_account_info(),
+            authority: self.treasury_auth.to_account_info(),
+            caller: self.market.to_account_info(),
+            maker: self.pool.to_account_info(),
+            treasury: self.treasury.to_account_info(),
+            reserve: self.reserve.to_account_info(),
+            subsidy_ledger: self.subsidy_ledger.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        };
+
+        // The subsidy is lent out of the treasury's reserve, and the market signs as its PDA to tie it to the round
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        fund_subsidy(cpi_ctx, self.market.signer(), self.market.round, params.facet.to_string(), amount)
+
+    }
+
+}
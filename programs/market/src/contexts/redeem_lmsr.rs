@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{LmsrPool, LmsrPosition, MarketParams, Verdict};
+use crate::error::{FacetError, LmsrError, ResultsError, TokenError};
+use crate::utils::functions::lmsr_payout;
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams, round: u16)]
+pub struct RedeemLmsr<'info_rl> {
+    #[account(mut)]
+    pub signer: Signer<'info_rl>,
+    #[account(
+        seeds = [b"verdict", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub verdict: Account<'info_rl, Verdict>,    // Only exists once the round has been called
+    #[account(
+        mut,
+        seeds = [b"lmsr", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pool: Account<'info_rl, LmsrPool>,
+    #[account(
+        mut,
+        seeds = [b"lmsr_position", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), round.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub position: Account<'info_rl, LmsrPosition>,
+}
+
+impl<'info_rl> RedeemLmsr<'info_rl> {
+
+    pub fn redeem(
+        &mut self,
+        params: &MarketParams,
+    ) -> Result<()> {
+
+        // Requirements:                                                        |   Implemented:
+        //  - The token must be the same as that which instantiated the market  |       √
+        //  - Verdict and pool should be for the given facet                    |       √
+        //  - Signer should be the holder of the position                       |       √
+        //  - Position should hold some shares                                  |       √
+        require!(self.verdict.market == params.authensus_token && self.pool.market == params.authensus_token, TokenError::NotTheSameToken);
+        require!(self.verdict.facet == params.facet && self.pool.facet == params.facet, FacetError::NotTheSameFacet);
+        require!(self.position.pk == self.signer.key(), ResultsError::SignerNotPK);
+        require!(self.position.shares.iter().any(|shares| *shares > 0), LmsrError::NoShares);
+
+        // Shares are settled against the same poll-based resolution as the pari-mutuel pools
+        let payout: u64 = lmsr_payout(&self.position.shares, self.verdict.outcome);
+
+        // Redeemed shares no longer count towards what the pool owes
+        for (pool_shares, shares) in self.pool.shares.iter_mut().zip(self.position.shares.iter_mut()) {
+            *pool_shares -= *shares;
+            *shares = 0;
+        }
+
+        self.pay_from_pool(payout)
+
+    }
+
+    fn pay_from_pool(&self, amount: u64) -> Result<()> {
+
+        let pool: AccountInfo<'info_rl> = self.pool.to_account_info();
+        let rent: u64 = Rent::get()?.minimum_balance(pool.data_len());
+
+        require!(pool.lamports() >= rent + amount, LmsrError::InsufficientPoolFunds);
+
+        // The pool is owned by this program, so its lamports can be moved directly
+        **pool.try_borrow_mut_lamports()? -= amount;
+        **self.signer.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        Ok(())
+
+    }
+
+}
@@ -0,0 +1,309 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token::{self, Burn, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use treasury::{
+    cpi::{accounts::Reimburse, reimburse},
+    program::TreasuryProgram,
+    self,
+    LedgerPurpose,
+    Treasury,
+};
+
+use voting_tokens::{
+    cpi::{accounts::MintTokens, mint_tokens},
+    self,
+    program::VotingTokens,
+};
+
+use crate::states::{Market, MarketParams, Verdict};
+use crate::constants::{PERCENTAGE_WINNINGS_KEPT, TREASURY_PROGRAM_ID, VOTING_TOKENS_MINT_ID, VOTING_TOKENS_PROGRAM_ID};
+use crate::error::{FacetError, MintError, OutcomeTokenError, StakeError, TokenError, TreasuryError, VotingError};
+use crate::utils::functions::{calc_bond_share, compute_returns, final_pools, scale_decimals, Stakes};
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams, round: u16, outcome: u8)]
+pub struct RedeemOutcomeTokens<'info_ro> {
+    #[account(mut)]
+    pub signer: Signer<'info_ro>,
+    #[account(
+        seeds = [b"market", params.authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_ro, Market>,
+    /// CHECK: Only signs for the vault, which outlives the escrow account once close_round has closed it
+    #[account(
+        seeds = [b"escrow", params.authensus_token.as_ref(), params.facet.to_string().as_bytes()],
+        bump,
+    )]
+    pub escrow: UncheckedAccount<'info_ro>,
+    #[account(
+        seeds = [b"verdict", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub verdict: Account<'info_ro, Verdict>,   // Only exists once the round has been called
+    #[account(
+        mut,
+        seeds = [b"outcome", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), round.to_le_bytes().as_ref(), [outcome].as_ref()],
+        bump,
+    )]
+    pub outcome_mint: Account<'info_ro, Mint>,
+    #[account(
+        mut,
+        token::mint = outcome_mint,
+        token::authority = signer,
+    )]
+    pub holder_account: Account<'info_ro, TokenAccount>,
+    pub stake_mint: Account<'info_ro, Mint>,
+    #[account(
+        mut,
+        seeds = [b"vault", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info_ro, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = signer,
+    )]
+    pub stake_account: Account<'info_ro, TokenAccount>,
+    #[account(mut)]
+    pub mint: Account<'info_ro, Mint>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+    )]
+    pub recipient: Account<'info_ro, TokenAccount>,
+    #[account(mut)]
+    pub treasury: Account<'info_ro, Treasury>,
+    pub treasury_program: Program<'info_ro, TreasuryProgram>,
+    /// CHECK: Checked by the treasury program, which tracks what the round owes
+    #[account(
+        mut,
+        seeds = [b"payable", market.key().as_ref(), round.to_le_bytes().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub payable: UncheckedAccount<'info_ro>,
+    /// CHECK: Checked by the treasury program, which keeps the round's appeal bonds
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), round.to_le_bytes().as_ref(), LedgerPurpose::Bonds.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub bonds_ledger: UncheckedAccount<'info_ro>,
+    /// CHECK: Created by the treasury program only if the payout falls short
+    #[account(
+        mut,
+        seeds = [b"iou", market.key().as_ref(), round.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub iou: UncheckedAccount<'info_ro>,
+    pub voting_tokens_program: Program<'info_ro, VotingTokens>,
+    pub token_program: Program<'info_ro, Token>,
+    pub associated_token_program: Program<'info_ro, AssociatedToken>,
+    pub system_program: Program<'info_ro, System>,
+    pub rent: Sysvar<'info_ro, Rent>,
+}
+
+impl<'info_ro> RedeemOutcomeTokens<'info_ro> {
+
+    pub fn redeem(
+        &mut self,
+        bumps: &RedeemOutcomeTokensBumps,
+        params: &MarketParams,
+        round: u16,
+        outcome: u8,
+        amount: u64,
+    ) -> Result<()> {
+
+        let mint_pk: Pubkey = Pubkey::from_str(VOTING_TOKENS_MINT_ID).unwrap();
+        let mint_program_pk: Pubkey = Pubkey::from_str(VOTING_TOKENS_PROGRAM_ID).unwrap();
+
+        let signer_ata: Pubkey = get_associated_token_address_with_program_id(
+             &self.signer.key(),
+             &mint_pk,
+             &mint_program_pk,
+        );
+
+        // Requirements:                                                        |   Implemented:
+        //  - The token must be the same as that which instantiated the market  |       √
+        //  - The outcome must exist for the given facet                        |       √
+        //  - Stake mint should be the one the market is denominated in         |       √
+        //  - Amount should be non-zero                                         |       √
+        //  - ATA needs to be correct                                           |       √
+        //  - Mint account ID needs to be correct                               |       √
+        //  - Voting Tokens Program needs to be correct                         |       √
+        //  - Treasury Program needs to be correct                              |       √
+        require!(self.market.token == params.authensus_token && self.verdict.market == params.authensus_token, TokenError::NotTheSameToken);
+        require!(outcome < params.facet.outcomes(), FacetError::OutcomeOutOfRange);
+        require!(self.market.stake_mint == Some(self.stake_mint.key()), StakeError::StakeAccountsMissing);
+        require!(amount > 0, OutcomeTokenError::NothingToRedeem);
+        require!(signer_ata == self.recipient.key(), VotingError::IncorrectATA);
+        require!(self.mint.key() == mint_pk, MintError::NotTheRightMintPK);
+        require!(self.voting_tokens_program.key().to_string() == VOTING_TOKENS_PROGRAM_ID, MintError::NotTheRightMintProgramPK);
+        require!(self.treasury_program.key().to_string() == TREASURY_PROGRAM_ID, TreasuryError::NotTheRightTreasuryProgramPK);
+
+        // Outcome tokens are burnt whether they won or not
+        self.burn_outcome_tokens(amount)?;
+
+        match self.verdict.outcome {
+            // A tie returns every stake
+            None => self.return_stake(params, bumps.escrow, amount),
+            Some(winner) if winner == outcome => {
+                let mut position: Vec<u64> = vec![0; self.verdict.pool_outcomes.len()];
+                position[outcome as usize] = amount;
+
+                // Tokenised stakes carry no time weighting
+                let (bet_returned, winnings_pre) = compute_returns(
+                    outcome,
+                    &Stakes {
+                        outcomes: &self.verdict.pool_outcomes,
+                        underdog: self.verdict.pool_underdog,
+                        weighted_outcomes: &self.verdict.pool_weighted_outcomes,
+                        weighted_underdog: self.verdict.pool_weighted_underdog,
+                    },
+                    &Stakes {
+                        outcomes: &position,
+                        underdog: 0,
+                        weighted_outcomes: &position,
+                        weighted_underdog: 0,
+                    },
+                    self.market.config.underdog_bonus.into(),
+                );
+
+                let winnings: u64 = scale_decimals(
+                    (PERCENTAGE_WINNINGS_KEPT*winnings_pre)/100,
+                    self.market.stake_decimals,
+                    self.mint.decimals,
+                );
+
+                // Holders share a failed appeal's bond like any other winning bettor, pro rata to the stake returned
+                let pool: u64 = final_pools(&self.verdict.pool_outcomes, self.verdict.pool_underdog)[outcome as usize];
+                let bond_share: u64 = calc_bond_share(self.verdict.forfeited_bond, bet_returned, pool);
+
+                self.return_stake(params, bumps.escrow, bet_returned)?;
+
+                if bond_share > 0 {
+                    self.reimburse_bond_share(params, round, bond_share)?;
+                }
+
+                self.mint_voting_tokens_to_holder(winnings)
+            },
+            Some(_) => Ok(()),
+        }
+
+    }
+
+    fn burn_outcome_tokens(&self, amount: u64) -> Result<()> {
+
+        let accounts = Burn {
+            mint: self.outcome_mint.to_account_info(),
+            from: self.holder_account.to_account_info(),
+            authority: self.signer.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), accounts);
+
+        token::burn(cpi_ctx, amount)
+
+    }
+
+    fn return_stake(&self, params: &MarketParams, escrow_bump: u8, amount: u64) -> Result<()> {
+
+        let facet: String = params.facet.to_string();
+        let seeds: &[&[u8]] = &[
+            b"escrow",
+            params.authensus_token.as_ref(),
+            facet.as_bytes(),
+            &[escrow_bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.stake_mint.to_account_info(),
+            to: self.stake_account.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+
+        token::transfer_checked(cpi_ctx, amount, self.market.stake_decimals)
+
+    }
+
+    fn reimburse_bond_share(
+        &self,
+        params: &MarketParams,
+        round: u16,
+        amount: u64,
+    ) -> Result<()> {
+
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = Reimburse {
+            payer: self.signer.to_account_info(),
+            caller: self.market.to_account_info(),
+            coparty: self.signer.to_account_info(),     // This needs to be the holder
+            treasury: self.treasury.to_account_info(),
+            payable: self.payable.to_account_info(),
+            ledger: self.bonds_ledger.to_account_info(),
+            iou: self.iou.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        };
+
+        // Bonds are always in SOL, so they are paid out by the treasury rather than the vault
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        reimburse(
+            cpi_ctx,
+            self.market.signer(),
+            round,
+            params.facet.to_string(),
+            LedgerPurpose::Bonds,
+            amount,
+            self.verdict.forfeited_bond,
+        )
+
+    }
+
+    fn mint_voting_tokens_to_holder(&self, winnings: u64) -> Result<()> {
+
+        let accounts: MintTokens<'_> = MintTokens{
+            payer: self.signer.to_account_info(),
+            mint: self.mint.to_account_info(),
+            recipient: self.recipient.to_account_info(),
+            associated_token_program: self.associated_token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+        };
+
+        // No PDAs required for the CPI, so we use new() and not new_with_signer()
+        let cpi_ctx: CpiContext<'_, '_, '_, '_, MintTokens<'_>> = CpiContext::new(
+            self.voting_tokens_program.to_account_info(),
+            accounts,
+        );
+
+        mint_tokens(
+            cpi_ctx,
+            winnings,
+        )
+
+    }
+
+}
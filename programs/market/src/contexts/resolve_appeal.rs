@@ -0,0 +1,221 @@
+use std::str::FromStr;
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token::TokenAccount,
+};
+
+use treasury::{
+    cpi::{accounts::{CollectFee, Reimburse}, collect_fee, reimburse},
+    program::TreasuryProgram,
+    self,
+    LedgerPurpose,
+    Treasury,
+};
+
+use crate::constants::{APPEAL_VOTE_THRESHOLD, TREASURY_PROGRAM_ID, VOTING_TOKENS_MINT_ID, VOTING_TOKENS_PROGRAM_ID};
+use crate::error::{AppealError, FacetError, TokenError, TreasuryError, VotingError};
+use crate::states::{Appeal, Escrow, Market, MarketParams, Poll};
+use crate::utils::functions::{final_outcome, poll_outcome, round_claims, winning_pool};
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams)]
+pub struct ResolveAppeal<'info_ra> {
+    #[account(mut)]
+    pub treasury_auth: Signer<'info_ra>,
+    #[account(mut)]
+    pub signer: Signer<'info_ra>,
+    #[account(
+        seeds = [b"market", params.authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_ra, Market>,
+    #[account(
+        seeds = [b"poll", params.authensus_token.as_ref(), params.facet.to_string().as_bytes()],
+        bump,
+    )]
+    pub poll: Account<'info_ra, Poll>,
+    #[account(
+        seeds = [b"escrow", params.authensus_token.as_ref(), params.facet.to_string().as_bytes()],
+        bump,
+    )]
+    pub escrow: Account<'info_ra, Escrow>,
+    #[account(
+        mut,
+        seeds = [b"appeal", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub appeal: Account<'info_ra, Appeal>,
+    /// CHECK: Only receives the bond of a successful appeal, and must be the appellant who posted it
+    #[account(mut)]
+    pub appellant: UncheckedAccount<'info_ra>,
+    #[account(mut)]
+    pub treasury_voting_token_account: Account<'info_ra, TokenAccount>, // The treasury PDA's ATA, initialised with the treasury
+    #[account(mut)]
+    pub treasury: Account<'info_ra, Treasury>,
+    pub treasury_program: Program<'info_ra, TreasuryProgram>,
+    /// CHECK: Checked by the treasury program, which tracks what the round owes
+    #[account(
+        mut,
+        seeds = [b"payable", market.key().as_ref(), market.round.to_le_bytes().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub payable: UncheckedAccount<'info_ra>,
+    /// CHECK: Checked by the treasury program, which keeps the round's appeal bonds
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), LedgerPurpose::Bonds.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub ledger: UncheckedAccount<'info_ra>,
+    /// CHECK: Created and checked by the treasury program, which keeps a forfeited bond that no bettor can claim
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), LedgerPurpose::Fees.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub fee_ledger: UncheckedAccount<'info_ra>,
+    /// CHECK: Created by the treasury program only if the payout falls short
+    #[account(
+        mut,
+        seeds = [b"iou", market.key().as_ref(), market.round.to_le_bytes().as_ref(), appellant.key().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub iou: UncheckedAccount<'info_ra>,
+    pub associated_token_program: Program<'info_ra, AssociatedToken>,
+    pub system_program: Program<'info_ra, System>,
+}
+
+impl<'info_ra> ResolveAppeal<'info_ra> {
+
+    pub fn resolve(
+        &mut self,
+        params: &MarketParams,
+    ) -> Result<()> {
+
+        let mint_pk: Pubkey = Pubkey::from_str(VOTING_TOKENS_MINT_ID).unwrap();
+        let mint_program_pk: Pubkey = Pubkey::from_str(VOTING_TOKENS_PROGRAM_ID).unwrap();
+
+        let treasury_ata: Pubkey = get_associated_token_address_with_program_id(
+            &self.treasury.key(),
+            &mint_pk,
+            &mint_program_pk,
+        );
+
+        // Requirements:                                                                |   Implemented:
+        //  - Market should contain the given facet                                     |       √
+        //  - The token must be the same as that which instantiated the market          |       √
+        //  - Treasury authority should be the same as treasury_auth                    |       √
+        //  - Treasury Program needs to be correct                                      |       √
+        //  - treasury_voting_token_account should be derivable from the treasury PDA   |       √
+        //  - The poll must have been appealed in this round                            |       √
+        //  - The appeal must not have been resolved already                            |       √
+        //  - The appeal poll must have reached its threshold                           |       √
+        //  - Appellant should be the one who posted the bond                           |       √
+        require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
+        require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
+        require!(self.treasury_auth.key() == self.treasury.authority, TreasuryError::TreasuryAuthoritiesDontMatch);
+        require!(self.treasury_program.key().to_string() == TREASURY_PROGRAM_ID, TreasuryError::NotTheRightTreasuryProgramPK);
+        require!(treasury_ata == self.treasury_voting_token_account.key(), VotingError::IncorrectTreasuryATA);
+        require!(self.poll.appealed && self.appeal.round == self.market.round, AppealError::WrongRound);
+        require!(!self.appeal.resolved, AppealError::AppealAlreadyResolved);
+        require!(self.appeal.total_outcomes.iter().sum::<u64>() >= APPEAL_VOTE_THRESHOLD.into(), AppealError::AppealNotFinished);
+        require!(self.appellant.key() == self.appeal.appellant, AppealError::WrongAppellant);
+
+        // Anyone can resolve the appeal so the round isn't held up, but the bond only ever goes back to the appellant.
+        // The appellant's outcome must be the outright winner of the appeal poll to override the original outcome
+        let appeal_outcome: Option<u8> = poll_outcome(&params.facet, &self.appeal.total_outcomes, &self.appeal.value_weights);
+        let overturned: bool = appeal_outcome == Some(self.appeal.outcome);
+
+        self.appeal.resolved = true;
+        self.appeal.overturned = overturned;
+
+        // A failed appeal's bond stays in the treasury and is paid out to the winning bettors in wager_results,
+        // unless nobody backed the winning outcome, in which case the treasury keeps it as a fee
+        if !overturned {
+            let pool: u64 = final_outcome(&self.poll, Some(&*self.appeal)).map_or(0, |outcome| winning_pool(&self.escrow, outcome));
+
+            return match pool {
+                0 => self.forfeit_bond(params, self.appeal.bond),
+                _ => Ok(()),
+            }
+        }
+
+        self.return_bond(params, self.appeal.bond)
+
+    }
+
+    fn return_bond(
+        &self,
+        params: &MarketParams,
+        amount: u64,
+    ) -> Result<()> {
+
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = Reimburse {
+            payer: self.signer.to_account_info(),
+            caller: self.market.to_account_info(),
+            coparty: self.appellant.to_account_info(),  // Checked above as the appellant
+            treasury: self.treasury.to_account_info(),
+            payable: self.payable.to_account_info(),
+            ledger: self.ledger.to_account_info(),
+            iou: self.iou.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        };
+
+        // The market signs as its PDA, so payouts are limited to what the round owes
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        reimburse(
+            cpi_ctx,
+            self.market.signer(),
+            self.market.round,
+            params.facet.to_string(),
+            LedgerPurpose::Bonds,
+            amount,
+            round_claims(&self.poll, Some(&*self.appeal), &self.escrow, LedgerPurpose::Bonds),
+        )
+
+    }
+
+    fn forfeit_bond(
+        &self,
+        params: &MarketParams,
+        amount: u64,
+    ) -> Result<()> {
+
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = CollectFee {
+            payer: self.signer.to_account_info(),
+            caller: self.market.to_account_info(),
+            treasury: self.treasury.to_account_info(),
+            payable: self.payable.to_account_info(),
+            ledger: self.ledger.to_account_info(),
+            fee_ledger: self.fee_ledger.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        };
+
+        // The bond moves from the round's bonds to its fees, so it is no longer owed to anyone
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        collect_fee(cpi_ctx, self.market.signer(), self.market.round, params.facet.to_string(), LedgerPurpose::Bonds, amount)
+
+    }
+
+}
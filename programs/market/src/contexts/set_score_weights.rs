@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use treasury::{
+    self,
+    Treasury,
+};
+
+use crate::states::{ContentScore, FacetWeight, Market};
+use crate::error::{FacetError, ScoreError, TokenError, TreasuryError};
+use crate::utils::functions::calc_weighted_score;
+
+#[derive(Accounts)]
+#[instruction(authensus_token: Pubkey)]
+pub struct SetScoreWeights<'info_sw> {
+    #[account(mut)]
+    pub admin: Signer<'info_sw>,
+    #[account(
+        seeds = [b"market", authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_sw, Market>,
+    #[account(
+        mut,
+        seeds = [b"score", authensus_token.as_ref()],
+        bump,
+    )]
+    pub content_score: Account<'info_sw, ContentScore>,
+    pub treasury: Account<'info_sw, Treasury>,     // Should already be initialised
+}
+
+impl<'info_sw> SetScoreWeights<'info_sw> {
+
+    pub fn set_weights(
+        &mut self,
+        authensus_token: Pubkey,
+        weights: Vec<FacetWeight>,
+    ) -> Result<()> {
+
+        let facets_condition: bool = weights.iter().all(|w| self.market.facets.contains(&w.facet));
+        let coverage_condition: bool = self.market.facets.iter().all(|f| weights.iter().any(|w| &w.facet == f));
+        let duplicates_condition: bool = weights.iter().enumerate().any(|(i, w)| weights[..i].iter().any(|v| v.facet == w.facet));
+
+        // Requirements:                                                        |   Implemented:
+        //  - The token must be the same as that which instantiated the market  |       √
+        //  - Admin should be the treasury authority                            |       √
+        //  - Every weighted facet must be in the market                        |       √
+        //  - Every facet in the market must be weighted                        |       √
+        //  - No facet can be weighted twice                                    |       √
+        //  - At least one weight must be non-zero                              |       √
+        require!(self.market.token == authensus_token && self.content_score.token == authensus_token, TokenError::NotTheSameToken);
+        require!(self.admin.key() == self.treasury.authority, TreasuryError::WrongTreasuryAuthority);
+        require!(facets_condition, FacetError::FacetNotInMarket);
+        require!(coverage_condition, ScoreError::MissingFacetWeight);
+        require!(!duplicates_condition, ScoreError::DuplicateFacetWeight);
+        require!(weights.iter().any(|w| w.weight > 0), ScoreError::AllWeightsZero);
+
+        self.content_score.weights = weights;
+
+        // Re-weight the existing facet scores straight away
+        self.content_score.score = calc_weighted_score(&self.content_score.weights, &self.content_score.facet_scores);
+        self.content_score.updated_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+
+    }
+
+}
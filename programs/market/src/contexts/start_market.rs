@@ -1,13 +1,18 @@
-use anchor_lang::{prelude::*, system_program::{Transfer, transfer}};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
 
 use treasury::{
+    cpi::{accounts::Accrue, accrue},
+    program::TreasuryProgram,
     self,
+    LedgerPurpose,
     Treasury,
 };
 
 use crate::states::{Bettor, Escrow, Market, MarketParams, MarketState, Poll};
-use crate::constants::TREASURY_AUTHORITY;
-use crate::error::{BettingError, FacetError, MarketError, TokenError, TreasuryError, VotingError};
+use crate::constants::BASIS_POINTS;
+use crate::error::{BettingError, FacetError, MarketError, StakeError, TokenError, TreasuryError, VotingError};
+use crate::utils::functions::{check_bet_limits, mul_div, stake_accounts_condition, time_weight};
 
 #[derive(Accounts)]
 #[instruction(params: MarketParams)]
@@ -48,6 +53,36 @@ pub struct StartMarket<'info_s> {
     pub initialiser: Account<'info_s, Bettor>,
     #[account(mut)]
     pub treasury: Account<'info_s, Treasury>,       // Should already be initialised
+    pub stake_mint: Option<Account<'info_s, Mint>>, // The remaining accounts are only required for markets denominated in an SPL token
+    #[account(
+        init,
+        payer = signer,
+        token::mint = stake_mint,
+        token::authority = escrow,
+        seeds = [b"vault", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), (market.round + 1).to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: Option<Account<'info_s, TokenAccount>>,
+    #[account(mut)]
+    pub stake_account: Option<Account<'info_s, TokenAccount>>,
+    pub token_program: Option<Program<'info_s, Token>>,
+    /// CHECK: Created and checked by the treasury program, which tracks what the round owes
+    #[account(
+        mut,
+        seeds = [b"payable", market.key().as_ref(), (market.round + 1).to_le_bytes().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub payable: UncheckedAccount<'info_s>,
+    /// CHECK: Created and checked by the treasury program, which keeps the round's share of its balance
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), (market.round + 1).to_le_bytes().as_ref(), LedgerPurpose::Stakes.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub ledger: UncheckedAccount<'info_s>,
+    pub treasury_program: Program<'info_s, TreasuryProgram>,
     pub system_program: Program<'info_s, System>,
 }
 
@@ -66,44 +101,55 @@ impl<'info_s> StartMarket<'info_s> {
         //  - There should be no bettors and no bets in the escrow              |       √
         //  - There should be no voters and no votes in the poll                |       √
         //  - Treasury authority should be the same as treasury_auth            |       √
-        //  - Treasury authority should be the same as on record                |       √
         require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
         require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
         require!(self.market.state == MarketState::Initialised || self.market.state == MarketState::Inactive, MarketError::MarketInWrongState);
-        require!(self.escrow.bettors == None && self.escrow.bettors_consolidated == None && self.escrow.tot_for + self.escrow.tot_against == 0, BettingError::StartingWithBetsInPlace);
-        require!(self.poll.voters == None && self.poll.voters_consolidated == None && self.poll.total_for + self.poll.total_against == 0, VotingError::StartingWithVotesInPlace);
+        require!(self.escrow.bettors.is_none() && self.escrow.bettors_consolidated.is_none() && self.escrow.tot_outcomes.iter().sum::<u64>() == 0, BettingError::StartingWithBetsInPlace);
+        require!(self.poll.voters.is_none() && self.poll.voters_consolidated.is_none() && self.poll.total_outcomes.iter().sum::<u64>() == 0, VotingError::StartingWithVotesInPlace);
         require!(self.treasury_auth.key() == self.treasury.authority, TreasuryError::TreasuryAuthoritiesDontMatch);
-        require!(self.treasury_auth.key().to_string() == TREASURY_AUTHORITY, TreasuryError::WrongTreasuryAuthority);
 
         let start_time = Clock::get()?.unix_timestamp;
 
+        // The escrow and poll are only paid for by whoever started the first round since they were last closed
+        let rent_payer: Pubkey = match self.escrow.rent_payer == Pubkey::default() {
+            true => self.signer.key(),
+            false => self.escrow.rent_payer,
+        };
+        let outcomes: usize = params.facet.outcomes().into();
+        let points: usize = params.facet.scalar_range().map_or(0, |range| range.points());
+
         self.escrow.set_inner(
             Escrow {
-                bump: bumps.escrow,             // u8
-                initialiser: self.signer.key(), // Pubkey
-                market: params.authensus_token, // Pubkey
-                facet: params.facet.clone(),    // Facet
-                bettors: None,                  // Option<Vec<Pubkey>>
-                bettors_consolidated: None,     // Option<Vec<Pubkey>>
-                tot_for: 0_u64,                 // u64
-                tot_against: 0_u64,             // u64
-                tot_underdog: 0_u64             // u64
+                bump: bumps.escrow,                   // u8
+                initialiser: self.signer.key(),       // Pubkey
+                rent_payer,                           // Pubkey
+                market: params.authensus_token,       // Pubkey
+                facet: params.facet.clone(),          // Facet
+                bettors: None,                        // Option<Vec<Pubkey>>
+                bettors_consolidated: None,           // Option<Vec<Pubkey>>
+                tot_outcomes: vec![0; outcomes],      // Vec<u64>
+                tot_underdog: 0_u64,                  // u64
+                weighted_outcomes: vec![0; outcomes], // Vec<u64>
+                weighted_underdog: 0_u64,             // u64
             }
         );
 
         self.poll.set_inner(
             Poll {
-                bump: bumps.poll,               // u8
-                market: params.authensus_token, // Pubkey
-                facet: params.facet.clone(),    // Facet
-                voters: None,                   // Option<Vec<Pubkey>>
-                voters_consolidated: None,      // Option<Vec<Pubkey>>
-                total_for: 0_u64,               // u64
-                total_against: 0_u64,           // u64
+                bump: bumps.poll,                  // u8
+                market: params.authensus_token,    // Pubkey
+                facet: params.facet.clone(),       // Facet
+                voters: None,                      // Option<Vec<Pubkey>>
+                voters_consolidated: None,         // Option<Vec<Pubkey>>
+                total_outcomes: vec![0; outcomes], // Vec<u64>
+                value_weights: vec![0; points],    // Vec<u64>
+                closed_at: 0_i64,                  // i64
+                appealed: false,                   // bool
             }
         );
 
         self.market.start_time = start_time;
+        self.market.extension = 0;
         self.market.state = MarketState::Betting;
         self.market.round += 1;
 
@@ -116,37 +162,60 @@ impl<'info_s> StartMarket<'info_s> {
         bumps: &StartMarketBumps,
         params: &MarketParams,
         amount: u64,
-        direction: bool,
+        outcome: u8,
     ) -> Result<()> {
 
+        let stake_condition: bool = self.market.stake_mint.is_none() || (self.token_program.is_some() && stake_accounts_condition(
+            self.market.stake_mint,
+            self.stake_mint.as_ref(),
+            self.vault.as_ref(),
+            self.escrow.key(),
+            self.stake_account.as_ref(),
+            self.signer.key(),
+        ));
+
+        let funds_condition: bool = match self.stake_account.as_ref() {
+            Some(stake_account) if self.market.stake_mint.is_some() => stake_account.amount >= amount,
+            _ => self.initialiser.get_lamports() > amount,
+        };
+
         // Requirements:                                                        |   Implemented:
         //  - The given facet must exist in the market                          |       √
+        //  - The outcome must exist for the given facet                        |       √
         //  - The token must be the same as that which instantiated the market  |       √
         //  - There should be no bottors and no bets in the escrow              |       √
+        //  - Stake accounts should be provided for a token market              |       √
         //  - Initialiser should have sufficient funds to make the bet          |       √
         //  - Market should now be in a betting state                           |       √
         //  - Treasury authority should be the same as treasury_auth            |       √
-        //  - Treasury authority should be the same as on record                |       √
         require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
+        require!(outcome < params.facet.outcomes(), FacetError::OutcomeOutOfRange);
         require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
-        require!(self.escrow.bettors == None && self.escrow.tot_for + self.escrow.tot_against == 0, BettingError::StartingWithBetsInPlace);
-        require!(self.initialiser.get_lamports() > amount, BettingError::InsufficientFunds);
+        require!(self.escrow.bettors.is_none() && self.escrow.tot_outcomes.iter().sum::<u64>() == 0, BettingError::StartingWithBetsInPlace);
+        require!(stake_condition, StakeError::StakeAccountsMissing);
+        require!(funds_condition, BettingError::InsufficientFunds);
         require!(self.market.state == MarketState::Betting, BettingError::MarketNotInBettingState);
         require!(self.treasury_auth.key() == self.treasury.authority, TreasuryError::TreasuryAuthoritiesDontMatch);
-        require!(self.treasury_auth.key().to_string() == TREASURY_AUTHORITY, TreasuryError::WrongTreasuryAuthority);
 
-        self.receive_sol_start(self.signer.to_account_info(), amount)?;
+        check_bet_limits(&self.market.config, amount, amount, amount)?;
 
-        let tot_for: u64 = match direction {
-            true => amount,
-            false => 0_u64
-        };
-        
-        let tot_against = amount - tot_for;
+        match self.market.stake_mint {
+            Some(_) => self.receive_token_start(amount)?,
+            None => self.accrue_payable(params, LedgerPurpose::Stakes, amount)?,
+        }
+
+        // The opening bet is placed as betting starts, so it carries the full time weight
+        let weight: u64 = time_weight(&self.market, self.market.start_time);
+
+        let mut tot_outcomes: Vec<u64> = vec![0; params.facet.outcomes().into()];
+        tot_outcomes[outcome as usize] = amount;
+
+        let mut weighted_outcomes: Vec<u64> = vec![0; params.facet.outcomes().into()];
+        weighted_outcomes[outcome as usize] = mul_div(amount, weight, BASIS_POINTS);
 
         self.escrow.bettors = Some(Vec::from([self.signer.key()]));
-        self.escrow.tot_for = tot_for;
-        self.escrow.tot_against = tot_against;
+        self.escrow.tot_outcomes = tot_outcomes.clone();
+        self.escrow.weighted_outcomes = weighted_outcomes.clone();
 
         self.initialiser.set_inner(
             Bettor {
@@ -154,26 +223,60 @@ impl<'info_s> StartMarket<'info_s> {
                 pk: self.signer.to_account_info().key(),    // Pubkey
                 market: self.escrow.market,                 // Pubkey
                 facet: self.escrow.facet.clone(),           // Facet
-                tot_for,                                    // u64
-                tot_against,                                // u64
+                tot_outcomes,                               // Vec<u64>
                 tot_underdog: 0_u64,                        // u64
+                weighted_outcomes,                          // Vec<u64>
+                weighted_underdog: 0_u64,                   // u64
             }
         );
 
         Ok(())
         
     }
+    fn receive_token_start(&self, amount: u64) -> Result<()> {
+
+        // The stake accounts have already been checked against the market's stake mint
+        let accounts = TransferChecked {
+            from: self.stake_account.as_ref().unwrap().to_account_info(),
+            mint: self.stake_mint.as_ref().unwrap().to_account_info(),
+            to: self.vault.as_ref().unwrap().to_account_info(),
+            authority: self.signer.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.as_ref().unwrap().to_account_info(), accounts);
+
+        token::transfer_checked(cpi_ctx, amount, self.market.stake_decimals)
+
+    }
+
+    fn accrue_payable(
+        &self,
+        params: &MarketParams,
+        purpose: LedgerPurpose,
+        amount: u64,
+    ) -> Result<()> {
 
-    fn receive_sol_start(&self, from: AccountInfo<'info_s>, amount: u64) -> Result<()> {
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
 
-        let accounts = Transfer {
-            from,
-            to: self.treasury_auth.to_account_info(),
+        let cpi_accounts = Accrue {
+            payer: self.signer.to_account_info(),
+            caller: self.market.to_account_info(),
+            treasury: self.treasury.to_account_info(),
+            payable: self.payable.to_account_info(),
+            ledger: self.ledger.to_account_info(),
+            system_program: self.system_program.to_account_info(),
         };
 
-        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+        // The treasury takes the lamports and owes them back out to the round's participants
+        // The market signs as its PDA, so only this program can add to what the round owes
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
 
-        transfer(cpi_ctx, amount)
+        accrue(cpi_ctx, self.market.signer(), self.market.round, params.facet.to_string(), purpose, amount)
 
     }
 
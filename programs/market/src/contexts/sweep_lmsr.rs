@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+
+use treasury::{
+    cpi::{accounts::Subsidise, return_subsidy},
+    program::TreasuryProgram,
+    self,
+    LedgerPurpose,
+    Treasury,
+};
+
+use crate::states::{LmsrPool, Market, MarketParams, Verdict};
+use crate::error::{FacetError, TokenError, TreasuryError};
+use crate::utils::functions::lmsr_payout;
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams, round: u16)]
+pub struct SweepLmsr<'info_sl> {
+    #[account(mut)]
+    pub treasury_auth: Signer<'info_sl>,
+    #[account(
+        seeds = [b"market", params.authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_sl, Market>,
+    #[account(
+        seeds = [b"verdict", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub verdict: Account<'info_sl, Verdict>,    // Only exists once the round has been called
+    #[account(
+        mut,
+        seeds = [b"lmsr", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pool: Account<'info_sl, LmsrPool>,
+    #[account(mut)]
+    pub treasury: Account<'info_sl, Treasury>,      // Should already be initialised
+    /// CHECK: Checked by the treasury program, to which the surplus goes back
+    #[account(
+        mut,
+        seeds = [b"ledger".as_ref(), b"reserve".as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub reserve: UncheckedAccount<'info_sl>,
+    /// CHECK: Checked by the treasury program, which settles the subsidy lent to the round
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), round.to_le_bytes().as_ref(), LedgerPurpose::Subsidy.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub subsidy_ledger: UncheckedAccount<'info_sl>,
+    pub treasury_program: Program<'info_sl, TreasuryProgram>,
+    pub system_program: Program<'info_sl, System>,
+}
+
+impl<'info_sl> SweepLmsr<'info_sl> {
+
+    pub fn sweep(
+        &mut self,
+        params: &MarketParams,
+        round: u16,
+    ) -> Result<u64> {
+
+        // Requirements:                                                        |   Implemented:
+        //  - The token must be the same as that which instantiated the market  |       √
+        //  - Verdict and pool should be for the given facet                    |       √
+        //  - Treasury authority should be the same as treasury_auth            |       √
+        require!(self.verdict.market == params.authensus_token && self.pool.market == params.authensus_token, TokenError::NotTheSameToken);
+        require!(self.verdict.facet == params.facet && self.pool.facet == params.facet, FacetError::NotTheSameFacet);
+        require!(self.treasury_auth.key() == self.treasury.authority, TreasuryError::TreasuryAuthoritiesDontMatch);
+
+        let pool: AccountInfo<'info_sl> = self.pool.to_account_info();
+        let rent: u64 = Rent::get()?.minimum_balance(pool.data_len());
+
+        // Everything beyond what is still owed to unredeemed shares goes back to the treasury's reserve
+        let owed: u64 = lmsr_payout(&self.pool.shares, self.verdict.outcome);
+        let surplus: u64 = pool.lamports().saturating_sub(rent + owed);
+
+        // The pool is owned by this program, so its lamports can be moved directly
+        **pool.try_borrow_mut_lamports()? -= surplus;
+        **self.treasury.to_account_info().try_borrow_mut_lamports()? += surplus;
+
+        self.return_subsidy(params, round, surplus)?;
+
+        Ok(surplus)
+
+    }
+
+    fn return_subsidy(
+        &self,
+        params: &MarketParams,
+        round: u16,
+        amount: u64,
+    ) -> Result<()> {
+
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = Subsidise {
+            payer: self.treasury_auth.to_account_info(),
+            authority: self.treasury_auth.to_account_info(),
+            caller: self.market.to_account_info(),
+            maker: self.pool.to_account_info(),
+            treasury: self.treasury.to_account_info(),
+            reserve: self.reserve.to_account_info(),
+            subsidy_ledger: self.subsidy_ledger.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        };
+
+        // The surplus is credited back to the reserve against the subsidy lent to the round
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        return_subsidy(cpi_ctx, self.market.signer(), round, params.facet.to_string(), amount)
+
+    }
+
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, MintTo, Token, TokenAccount},
+};
+
+use crate::states::{Bettor, Escrow, Market, MarketParams, MarketState};
+use crate::error::{FacetError, MarketError, OutcomeTokenError, ResultsError, TokenError};
+use crate::utils::functions::mul_div;
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams, amount: u64, outcome: u8)]
+pub struct TokenisePosition<'info_tp> {
+    #[account(mut)]
+    pub signer: Signer<'info_tp>,
+    #[account(
+        seeds = [b"market", params.authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_tp, Market>,
+    #[account(
+        mut,
+        seeds = [b"escrow", params.authensus_token.as_ref(), params.facet.to_string().as_bytes()],
+        bump,
+    )]
+    pub escrow: Account<'info_tp, Escrow>,
+    #[account(
+        mut,
+        seeds = [b"bettor", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), signer.key().as_ref()],
+        bump,
+    )]
+    pub bettor: Account<'info_tp, Bettor>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        mint::decimals = market.stake_decimals,
+        mint::authority = escrow,
+        seeds = [b"outcome", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), [outcome].as_ref()],
+        bump,
+    )]
+    pub outcome_mint: Account<'info_tp, Mint>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = outcome_mint,
+        associated_token::authority = signer,
+    )]
+    pub holder_account: Account<'info_tp, TokenAccount>,
+    pub token_program: Program<'info_tp, Token>,
+    pub associated_token_program: Program<'info_tp, AssociatedToken>,
+    pub system_program: Program<'info_tp, System>,
+}
+
+impl<'info_tp> TokenisePosition<'info_tp> {
+
+    pub fn tokenise(
+        &mut self,
+        params: &MarketParams,
+        amount: u64,
+        outcome: u8,
+    ) -> Result<()> {
+
+        let bettors_condition: bool = match self.escrow.bettors.as_ref() {
+            Some(bettors) => bettors.contains(&self.signer.key()),
+            None => false,
+        };
+
+        let state_condition: bool = self.market.state == MarketState::Betting || self.market.state == MarketState::Voting;
+
+        // Requirements:                                                        |   Implemented:
+        //  - Outcome tokens should be enabled for the market                   |       √
+        //  - Market should be betting or voting, so no payouts have been made  |       √
+        //  - Market should contain the given facet                             |       √
+        //  - The outcome must exist for the given facet                        |       √
+        //  - The token must be the same as that which instantiated the market  |       √
+        //  - Signer should be a bettor in the escrow                           |       √
+        //  - Amount should be non-zero                                         |       √
+        //  - Amount should not be more than the position on the outcome        |       √
+        require!(self.market.config.outcome_tokens, OutcomeTokenError::OutcomeTokensDisabled);
+        require!(state_condition, MarketError::MarketInWrongState);
+        require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
+        require!(outcome < params.facet.outcomes(), FacetError::OutcomeOutOfRange);
+        require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
+        require!(bettors_condition, ResultsError::NotABettor);
+        require!(amount > 0, OutcomeTokenError::NothingToRedeem);
+        require!(self.bettor.tot_outcomes[outcome as usize] >= amount, OutcomeTokenError::PositionTooSmall);
+
+        let index: usize = outcome as usize;
+        let weighted_amount: u64 = mul_div(self.bettor.weighted_outcomes[index], amount, self.bettor.tot_outcomes[index]);
+
+        // The stake stays in the escrow and vault; only the claim on it moves from the bettor account into tokens
+        self.bettor.tot_outcomes[index] -= amount;
+        self.bettor.weighted_outcomes[index] -= weighted_amount;
+
+        // Outcome tokens are fungible, so the tokenised stake loses its time weighting
+        self.escrow.weighted_outcomes[index] = self.escrow.weighted_outcomes[index] - weighted_amount + amount;
+
+        self.mint_outcome_tokens(params, amount)?;
+
+        // Bettors with nothing left in the market no longer need consolidating
+        if self.bettor.tot_outcomes.iter().all(|tot| *tot == 0) && self.bettor.tot_underdog == 0 {
+            let signer: Pubkey = self.signer.key();
+
+            if let Some(bettors) = self.escrow.bettors.as_mut() {
+                bettors.retain(|bettor| *bettor != signer);
+            }
+        }
+
+        Ok(())
+
+    }
+
+    fn mint_outcome_tokens(&self, params: &MarketParams, amount: u64) -> Result<()> {
+
+        let facet: String = params.facet.to_string();
+        let seeds: &[&[u8]] = &[
+            b"escrow",
+            params.authensus_token.as_ref(),
+            facet.as_bytes(),
+            &[self.escrow.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let accounts = MintTo {
+            mint: self.outcome_mint.to_account_info(),
+            to: self.holder_account.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+
+        token::mint_to(cpi_ctx, amount)
+
+    }
+
+}
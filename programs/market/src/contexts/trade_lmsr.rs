@@ -0,0 +1,173 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer}
+};
+
+use crate::states::{LmsrPool, LmsrPosition, Market, MarketParams, MarketState};
+use crate::error::{BettingError, FacetError, LmsrError, TokenError};
+use crate::utils::functions::{lmsr_buy_cost, lmsr_sell_refund};
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams)]
+pub struct TradeLmsr<'info_tl> {
+    #[account(mut)]
+    pub signer: Signer<'info_tl>,
+    #[account(
+        mut,
+        seeds = [b"market", params.authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_tl, Market>,
+    #[account(
+        mut,
+        seeds = [b"lmsr", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pool: Account<'info_tl, LmsrPool>,
+    #[account(
+        init_if_needed,
+        space = 8 + LmsrPosition::INIT_SPACE,
+        payer = signer,
+        seeds = [b"lmsr_position", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub position: Account<'info_tl, LmsrPosition>,
+    pub system_program: Program<'info_tl, System>,
+}
+
+impl<'info_tl> TradeLmsr<'info_tl> {
+
+    pub fn buy(
+        &mut self,
+        bumps: &TradeLmsrBumps,
+        params: &MarketParams,
+        outcome: u8,
+        amount: u64,
+        max_cost: u64,
+    ) -> Result<()> {
+
+        let time: i64 = Clock::get()?.unix_timestamp;
+
+        self.check_trade(params, outcome, amount)?;
+
+        // If the market has timed out then abort the trade after setting the market state to MarketState::Voting
+        if self.market.betting_deadline() < time {
+
+            self.market.state = MarketState::Voting;
+
+            return Ok(())
+        }
+
+        let cost: u64 = lmsr_buy_cost(&self.pool.shares, self.pool.liquidity, outcome, amount)?;
+
+        require!(cost <= max_cost, LmsrError::CostTooHigh);
+
+        self.receive_sol_trade(cost)?;
+
+        if self.position.pk == Pubkey::default() {
+            self.position.set_inner(
+                LmsrPosition {
+                    bump: bumps.position,                                   // u8
+                    pk: self.signer.key(),                                  // Pubkey
+                    market: params.authensus_token,                         // Pubkey
+                    facet: params.facet.clone(),                            // Facet
+                    round: self.market.round,                               // u16
+                    shares: vec![0; params.facet.outcomes().into()],        // Vec<u64>
+                }
+            );
+        }
+
+        self.position.shares[outcome as usize] += amount;
+        self.pool.shares[outcome as usize] += amount;
+
+        Ok(())
+
+    }
+
+    pub fn sell(
+        &mut self,
+        params: &MarketParams,
+        outcome: u8,
+        amount: u64,
+        min_refund: u64,
+    ) -> Result<()> {
+
+        let time: i64 = Clock::get()?.unix_timestamp;
+
+        self.check_trade(params, outcome, amount)?;
+
+        // If the market has timed out then abort the trade after setting the market state to MarketState::Voting
+        if self.market.betting_deadline() < time {
+
+            self.market.state = MarketState::Voting;
+
+            return Ok(())
+        }
+
+        let held: u64 = self.position.shares.get(outcome as usize).copied().unwrap_or(0);
+
+        require!(held >= amount, LmsrError::InsufficientShares);
+
+        let refund: u64 = lmsr_sell_refund(&self.pool.shares, self.pool.liquidity, outcome, amount)?;
+
+        require!(refund >= min_refund, LmsrError::RefundTooLow);
+
+        self.position.shares[outcome as usize] -= amount;
+        self.pool.shares[outcome as usize] -= amount;
+
+        self.pay_from_pool(refund)
+
+    }
+
+    fn check_trade(
+        &self,
+        params: &MarketParams,
+        outcome: u8,
+        amount: u64,
+    ) -> Result<()> {
+
+        // Requirements:                                                        |   Implemented:
+        //  - Market should be in a betting state                               |       √
+        //  - Market should contain the given facet                             |       √
+        //  - The outcome must exist for the given facet                        |       √
+        //  - The token must be the same as that which instantiated the market  |       √
+        //  - Number of shares should be non-zero                               |       √
+        require!(self.market.state == MarketState::Betting, BettingError::MarketNotInBettingState);
+        require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
+        require!(outcome < params.facet.outcomes(), FacetError::OutcomeOutOfRange);
+        require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
+        require!(amount > 0, LmsrError::NoShares);
+
+        Ok(())
+
+    }
+
+    fn receive_sol_trade(&self, amount: u64) -> Result<()> {
+
+        let accounts = Transfer {
+            from: self.signer.to_account_info(),
+            to: self.pool.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+
+        transfer(cpi_ctx, amount)
+
+    }
+
+    fn pay_from_pool(&self, amount: u64) -> Result<()> {
+
+        let pool: AccountInfo<'info_tl> = self.pool.to_account_info();
+        let rent: u64 = Rent::get()?.minimum_balance(pool.data_len());
+
+        require!(pool.lamports() >= rent + amount, LmsrError::InsufficientPoolFunds);
+
+        // The pool is owned by this program, so its lamports can be moved directly
+        **pool.try_borrow_mut_lamports()? -= amount;
+        **self.signer.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        Ok(())
+
+    }
+
+}
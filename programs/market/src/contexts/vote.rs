@@ -10,9 +10,9 @@ use treasury::{
     Treasury,
 };
 
-use crate::constants::{MAX_VOTE_AMOUNT, MIN_VOTE_AMOUNT, VOTING_TOKENS_MINT_ID, VOTING_TOKENS_PROGRAM_ID, VOTE_THRESHOLD};
-use crate::error::{FacetError, MintError, TokenError, VotingError};
-use crate::states::{Escrow, Market, MarketParams, MarketState, Poll, Voter};
+use crate::constants::{APPEAL_MIN_VOTE_AMOUNT, APPEAL_VOTE_THRESHOLD, MAX_EVIDENCE_URI_LENGTH, MAX_VOTE_AMOUNT, MIN_VOTE_AMOUNT, VOTING_TOKENS_MINT_ID, VOTING_TOKENS_PROGRAM_ID, VOTE_THRESHOLD};
+use crate::error::{AppealError, EvidenceError, FacetError, MintError, TokenError, VotingError};
+use crate::states::{Appeal, Escrow, EvidenceParams, Market, MarketParams, MarketState, Poll, Voter, VoteEvidence};
 
 #[derive(Accounts)]
 #[instruction(params: MarketParams)]
@@ -37,6 +37,12 @@ pub struct Vote<'info_v> {
         bump,
     )]
     pub poll: Account<'info_v, Poll>,
+    #[account(
+        mut,
+        seeds = [b"appeal", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub appeal: Option<Account<'info_v, Appeal>>,                       // Only required once the poll has been appealed
     #[account(
         init_if_needed,
         space = Voter::INIT_SPACE,
@@ -45,12 +51,20 @@ pub struct Vote<'info_v> {
         bump,
     )]
     pub voter: Account<'info_v, Voter>,
+    #[account(
+        init,
+        space = 8 + VoteEvidence::INIT_SPACE,
+        payer = signer,
+        seeds = [b"evidence", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub vote_evidence: Option<Account<'info_v, VoteEvidence>>,          // Only required when evidence is submitted with the vote
     #[account(mut)]
     pub voting_token_account: Account<'info_v, TokenAccount>,           // This should already be initialised from wager_results (or purchasing)
     #[account(mut)]
     pub mint: Account<'info_v, Mint>,
     #[account(mut)]
-    pub treasury_voting_token_account: Account<'info_v, TokenAccount>,  // This should already be initialised with the treasury
+    pub treasury_voting_token_account: Account<'info_v, TokenAccount>,  // The treasury PDA's ATA, initialised with the treasury
     #[account(mut)]
     pub treasury: Account<'info_v, Treasury>,
     pub system_program: Program<'info_v, System>,
@@ -64,7 +78,49 @@ impl<'info_v> Vote<'info_v> {
         bumps: &VoteBumps,
         params: &MarketParams,
         amount: u64,
-        direction: bool,
+        outcome: u8,
+        evidence: Option<EvidenceParams>,
+    ) -> Result<()> {
+
+        // Requirements:                            |   Implemented:
+        //  - The facet cannot be a scalar facet    |       √
+        require!(params.facet.scalar_range().is_none(), FacetError::ScalarFacet);
+
+        self.cast_vote(bumps, params, amount, outcome, None, evidence)
+
+    }
+
+    pub fn add_scalar_vote(
+        &mut self,
+        bumps: &VoteBumps,
+        params: &MarketParams,
+        amount: u64,
+        value: u16,
+        evidence: Option<EvidenceParams>,
+    ) -> Result<()> {
+
+        let range = match params.facet.scalar_range() {
+            Some(range) => range,
+            None => return Err(anchor_lang::error!(FacetError::NotAScalarFacet)),
+        };
+
+        // Requirements:                                    |   Implemented:
+        //  - The value must be in the range of the facet   |       √
+        require!(range.contains(value), FacetError::ValueOutOfRange);
+
+        // The vote is tallied against the bucket containing the value
+        self.cast_vote(bumps, params, amount, range.bucket(value), Some(value), evidence)
+
+    }
+
+    fn cast_vote(
+        &mut self,
+        bumps: &VoteBumps,
+        params: &MarketParams,
+        amount: u64,
+        outcome: u8,
+        value: Option<u16>,
+        evidence: Option<EvidenceParams>,
     ) -> Result<()> {
 
         let time: i64 = Clock::get()?.unix_timestamp;
@@ -77,8 +133,8 @@ impl<'info_v> Vote<'info_v> {
              &mint_pk,
              &mint_program_pk,
         );
-        let treasury_authority_ata: Pubkey = get_associated_token_address_with_program_id(
-            &self.treasury.authority,
+        let treasury_ata: Pubkey = get_associated_token_address_with_program_id(
+            &self.treasury.key(),
             &mint_pk,
             &mint_program_pk,
         );
@@ -93,12 +149,30 @@ impl<'info_v> Vote<'info_v> {
             false => false,
         };
 
+        // Once a poll has been appealed, votes go to the larger appeal poll with its higher minimum vote
+        let (votes_cast, vote_threshold, min_vote_amount, appeal_voters_condition): (u64, u64, u64, bool) = match self.poll.appealed {
+            true => {
+                let appeal: &Appeal = self.appeal.as_ref().ok_or(anchor_lang::error!(AppealError::AppealAccountMissing))?;
+
+                require!(appeal.round == self.market.round, AppealError::WrongRound);
+                require!(appeal.appellant != self.signer.key(), AppealError::AppellantCannotVote);
+
+                let appeal_voters_condition: bool = match appeal.voters.is_some() {
+                    true => appeal.voters.as_ref().unwrap().contains(&self.signer.key()),
+                    false => false,
+                };
+
+                (appeal.total_outcomes.iter().sum(), APPEAL_VOTE_THRESHOLD.into(), APPEAL_MIN_VOTE_AMOUNT, appeal_voters_condition)
+            },
+            false => (self.poll.total_outcomes.iter().sum(), VOTE_THRESHOLD.into(), MIN_VOTE_AMOUNT, false),
+        };
+
         // Requirements:                                                                |   Implemented:
         //  - The token must be the same as that which instantiated the market          |       √
-        //  - treasury_voting_token_account should be derivable from treasury authority |       √
+        //  - treasury_voting_token_account should be derivable from the treasury PDA   |       √
         //  - The betting round has finished                                            |       √
-        //  - Cannot have voted here already                                            |       √
-        //  - Voting threshold cannot have been reached yet                             |       √
+        //  - Cannot have voted here already (in the poll or its appeal)                |       √
+        //  - Voting threshold (of the poll or its appeal) cannot have been reached yet |       √
         //  - ATA needs to be correct                                                   |       √
         //  - ATA must have sufficient tokens for this vote                             |       √
         //  - Vote amount must be higher than minimum                                   |       √
@@ -106,23 +180,31 @@ impl<'info_v> Vote<'info_v> {
         //  - Mint provided must be correct                                             |       √
         //  - Voter cannot have placed any bets                                         |       √
         //  - Market should contain the given facet                                     |       √
+        //  - The outcome must exist for the given facet                                |       √
         //  - Mint PK needs to be correct                                               |       √
         require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
-        require!(treasury_authority_ata == self.treasury_voting_token_account.key(), VotingError::IncorrectTreasuryATA);
-        require!(self.market.start_time + self.market.timeout < time, VotingError::NotVotingTime);
-        require!(!voters_count_condition, VotingError::AlreadyVoted);
-        require!(self.poll.total_for + self.poll.total_against < VOTE_THRESHOLD.into(), VotingError::VotingClosed);    // Better to do time- or threshold-based?
+        require!(treasury_ata == self.treasury_voting_token_account.key(), VotingError::IncorrectTreasuryATA);
+        require!(self.market.betting_deadline() < time, VotingError::NotVotingTime);
+        require!(!voters_count_condition && !appeal_voters_condition, VotingError::AlreadyVoted);
+        require!(votes_cast < vote_threshold, VotingError::VotingClosed);    // Better to do time- or threshold-based?
         require!(signer_ata == self.voting_token_account.key(), VotingError::IncorrectATA);
         require!(self.voting_token_account.amount >= amount, VotingError::InsufficientVotingTokens);
-        require!(amount >= MIN_VOTE_AMOUNT, VotingError::AmountTooLow);
+        require!(amount >= min_vote_amount, VotingError::AmountTooLow);
         require!(amount <= MAX_VOTE_AMOUNT, VotingError::AmountTooHigh);
         require!(self.mint.key() == mint_pk, VotingError::IncorrectMint);
         require!(!wagers_count_condition, VotingError::CannotVoteWithBets);
         require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
+        require!(outcome < params.facet.outcomes(), FacetError::OutcomeOutOfRange);
         require!(self.mint.key() == mint_pk, MintError::NotTheRightMintPK);
 
+        if let Some(evidence) = &evidence {
+            self.check_evidence(evidence)?;
+        } else {
+            require!(self.vote_evidence.is_none(), EvidenceError::EvidenceMissing);
+        }
+
         // If the market state is still set to Betting but the betting markets have passed the timeout, then change to Voting
-        if self.market.state == MarketState::Betting && self.market.start_time + self.market.timeout < time {
+        if self.market.state == MarketState::Betting && self.market.betting_deadline() < time {
             self.market.state = MarketState::Voting;
         }
 
@@ -131,38 +213,151 @@ impl<'info_v> Vote<'info_v> {
 
         // Update poll + voter totals
         // Amount of vote does not change number of votes in the poll, only redemption
-        // Everyone is marked as a single vote in the poll
-        let vote_for: u64 = match direction {
-            true => 1_u64,
-            false => 0_u64
-        };
-        
-        let vote_against: u64 = 1 - vote_for;
+        // Everyone is marked as a single vote in the poll (or the appeal poll)
+        let appeal: bool = self.poll.appealed;
 
-        // Update the poll
-        let voters: &mut Vec<Pubkey> = &mut self.poll.voters.clone().unwrap();
-        voters.push(self.signer.key());
-        
-        self.poll.voters = Some(voters.clone());
-        self.poll.total_for += vote_for;
-        self.poll.total_against += vote_against;
+        match appeal {
+            true => self.add_to_appeal(outcome, value, amount, vote_threshold, time)?,
+            false => self.add_to_poll(outcome, value, amount, vote_threshold, time),
+        }
 
         // As per requirements above, voter cannot have already cast a vote; so this is de novo
         self.voter.set_inner(
             Voter {
                 bump: bumps.voter,              // u8
-                pk: self.signer.key(),             // Pubkey
+                pk: self.signer.key(),          // Pubkey
                 market: params.authensus_token, // Pubkey
                 facet: params.facet.clone(),    // Facet
                 amount,                         // u64
-                direction,                      // bool
+                outcome,                        // u8
+                value,                          // Option<u16>
+                appeal,                         // bool
             }
         );
+
+        if let Some(evidence) = evidence {
+            self.record_evidence(bumps, params, outcome, evidence, time)?;
+        }
         
         Ok(())
 
     }
 
+    fn add_to_poll(
+        &mut self,
+        outcome: u8,
+        value: Option<u16>,
+        amount: u64,
+        vote_threshold: u64,
+        time: i64,
+    ) {
+
+        let voters: &mut Vec<Pubkey> = &mut self.poll.voters.clone().unwrap_or_default();
+        voters.push(self.signer.key());
+
+        self.poll.voters = Some(voters.clone());
+        self.poll.total_outcomes[outcome as usize] += 1;
+
+        // Votes on scalar facets are also weighted by their amount, for the median
+        if let (Some(value), Some(range)) = (value, self.poll.facet.scalar_range()) {
+            self.poll.value_weights[(value - range.min) as usize] += amount;
+        }
+
+        // Reaching the threshold opens the appeal window
+        if self.poll.total_outcomes.iter().sum::<u64>() >= vote_threshold {
+            self.poll.closed_at = time;
+        }
+
+    }
+
+    fn add_to_appeal(
+        &mut self,
+        outcome: u8,
+        value: Option<u16>,
+        amount: u64,
+        vote_threshold: u64,
+        time: i64,
+    ) -> Result<()> {
+
+        let signer: Pubkey = self.signer.key();
+
+        let appeal = match self.appeal.as_mut() {
+            Some(appeal) => appeal,
+            None => return Err(anchor_lang::error!(AppealError::AppealAccountMissing)),
+        };
+
+        let voters: &mut Vec<Pubkey> = &mut appeal.voters.clone().unwrap_or_default();
+        voters.push(signer);
+
+        appeal.voters = Some(voters.clone());
+        appeal.total_outcomes[outcome as usize] += 1;
+
+        if let (Some(value), Some(range)) = (value, appeal.facet.scalar_range()) {
+            appeal.value_weights[(value - range.min) as usize] += amount;
+        }
+
+        if appeal.total_outcomes.iter().sum::<u64>() >= vote_threshold {
+            appeal.closed_at = time;
+        }
+
+        Ok(())
+
+    }
+
+    fn check_evidence(
+        &self,
+        evidence: &EvidenceParams,
+    ) -> Result<()> {
+
+        // Requirements:                                        |   Implemented:
+        //  - An account to store the evidence must be supplied |       √
+        //  - URI cannot be empty                               |       √
+        //  - URI cannot be longer than the maximum             |       √
+        require!(self.vote_evidence.is_some(), EvidenceError::EvidenceAccountMissing);
+        require!(!evidence.uri.is_empty(), EvidenceError::EmptyUri);
+        require!(evidence.uri.len() <= MAX_EVIDENCE_URI_LENGTH.into(), EvidenceError::UriTooLong);
+
+        Ok(())
+
+    }
+
+    fn record_evidence(
+        &mut self,
+        bumps: &VoteBumps,
+        params: &MarketParams,
+        outcome: u8,
+        evidence: EvidenceParams,
+        time: i64,
+    ) -> Result<()> {
+
+        let round: u16 = self.market.round;
+        let voter: Pubkey = self.signer.key();
+
+        let vote_evidence = match self.vote_evidence.as_mut() {
+            Some(vote_evidence) => vote_evidence,
+            None => return Err(anchor_lang::error!(EvidenceError::EvidenceAccountMissing)),
+        };
+
+        // Each round's evidence has its own account, so earlier rounds stay on record
+        vote_evidence.set_inner(
+            VoteEvidence {
+                bump: bumps.vote_evidence.unwrap(), // u8
+                voter,                              // Pubkey
+                market: params.authensus_token,     // Pubkey
+                facet: params.facet.clone(),        // Facet
+                round,                              // u16
+                outcome,                            // u8
+                uri: evidence.uri,                  // String
+                hash: evidence.hash,                // [u8; 32]
+                rationale: evidence.rationale,      // RationaleCode
+                submitted_at: time,                 // i64
+            }
+        );
+
+        Ok(())
+
+    }
+
     fn receive_vote_token_into_treasury(
         &self,
         from: AccountInfo<'info_v>,
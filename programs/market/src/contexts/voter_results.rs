@@ -6,21 +6,21 @@ use anchor_spl::{
 };
 
 use treasury::{
-    cpi::{accounts::Transact, reimburse},
+    cpi::{accounts::VotingTokenCustody, return_voting_tokens},
     program::TreasuryProgram,
     self,
     Treasury,
 };
 use voting_tokens::{
-    cpi::{accounts::MintTokens, mint_tokens},
+    cpi::{accounts::MintTokensTo, mint_tokens_to},
     self,
     program::VotingTokens,
 };
 
-use crate::constants::{TREASURY_AUTHORITY, TREASURY_PROGRAM_ID, VOTING_TOKENS_MINT_ID, VOTING_TOKENS_PROGRAM_ID};
-use crate::error::{CpiError, FacetError, MintError, ResultsError, TokenError, TreasuryError, VotingError};
-use crate::states::{Market, MarketParams, MarketState, Poll, Voter};
-use crate::utils::functions::calc_winnings_from_votes;
+use crate::constants::{TREASURY_PROGRAM_ID, VOTING_TOKENS_MINT_ID, VOTING_TOKENS_PROGRAM_ID};
+use crate::error::{AppealError, FacetError, MintError, ResultsError, TokenError, TreasuryError, VotingError};
+use crate::states::{Appeal, Market, MarketParams, MarketState, Poll, Voter};
+use crate::utils::functions::{calc_winnings_from_votes, final_outcome};
 
 #[derive(Accounts)]
 #[instruction(params: MarketParams)]
@@ -40,6 +40,12 @@ pub struct VoterResult<'info_vr> {
         bump,
     )]
     pub poll: Account<'info_vr, Poll>,
+    #[account(
+        mut,
+        seeds = [b"appeal", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub appeal: Option<Account<'info_vr, Appeal>>,                      // Only required if the poll was appealed
     #[account(
         mut,
         seeds = [b"voter", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), signer.key().as_ref()],
@@ -49,22 +55,22 @@ pub struct VoterResult<'info_vr> {
     #[account(mut)]
     pub voting_token_account: Account<'info_vr, TokenAccount>,          // Should already be initialised
     #[account(mut)]
-    pub treasury_voting_token_account: Account<'info_vr, TokenAccount>, // This should already be initialised with the treasury
+    pub treasury_voting_token_account: Account<'info_vr, TokenAccount>, // The treasury PDA's ATA, initialised with the treasury
     #[account(mut)]
     pub treasury: Account<'info_vr, Treasury>,
     pub treasury_program: Program<'info_vr, TreasuryProgram>,
+    pub voting_tokens_program: Program<'info_vr, VotingTokens>,
     pub associated_token_program: Program<'info_vr, AssociatedToken>,
     #[account(mut)]
     pub mint: Account<'info_vr, Mint>,
     pub system_program: Program<'info_vr, System>,
     pub token_program: Program<'info_vr, Token>,
     pub rent: Sysvar<'info_vr, Rent>,
-    pub voting_tokens_program: Program<'info_vr, VotingTokens>,
 }
 
 impl<'info_vr> VoterResult<'info_vr> {
 
-    pub fn distribute_sol_to_voter(
+    pub fn distribute_votes_to_voter(
         &mut self,
         params: &MarketParams,
     ) -> Result<()> {
@@ -77,19 +83,28 @@ impl<'info_vr> VoterResult<'info_vr> {
              &mint_pk,
              &mint_program_pk,
         );
-        let treasury_authority_ata: Pubkey = get_associated_token_address_with_program_id(
-            &self.treasury.authority,
+        let treasury_ata: Pubkey = get_associated_token_address_with_program_id(
+            &self.treasury.key(),
             &mint_pk,
             &mint_program_pk,
         );
 
-        let voters_count_condition: bool = match self.poll.voters.is_some() {
-            true => self.poll.voters.as_ref().unwrap().contains(&self.signer.key()),
+        require!(!self.poll.appealed || self.appeal.is_some(), AppealError::AppealAccountMissing);
+
+        // Appeal voters are recorded in the appeal poll rather than the original one
+        let (voters, voters_consolidated): (&Option<Vec<Pubkey>>, &Option<Vec<Pubkey>>) = match (self.voter.appeal, self.appeal.as_ref()) {
+            (true, Some(appeal)) => (&appeal.voters, &appeal.voters_consolidated),
+            (true, None) => return Err(anchor_lang::error!(AppealError::AppealAccountMissing)),
+            (false, _) => (&self.poll.voters, &self.poll.voters_consolidated),
+        };
+
+        let voters_count_condition: bool = match voters.is_some() {
+            true => voters.as_ref().unwrap().contains(&self.signer.key()),
             false => false,
         };
 
-        let consolidated_voters_condition: bool = match self.poll.voters_consolidated.is_some() {
-            true => self.poll.voters_consolidated.as_ref().unwrap().contains(&self.signer.key()),
+        let consolidated_voters_condition: bool = match voters_consolidated.is_some() {
+            true => voters_consolidated.as_ref().unwrap().contains(&self.signer.key()),
             false => false,
         };
 
@@ -100,36 +115,33 @@ impl<'info_vr> VoterResult<'info_vr> {
         //  - Market should contain the given facet                                                             |       √
         //  - The token must be the same as that which instantiated the market                                  |       √
         //  - Treasury authority should be the same as treasury_auth                                            |       √
-        //  - Treasury authority should be the same as on record                                                |       √
         //  - ATA needs to be correct                                                                           |       √
         //  - Mint PK needs to be correct                                                                       |       √
         //  - Treasury Program needs to be correct                                                              |       √
         //  - Voting Tokens Program needs to be correct                                                         |       √
-        //  - treasury_voting_token_account should be derivable from treasury authority                         |       √
+        //  - treasury_voting_token_account should be derivable from the treasury PDA                           |       √
         require!(self.market.state == MarketState::Consolidating, ResultsError::VotingNotFinished);
         require!(voters_count_condition, ResultsError::NotAVoter);
         require!(!consolidated_voters_condition, ResultsError::VoterAlreadyConsolidated);
         require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
         require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
         require!(self.treasury_auth.key() == self.treasury.authority, TreasuryError::TreasuryAuthoritiesDontMatch);
-        require!(self.treasury_auth.key().to_string() == TREASURY_AUTHORITY, TreasuryError::WrongTreasuryAuthority);
         require!(signer_ata == self.voting_token_account.key(), VotingError::IncorrectATA);
         require!(self.mint.key() == mint_pk, MintError::NotTheRightMintPK);
         require!(self.treasury_program.key().to_string() == TREASURY_PROGRAM_ID, TreasuryError::NotTheRightTreasuryProgramPK);
         require!(self.voting_tokens_program.key().to_string() == VOTING_TOKENS_PROGRAM_ID, MintError::NotTheRightMintProgramPK);
-        require!(treasury_authority_ata == self.treasury_voting_token_account.key(), VotingError::IncorrectTreasuryATA);
+        require!(treasury_ata == self.treasury_voting_token_account.key(), VotingError::IncorrectTreasuryATA);
 
         self.add_to_consolidated()?;
 
-        if self.poll.total_for == self.poll.total_against {
-            return self.voting_tie();
-        }
-
-        let direction: bool = self.poll.total_for > self.poll.total_against;
+        let outcome: u8 = match final_outcome(&self.poll, self.appeal.as_deref()) {
+            Some(outcome) => outcome,
+            None => return self.voting_tie(),
+        };
 
         let winnings: u64 = calc_winnings_from_votes(
-            direction,
-            self.voter.direction,
+            outcome,
+            self.voter.outcome,
             self.voter.amount,
         );
 
@@ -137,7 +149,10 @@ impl<'info_vr> VoterResult<'info_vr> {
             return Ok(())
         }
 
-        self.reimburse_winnings(winnings)
+        // Winners get their votes back out of custody along with their winnings, which are minted rather
+        // than paid out of the round's stakes, so voters are rewarded in the same way in every market
+        self.return_votes(self.voter.amount)?;
+        self.mint_winnings(winnings)
 
     }
 
@@ -145,84 +160,93 @@ impl<'info_vr> VoterResult<'info_vr> {
         &mut self
     ) -> Result<()> {
 
-        // In the case of a tie everyone gets their votes tokens re-minted
-        self.reimburse_votes(self.voting_token_account.to_account_info(), self.voter.amount)
+        // In the case of a tie everyone gets their voting tokens back out of the treasury's custody
+        self.return_votes(self.voter.amount)
 
     }
 
-    fn reimburse_votes(
+    fn return_votes(
         &self,
-        to: AccountInfo<'info_vr>,
         amount: u64
     ) -> Result<()> {
 
         require!(self.market.state == MarketState::Consolidating, ResultsError::VotingNotFinished);
 
-        let program_account: AccountInfo<'_> = self.voting_tokens_program.to_account_info();
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
 
-        require!(program_account.key().to_string() == VOTING_TOKENS_PROGRAM_ID, CpiError::WrongProgramID);
-
-        let accounts: MintTokens<'_> = MintTokens{
-            payer: self.signer.to_account_info(),
+        let cpi_accounts = VotingTokenCustody {
+            signer: self.market.to_account_info(),
+            treasury: self.treasury.to_account_info(),
             mint: self.mint.to_account_info(),
-            recipient: to,
-            associated_token_program: self.associated_token_program.to_account_info(),
-            system_program: self.system_program.to_account_info(),
+            treasury_voting_token_account: self.treasury_voting_token_account.to_account_info(),
+            voting_token_account: Some(self.voting_token_account.to_account_info()),
             token_program: self.token_program.to_account_info(),
-            rent: self.rent.to_account_info(),
+            associated_token_program: self.associated_token_program.to_account_info(),
         };
 
-        let cpi_ctx: CpiContext<'_, '_, '_, '_, MintTokens<'_>> = CpiContext::new(
-            program_account,
-            accounts,
-        );
+        // The market signs as its PDA, which the treasury accepts in place of its authority
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
 
-        mint_tokens(
-            cpi_ctx,
-            amount,
-        )?;
-
-        Ok(())
+        return_voting_tokens(cpi_ctx, Some(self.market.signer()), amount)
 
     }
 
-    fn reimburse_winnings(
-        &mut self,
+    fn mint_winnings(
+        &self,
         winnings: u64,
     ) -> Result<()> {
-        let cpi_accounts = Transact {
-            signer: self.treasury_auth.to_account_info(),                               // This needs to be the treasury authority
-            coparty: self.signer.to_account_info(),                                     // This needs to be the person receiving the reimbursement
-            treasury: self.treasury.to_account_info(),
-            voting_token_account: self.treasury_voting_token_account.to_account_info(),
+
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = MintTokensTo {
+            payer: self.signer.to_account_info(),
+            caller: self.market.to_account_info(),
+            owner: self.signer.to_account_info(),
+            mint: self.mint.to_account_info(),
+            recipient: self.voting_token_account.to_account_info(),
             associated_token_program: self.associated_token_program.to_account_info(),
             system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            rent: self.rent.to_account_info(),
         };
 
-        let cpi_ctx = CpiContext::new(
-            self.treasury_program.to_account_info(),
-            cpi_accounts,
-        );
+        // The market signs as its PDA, since only a market can mint winnings into another account
+        let cpi_ctx = CpiContext::new_with_signer(self.voting_tokens_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        mint_tokens_to(cpi_ctx, self.market.token, self.market.bump, winnings)
 
-        // Pay out winnings in SOL
-        reimburse(
-            cpi_ctx,
-            winnings,
-        )
     }
 
     fn add_to_consolidated(&mut self) -> Result<()> {
 
-        if self.poll.voters_consolidated.is_some() {
+        let signer: Pubkey = self.signer.key();
+
+        let voters_consolidated: &mut Option<Vec<Pubkey>> = match (self.voter.appeal, self.appeal.as_mut()) {
+            (true, Some(appeal)) => &mut appeal.voters_consolidated,
+            (true, None) => return Err(anchor_lang::error!(AppealError::AppealAccountMissing)),
+            (false, _) => &mut self.poll.voters_consolidated,
+        };
+
+        if voters_consolidated.is_some() {
 
-            let mut consolidated_vec: Vec<Pubkey> = self.poll.voters_consolidated.clone().unwrap();
-            consolidated_vec.push(self.signer.key());
+            let mut consolidated_vec: Vec<Pubkey> = voters_consolidated.clone().unwrap();
+            consolidated_vec.push(signer);
             
-            self.poll.voters_consolidated = Some(consolidated_vec.clone());
+            *voters_consolidated = Some(consolidated_vec.clone());
 
         } else {
             
-            self.poll.voters_consolidated = Some(Vec::from([self.signer.key()]));
+            *voters_consolidated = Some(Vec::from([signer]));
 
         }
 
@@ -1,16 +1,18 @@
-use anchor_lang::{
-    prelude::*,
-    system_program::{transfer, Transfer}
-};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
 
 use treasury::{
+    cpi::{accounts::Accrue, accrue},
+    program::TreasuryProgram,
     self,
+    LedgerPurpose,
     Treasury,
 };
 
-use crate::states::{Bettor, Escrow, Market, MarketParams, MarketState};
-use crate::constants::{MAX_WAGERS, TREASURY_AUTHORITY};
-use crate::error::{BettingError, FacetError, TokenError, TreasuryError};
+use crate::states::{BetType, Bettor, Escrow, Market, MarketParams, MarketState, Quote};
+use crate::constants::{BASIS_POINTS, MAX_WAGERS};
+use crate::error::{BettingError, FacetError, StakeError, TokenError, TreasuryError};
+use crate::utils::functions::{check_bet_limits, mul_div, payout_multiplier, pool_position, quote_bet, sniping_extension, stake_accounts_condition, time_weight, underdog_outcome, Stakes};
 
 #[derive(Accounts)]
 #[instruction(params: MarketParams)]
@@ -41,6 +43,33 @@ pub struct Wager<'info_w> {
     pub bettor: Account<'info_w, Bettor>,
     #[account(mut)]
     pub treasury: Account<'info_w, Treasury>,       // Should already be initialised
+    pub stake_mint: Option<Account<'info_w, Mint>>, // The remaining accounts are only required for markets denominated in an SPL token
+    #[account(
+        mut,
+        seeds = [b"vault", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: Option<Account<'info_w, TokenAccount>>,
+    #[account(mut)]
+    pub stake_account: Option<Account<'info_w, TokenAccount>>,
+    pub token_program: Option<Program<'info_w, Token>>,
+    /// CHECK: Created and checked by the treasury program, which tracks what the round owes
+    #[account(
+        mut,
+        seeds = [b"payable", market.key().as_ref(), market.round.to_le_bytes().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub payable: UncheckedAccount<'info_w>,
+    /// CHECK: Created and checked by the treasury program, which keeps the round's share of its balance
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), LedgerPurpose::Stakes.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub ledger: UncheckedAccount<'info_w>,
+    pub treasury_program: Program<'info_w, TreasuryProgram>,
     pub system_program: Program<'info_w, System>,
 }
 
@@ -51,7 +80,8 @@ impl<'info_w> Wager<'info_w> {
         bumps: &WagerBumps,
         params: &MarketParams,
         amount: u64,
-        direction: bool,
+        outcome: u8,
+        min_multiplier: Option<u64>,
     ) -> Result<()> {
 
         let time: i64 = Clock::get()?.unix_timestamp;
@@ -61,58 +91,87 @@ impl<'info_w> Wager<'info_w> {
             false => true,
         };
 
+        let stake_condition: bool = self.market.stake_mint.is_none() || (self.token_program.is_some() && stake_accounts_condition(
+            self.market.stake_mint,
+            self.stake_mint.as_ref(),
+            self.vault.as_ref(),
+            self.escrow.key(),
+            self.stake_account.as_ref(),
+            self.signer.key(),
+        ));
+
+        let funds_condition: bool = match self.stake_account.as_ref() {
+            Some(stake_account) if self.market.stake_mint.is_some() => stake_account.amount >= amount,
+            _ => self.bettor.get_lamports() > amount,
+        };
+
         // Requirements:                                                        |   Implemented:
         //  - Market should be in a betting state                               |       √
+        //  - Stake accounts should be provided for a token market              |       √
         //  - Bettor should have sufficient balance to place the bet            |       √
         //  - Market should contain the given facet                             |       √
+        //  - The outcome must exist for the given facet                        |       √
         //  - The token must be the same as that which instantiated the market  |       √
-        //  - Bettor should not have placed any underdog bets                   |       √
         //  - Treasury authority should be the same as treasury_auth            |       √
-        //  - Treasury authority should be the same as on record                |       √
         //  - Current number of wagers must be less than the max                |       √
         require!(self.market.state == MarketState::Betting, BettingError::MarketNotInBettingState);
-        require!(self.bettor.get_lamports() > amount, BettingError::InsufficientFunds);
+        require!(stake_condition, StakeError::StakeAccountsMissing);
+        require!(funds_condition, BettingError::InsufficientFunds);
         require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
+        require!(outcome < params.facet.outcomes(), FacetError::OutcomeOutOfRange);
         require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
-        require!(self.bettor.tot_underdog == 0, BettingError::BetWithUnderdogBet);
         require!(self.treasury_auth.key() == self.treasury.authority, TreasuryError::TreasuryAuthoritiesDontMatch);
-        require!(self.treasury_auth.key().to_string() == TREASURY_AUTHORITY, TreasuryError::WrongTreasuryAuthority);
         require!(wagers_count_condition, BettingError::TooManyBettors);
 
         // If the market has timed out then abort the bet after setting the market state to MarketState::Voting
-        if self.market.start_time + self.market.timeout < time {
+        if self.market.betting_deadline() < time {
 
             self.market.state = MarketState::Voting;
 
             return Ok(())
         }
 
-        self.receive_sol_wager(self.signer.to_account_info(), amount)?;
+        let mut bettor_outcomes: Vec<u64> = self.bettor.tot_outcomes.clone();
+        let mut pool_outcomes: Vec<u64> = self.escrow.tot_outcomes.clone();
 
-        let amount_for: u64 = match direction {
-            true => amount,
-            false => 0_u64
-        };
-        
-        let amount_against: u64 = amount - amount_for;
+        bettor_outcomes.resize(pool_outcomes.len(), 0);
+        bettor_outcomes[outcome as usize] += amount;
+        pool_outcomes[outcome as usize] += amount;
+
+        let (position, pool): (u64, u64) = pool_position(&bettor_outcomes, self.bettor.tot_underdog, &pool_outcomes, self.escrow.tot_underdog, outcome);
+
+        check_bet_limits(&self.market.config, amount, position, pool)?;
+
+        let weighted_amount: u64 = mul_div(amount, time_weight(&self.market, time), BASIS_POINTS);
+
+        self.check_slippage(amount, outcome, BetType::Normal, time, min_multiplier)?;
+
+        match self.market.stake_mint {
+            Some(_) => self.receive_token_wager(amount)?,
+            None => self.accrue_payable(params, LedgerPurpose::Stakes, amount)?,
+        }
 
-        if self.bettor.tot_against == 0 && self.bettor.tot_against == 0 {
+        let outcomes: usize = params.facet.outcomes().into();
+
+        // Bettors can hold normal and underdog positions together, so only a bettor with neither is reset
+        if self.bettor.tot_outcomes.iter().all(|tot| *tot == 0) && self.bettor.tot_underdog == 0 {
             self.bettor.set_inner(
                 Bettor {
-                    bump: bumps.bettor,             // u8
-                    pk: self.signer.key(),          // Pubkey
-                    market: params.authensus_token, // Pubkey
-                    facet: params.facet.clone(),    // Facet
-                    tot_for: amount_for,            // u64
-                    tot_against: amount_against,    // u64
-                    tot_underdog: 0_u64             // u64
+                    bump: bumps.bettor,                   // u8
+                    pk: self.signer.key(),                // Pubkey
+                    market: params.authensus_token,       // Pubkey
+                    facet: params.facet.clone(),          // Facet
+                    tot_outcomes: vec![0; outcomes],      // Vec<u64>
+                    tot_underdog: 0_u64,                  // u64
+                    weighted_outcomes: vec![0; outcomes], // Vec<u64>
+                    weighted_underdog: 0_u64,             // u64
                 }
             );
-        } else {
-            self.bettor.tot_for += amount_for;
-            self.bettor.tot_against += amount_against;
         }
 
+        self.bettor.tot_outcomes[outcome as usize] += amount;
+        self.bettor.weighted_outcomes[outcome as usize] += weighted_amount;
+
         let bettors_clone = &mut self.escrow.bettors.clone().unwrap();
 
         if !bettors_clone.contains(&self.signer.key()) {
@@ -120,8 +179,10 @@ impl<'info_w> Wager<'info_w> {
             self.escrow.bettors = Some(bettors_clone.clone());
         }
 
-        self.escrow.tot_for += amount_for;
-        self.escrow.tot_against += amount_against;
+        self.escrow.tot_outcomes[outcome as usize] += amount;
+        self.escrow.weighted_outcomes[outcome as usize] += weighted_amount;
+
+        self.extend_deadline(amount, time);
         
         Ok(())
 
@@ -132,50 +193,83 @@ impl<'info_w> Wager<'info_w> {
         bumps: &WagerBumps,
         params: &MarketParams,
         amount: u64,
+        min_multiplier: Option<u64>,
     ) -> Result<()> {
 
         let time: i64 = Clock::get()?.unix_timestamp;
 
+        let stake_condition: bool = self.market.stake_mint.is_none() || (self.token_program.is_some() && stake_accounts_condition(
+            self.market.stake_mint,
+            self.stake_mint.as_ref(),
+            self.vault.as_ref(),
+            self.escrow.key(),
+            self.stake_account.as_ref(),
+            self.signer.key(),
+        ));
+
+        let funds_condition: bool = match self.stake_account.as_ref() {
+            Some(stake_account) if self.market.stake_mint.is_some() => stake_account.amount >= amount,
+            _ => self.bettor.get_lamports() > amount,
+        };
+
         // Requirements:                                                                    |   Implemented:
         //  - Market should be in a betting state                                           |       √
+        //  - Stake accounts should be provided for a token market                          |       √
         //  - Bettor should have sufficient balance to place the bet                        |       √
         //  - Market should contain the given facet                                         |       √
         //  - The token must be the same as that which instantiated the market              |       √
         //  - At least some normal bets have already been placed                            |       √
-        //  - No other bets should have been placed by this bettor already in this market   |       √
         require!(self.market.state == MarketState::Betting, BettingError::MarketNotInBettingState);
-        require!(self.bettor.get_lamports() > amount, BettingError::InsufficientFunds);
+        require!(stake_condition, StakeError::StakeAccountsMissing);
+        require!(funds_condition, BettingError::InsufficientFunds);
         require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
         require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
-        require!(self.escrow.tot_for + self.escrow.tot_against > 0, BettingError::UnderdogBetTooEarly);
-        require!(self.bettor.tot_for + self.bettor.tot_against == 0, BettingError::UnderdogWithOtherBet);
+        require!(self.escrow.tot_outcomes.iter().sum::<u64>() > 0, BettingError::UnderdogBetTooEarly);
 
         // If the market has timed out then abort the bet after setting the market state to MarketState::Voting
-        if self.market.start_time + self.market.timeout < time {
+        if self.market.betting_deadline() < time {
 
             self.market.state = MarketState::Voting;
 
             return Ok(())
         }
 
-        self.receive_sol_wager(self.signer.to_account_info(), amount)?;
+        // Underdog bets are assigned to the smallest pool at close, so the payout protected is that of the current smallest pool
+        let outcome: u8 = underdog_outcome(&self.escrow.tot_outcomes);
+
+        let (position, pool): (u64, u64) = pool_position(&self.bettor.tot_outcomes, self.bettor.tot_underdog + amount, &self.escrow.tot_outcomes, self.escrow.tot_underdog + amount, outcome);
+
+        check_bet_limits(&self.market.config, amount, position, pool)?;
+
+        let weighted_amount: u64 = mul_div(amount, time_weight(&self.market, time), BASIS_POINTS);
+
+        self.check_slippage(amount, outcome, BetType::Underdog, time, min_multiplier)?;
+
+        match self.market.stake_mint {
+            Some(_) => self.receive_token_wager(amount)?,
+            None => self.accrue_payable(params, LedgerPurpose::Stakes, amount)?,
+        }
+
+        let outcomes: usize = params.facet.outcomes().into();
 
-        if self.bettor.tot_underdog == 0 {
+        if self.bettor.tot_outcomes.iter().all(|tot| *tot == 0) && self.bettor.tot_underdog == 0 {
             self.bettor.set_inner(
                 Bettor {
-                    bump: bumps.bettor,             // u8
-                    pk: self.signer.key(),          // Pubkey
-                    market: params.authensus_token, // Pubkey
-                    facet: params.facet.clone(),    // Facet
-                    tot_for: 0_u64,                 // u64
-                    tot_against: 0_u64,             // u64
-                    tot_underdog: amount,           // u64
+                    bump: bumps.bettor,                   // u8
+                    pk: self.signer.key(),                // Pubkey
+                    market: params.authensus_token,       // Pubkey
+                    facet: params.facet.clone(),          // Facet
+                    tot_outcomes: vec![0; outcomes],      // Vec<u64>
+                    tot_underdog: 0_u64,                  // u64
+                    weighted_outcomes: vec![0; outcomes], // Vec<u64>
+                    weighted_underdog: 0_u64,             // u64
                 }
             );
-        } else {
-            self.bettor.tot_underdog += amount;
         }
 
+        self.bettor.tot_underdog += amount;
+        self.bettor.weighted_underdog += weighted_amount;
+
         let bettors_clone = &mut self.escrow.bettors.clone().unwrap();
 
         if !bettors_clone.contains(&self.signer.key()) {
@@ -184,21 +278,103 @@ impl<'info_w> Wager<'info_w> {
         }
 
         self.escrow.tot_underdog += amount;
+        self.escrow.weighted_underdog += weighted_amount;
+
+        self.extend_deadline(amount, time);
         
         Ok(())
 
     }
 
-    fn receive_sol_wager(&self, from: AccountInfo<'info_w>, amount: u64) -> Result<()> {
+    fn check_slippage(
+        &self,
+        amount: u64,
+        outcome: u8,
+        bet_type: BetType,
+        time: i64,
+        min_multiplier: Option<u64>,
+    ) -> Result<()> {
+
+        let min_multiplier: u64 = match min_multiplier {
+            Some(min_multiplier) => min_multiplier,
+            None => return Ok(()),
+        };
+
+        // Project the payout with the bet included, as the odds stand when the bet lands
+        let quote: Quote = quote_bet(
+            outcome,
+            amount,
+            time_weight(&self.market, time),
+            self.market.config.underdog_bonus.into(),
+            bet_type,
+            &Stakes {
+                outcomes: &self.escrow.tot_outcomes,
+                underdog: self.escrow.tot_underdog,
+                weighted_outcomes: &self.escrow.weighted_outcomes,
+                weighted_underdog: self.escrow.weighted_underdog,
+            },
+        );
+
+        require!(payout_multiplier(&quote, amount) >= min_multiplier, BettingError::SlippageExceeded);
+
+        Ok(())
+
+    }
+
+    fn extend_deadline(&mut self, amount: u64, time: i64) {
+
+        let pool: u64 = self.escrow.tot_outcomes.iter().sum::<u64>() + self.escrow.tot_underdog;
+
+        if let Some(rule) = self.market.config.anti_sniping.as_ref() {
+            self.market.extension = sniping_extension(rule, self.market.extension, self.market.betting_deadline() - time, amount, pool);
+        }
+
+    }
+
+    fn receive_token_wager(&self, amount: u64) -> Result<()> {
+
+        // The stake accounts have already been checked against the market's stake mint
+        let accounts = TransferChecked {
+            from: self.stake_account.as_ref().unwrap().to_account_info(),
+            mint: self.stake_mint.as_ref().unwrap().to_account_info(),
+            to: self.vault.as_ref().unwrap().to_account_info(),
+            authority: self.signer.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.as_ref().unwrap().to_account_info(), accounts);
+
+        token::transfer_checked(cpi_ctx, amount, self.market.stake_decimals)
+
+    }
+
+    fn accrue_payable(
+        &self,
+        params: &MarketParams,
+        purpose: LedgerPurpose,
+        amount: u64,
+    ) -> Result<()> {
 
-        let accounts = Transfer {
-            from,
-            to: self.treasury_auth.to_account_info(),
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = Accrue {
+            payer: self.signer.to_account_info(),
+            caller: self.market.to_account_info(),
+            treasury: self.treasury.to_account_info(),
+            payable: self.payable.to_account_info(),
+            ledger: self.ledger.to_account_info(),
+            system_program: self.system_program.to_account_info(),
         };
 
-        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+        // The treasury takes the lamports and owes them back out to the round's participants
+        // The market signs as its PDA, so only this program can add to what the round owes
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
 
-        transfer(cpi_ctx, amount)
+        accrue(cpi_ctx, self.market.signer(), self.market.round, params.facet.to_string(), purpose, amount)
 
     }
 
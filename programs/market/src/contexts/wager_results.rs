@@ -3,13 +3,14 @@ use std::str::FromStr;
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
-    token::{Mint, Token, TokenAccount}
+    token::{self, Mint, Token, TokenAccount, TransferChecked}
 };
 
 use treasury::{
-    cpi::{accounts::Transact, reimburse},
+    cpi::{accounts::Reimburse, reimburse},
     program::TreasuryProgram,
     self,
+    LedgerPurpose,
     Treasury,
 };
 use voting_tokens::{
@@ -18,10 +19,10 @@ use voting_tokens::{
     program::VotingTokens,
 };
 
-use crate::constants::{PERCENTAGE_WINNINGS_KEPT, TREASURY_AUTHORITY, TREASURY_PROGRAM_ID, VOTE_THRESHOLD, VOTING_TOKENS_MINT_ID, VOTING_TOKENS_PROGRAM_ID};
-use crate::error::{CpiError, FacetError, MintError, ResultsError, TokenError, TreasuryError, VotingError};
-use crate::states::{Bettor, Escrow, Market, MarketParams, MarketState, Poll};
-use crate::utils::functions::compute_returns;
+use crate::constants::{APPEAL_WINDOW, PERCENTAGE_WINNINGS_KEPT, TREASURY_PROGRAM_ID, VOTE_THRESHOLD, VOTING_TOKENS_MINT_ID, VOTING_TOKENS_PROGRAM_ID};
+use crate::error::{AppealError, CpiError, FacetError, MintError, ResultsError, StakeError, TokenError, TreasuryError, VotingError};
+use crate::states::{Appeal, Bettor, Escrow, Market, MarketParams, MarketState, Poll};
+use crate::utils::functions::{compute_returns, final_outcome, forfeited_bond_share, round_claims, scale_decimals, stake_accounts_condition, Stakes};
 
 #[derive(Accounts)]
 #[instruction(params: MarketParams)]
@@ -54,6 +55,11 @@ pub struct WagerResult<'info_wr> {
         bump,
     )]
     pub poll: Account<'info_wr, Poll>,
+    #[account(
+        seeds = [b"appeal", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub appeal: Option<Account<'info_wr, Appeal>>,  // Only required if the poll was appealed
     #[account(mut)]
     pub mint: Account<'info_wr, Mint>,
     #[account(
@@ -65,7 +71,48 @@ pub struct WagerResult<'info_wr> {
     pub recipient: Account<'info_wr, TokenAccount>,
     #[account(mut)]
     pub treasury: Account<'info_wr, Treasury>,
+    pub stake_mint: Option<Account<'info_wr, Mint>>,    // The remaining stake accounts are only required for markets denominated in an SPL token
+    #[account(
+        mut,
+        seeds = [b"vault", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: Option<Account<'info_wr, TokenAccount>>,
+    #[account(mut)]
+    pub stake_account: Option<Account<'info_wr, TokenAccount>>,
     pub treasury_program: Program<'info_wr, TreasuryProgram>,
+    /// CHECK: Checked by the treasury program, which tracks what the round owes
+    #[account(
+        mut,
+        seeds = [b"payable", market.key().as_ref(), market.round.to_le_bytes().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub payable: UncheckedAccount<'info_wr>,
+    /// CHECK: Checked by the treasury program, which keeps the round's share of its balance
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), LedgerPurpose::Stakes.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub stakes_ledger: UncheckedAccount<'info_wr>,
+    /// CHECK: Checked by the treasury program, which keeps the round's appeal bonds
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), LedgerPurpose::Bonds.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub bonds_ledger: UncheckedAccount<'info_wr>,
+    /// CHECK: Created by the treasury program only if the payout falls short
+    #[account(
+        mut,
+        seeds = [b"iou", market.key().as_ref(), market.round.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub iou: UncheckedAccount<'info_wr>,
     pub voting_tokens_program: Program<'info_wr, VotingTokens>,
     pub system_program: Program<'info_wr, System>,
     pub token_program: Program<'info_wr, Token>,
@@ -99,26 +146,46 @@ impl<'info_wr> WagerResult<'info_wr> {
             false => false,
         };
 
+        let time: i64 = Clock::get()?.unix_timestamp;
+
+        let appeal_resolved: bool = match self.appeal.as_ref() {
+            Some(appeal) => appeal.resolved && appeal.round == self.market.round,
+            None => false,
+        };
+
+        let stake_condition: bool = stake_accounts_condition(
+            self.market.stake_mint,
+            self.stake_mint.as_ref(),
+            self.vault.as_ref(),
+            self.escrow.key(),
+            self.stake_account.as_ref(),
+            self.signer.key(),
+        );
+
         // Requirements:                                                        |   Implemented:
         //  - Voting is finished                                                |       √
+        //  - The appeal window has passed without an appeal                    |       √
+        //  - Any appeal has been resolved                                      |       √
         //  - Given address is a bettor                                         |       √
         //  - The person should not yet have had their votes consolidated
         //  - Market should contain the given facet                             |       √
         //  - The token must be the same as that which instantiated the market  |       √
         //  - Treasury authority should be the same as treasury_auth            |       √
-        //  - Treasury authority should be the same as on record                |       √
         //  - ATA needs to be correct                                           |       √
+        //  - Stake accounts should be provided for a token market              |       √
         //  - Mint account ID needs to be correct                               |       √
         //  - Treasury Program needs to be correct                              |       √
         //  - Voting Tokens Program needs to be correct                         |       √
-        require!(self.poll.total_for + self.poll.total_against >= VOTE_THRESHOLD.into(), ResultsError::VotingNotFinished);
+        require!(self.poll.total_outcomes.iter().sum::<u64>() >= VOTE_THRESHOLD.into(), ResultsError::VotingNotFinished);
+        require!(self.poll.appealed || self.poll.closed_at + APPEAL_WINDOW < time, AppealError::AppealWindowOpen);
+        require!(!self.poll.appealed || appeal_resolved, AppealError::AppealNotResolved);
         require!(wagers_count_condition, ResultsError::NotABettor);
         require!(!consolidated_bettors_condition, ResultsError::BettorAlreadyConsolidated);
         require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
         require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
         require!(self.treasury_auth.key() == self.treasury.authority, TreasuryError::TreasuryAuthoritiesDontMatch);
-        require!(self.treasury_auth.key().to_string() == TREASURY_AUTHORITY, TreasuryError::WrongTreasuryAuthority);
         require!(signer_ata == self.recipient.key(), VotingError::IncorrectATA);
+        require!(stake_condition, StakeError::StakeAccountsMissing);
         require!(self.mint.key() == mint_pk, MintError::NotTheRightMintPK);
         require!(self.treasury_program.key().to_string() == TREASURY_PROGRAM_ID, TreasuryError::NotTheRightTreasuryProgramPK);
         require!(self.voting_tokens_program.key().to_string() == VOTING_TOKENS_PROGRAM_ID, MintError::NotTheRightMintProgramPK);
@@ -130,30 +197,50 @@ impl<'info_wr> WagerResult<'info_wr> {
             self.market.state = MarketState::Consolidating;
         }
 
-        if self.poll.total_for == self.poll.total_against {
-            return self.voting_tie();
-        }
-
-        let direction = self.poll.total_for > self.poll.total_against;
+        let outcome: u8 = match final_outcome(&self.poll, self.appeal.as_deref()) {
+            Some(outcome) => outcome,
+            None => return self.voting_tie(params),
+        };
 
         let (bet_returned, winnings_pre) = compute_returns(
-            direction,
-            self.escrow.tot_for,
-            self.escrow.tot_against,
-            self.escrow.tot_underdog,
-            self.bettor.tot_for,
-            self.bettor.tot_against,
-            self.bettor.tot_underdog,
+            outcome,
+            &Stakes {
+                outcomes: &self.escrow.tot_outcomes,
+                underdog: self.escrow.tot_underdog,
+                weighted_outcomes: &self.escrow.weighted_outcomes,
+                weighted_underdog: self.escrow.weighted_underdog,
+            },
+            &Stakes {
+                outcomes: &self.bettor.tot_outcomes,
+                underdog: self.bettor.tot_underdog,
+                weighted_outcomes: &self.bettor.weighted_outcomes,
+                weighted_underdog: self.bettor.weighted_underdog,
+            },
+            self.market.config.underdog_bonus.into(),
         );
 
         if bet_returned == 0 {
             return Ok(())
         }
 
-        let winnings: u64 = (PERCENTAGE_WINNINGS_KEPT*winnings_pre)/100;
+        // Voting tokens are minted in their own decimals, whatever the stake denomination
+        let winnings: u64 = scale_decimals(
+            (PERCENTAGE_WINNINGS_KEPT*winnings_pre)/100,
+            self.market.stake_decimals,
+            self.mint.decimals,
+        );
+
+        // Reimburse bets, along with a share of any forfeited appeal bond (which is always in SOL)
+        let bond_share: u64 = forfeited_bond_share(&self.poll, self.appeal.as_deref(), &self.escrow, outcome, bet_returned);
 
-        // Reimburse bets
-        self.reimburse_sol_wager(bet_returned)?;
+        match self.market.stake_mint {
+            Some(_) => self.return_token_wager(params, bet_returned)?,
+            None => self.reimburse_sol_wager(params, LedgerPurpose::Stakes, bet_returned)?,
+        }
+
+        if bond_share > 0 {
+            self.reimburse_sol_wager(params, LedgerPurpose::Bonds, bond_share)?;
+        }
 
         // Mint and allocate voting tokens
         self.mint_voting_tokens_to_winner(winnings)?;
@@ -164,35 +251,90 @@ impl<'info_wr> WagerResult<'info_wr> {
     }
     
     fn voting_tie(
-        &mut self
+        &mut self,
+        params: &MarketParams,
     ) -> Result<()> {
 
-        let total_bets = self.bettor.tot_for + self.bettor.tot_against + self.bettor.tot_underdog;
-        self.reimburse_sol_wager(total_bets)
+        let total_bets = self.bettor.tot_outcomes.iter().sum::<u64>() + self.bettor.tot_underdog;
+
+        match self.market.stake_mint {
+            Some(_) => self.return_token_wager(params, total_bets),
+            None => self.reimburse_sol_wager(params, LedgerPurpose::Stakes, total_bets),
+        }
+
+    }
+
+    fn return_token_wager(
+        &self,
+        params: &MarketParams,
+        amount: u64,
+    ) -> Result<()> {
+
+        let facet: String = params.facet.to_string();
+        let seeds: &[&[u8]] = &[
+            b"escrow",
+            params.authensus_token.as_ref(),
+            facet.as_bytes(),
+            &[self.escrow.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        // The stake accounts have already been checked against the market's stake mint
+        let accounts = TransferChecked {
+            from: self.vault.as_ref().unwrap().to_account_info(),
+            mint: self.stake_mint.as_ref().unwrap().to_account_info(),
+            to: self.stake_account.as_ref().unwrap().to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+
+        token::transfer_checked(cpi_ctx, amount, self.market.stake_decimals)
 
     }
 
     fn reimburse_sol_wager(
         &self,
+        params: &MarketParams,
+        purpose: LedgerPurpose,
         amount: u64,
     ) -> Result<()> {
 
-        let cpi_accounts = Transact {
-            signer: self.treasury_auth.to_account_info(),
-            coparty: self.signer.to_account_info(),
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = Reimburse {
+            payer: self.signer.to_account_info(),
+            caller: self.market.to_account_info(),
+            coparty: self.signer.to_account_info(),     // This needs to be the bettor
             treasury: self.treasury.to_account_info(),
-            voting_token_account: self.recipient.to_account_info(),
+            payable: self.payable.to_account_info(),
+            ledger: match purpose {
+                LedgerPurpose::Bonds => self.bonds_ledger.to_account_info(),
+                _ => self.stakes_ledger.to_account_info(),
+            },
+            iou: self.iou.to_account_info(),
             system_program: self.system_program.to_account_info(),
-            associated_token_program: self.associated_token_program.to_account_info(),
         };
-        let cpi_ctx = CpiContext::new(
-            self.treasury_program.to_account_info(),
-            cpi_accounts,
-        );
+
+        // Claims on the round are shared out fairly if it runs short
+        let claims: u64 = round_claims(&self.poll, self.appeal.as_deref(), &self.escrow, purpose);
+
+        // The market signs as its PDA, so payouts are limited to what the round owes
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
 
         reimburse(
             cpi_ctx,
+            self.market.signer(),
+            self.market.round,
+            params.facet.to_string(),
+            purpose,
             amount,
+            claims,
         )
 
     }
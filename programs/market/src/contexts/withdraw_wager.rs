@@ -0,0 +1,319 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, TransferChecked}
+};
+
+use treasury::{
+    cpi::{accounts::{CollectFee, Reimburse}, collect_fee, reimburse},
+    program::TreasuryProgram,
+    self,
+    LedgerPurpose,
+    Treasury,
+};
+
+use crate::states::{BetType, Bettor, Escrow, Market, MarketParams, MarketState};
+use crate::constants::BASIS_POINTS;
+use crate::error::{BettingError, FacetError, ResultsError, StakeError, TokenError, TreasuryError};
+use crate::utils::functions::{mul_div, sniping_extension, stake_accounts_condition};
+
+#[derive(Accounts)]
+#[instruction(params: MarketParams)]
+pub struct WithdrawWager<'info_ww> {
+    #[account(mut)]
+    pub treasury_auth: Signer<'info_ww>,
+    #[account(mut)]
+    pub signer: Signer<'info_ww>,
+    #[account(
+        mut,
+        seeds = [b"market", params.authensus_token.as_ref()],
+        bump,
+    )]
+    pub market: Account<'info_ww, Market>,
+    #[account(
+        mut,
+        seeds = [b"escrow", params.authensus_token.as_ref(), params.facet.to_string().as_bytes()],
+        bump,
+    )]
+    pub escrow: Account<'info_ww, Escrow>,
+    #[account(
+        mut,
+        seeds = [b"bettor", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), signer.key().as_ref()],
+        bump,
+    )]
+    pub bettor: Account<'info_ww, Bettor>,
+    #[account(mut)]
+    pub treasury: Account<'info_ww, Treasury>,      // Should already be initialised
+    pub stake_mint: Option<Account<'info_ww, Mint>>, // The remaining accounts are only required for markets denominated in an SPL token
+    #[account(
+        mut,
+        seeds = [b"vault", params.authensus_token.as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: Option<Account<'info_ww, TokenAccount>>,
+    #[account(mut)]
+    pub stake_account: Option<Account<'info_ww, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = stake_mint,
+        associated_token::authority = treasury,
+    )]
+    pub fee_account: Option<Account<'info_ww, TokenAccount>>,  // The treasury's account for exit fees in the stake token
+    pub token_program: Option<Program<'info_ww, Token>>,
+    pub associated_token_program: Option<Program<'info_ww, AssociatedToken>>,
+    /// CHECK: Created and checked by the treasury program, which tracks what the round owes
+    #[account(
+        mut,
+        seeds = [b"payable", market.key().as_ref(), market.round.to_le_bytes().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub payable: UncheckedAccount<'info_ww>,
+    /// CHECK: Created and checked by the treasury program, which keeps the round's share of its balance
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), LedgerPurpose::Stakes.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub stakes_ledger: UncheckedAccount<'info_ww>,
+    /// CHECK: Created and checked by the treasury program, which keeps the fees taken from the round
+    #[account(
+        mut,
+        seeds = [b"ledger", market.key().as_ref(), params.facet.to_string().as_bytes(), market.round.to_le_bytes().as_ref(), LedgerPurpose::Fees.seed().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub fee_ledger: UncheckedAccount<'info_ww>,
+    /// CHECK: Created by the treasury program only if the payout falls short
+    #[account(
+        mut,
+        seeds = [b"iou", market.key().as_ref(), market.round.to_le_bytes().as_ref(), signer.key().as_ref()],
+        bump,
+        seeds::program = treasury_program.key(),
+    )]
+    pub iou: UncheckedAccount<'info_ww>,
+    pub treasury_program: Program<'info_ww, TreasuryProgram>,
+    pub system_program: Program<'info_ww, System>,
+}
+
+impl<'info_ww> WithdrawWager<'info_ww> {
+
+    pub fn withdraw(
+        &mut self,
+        params: &MarketParams,
+        amount: u64,
+        outcome: u8,
+        bet_type: BetType,
+    ) -> Result<()> {
+
+        let time: i64 = Clock::get()?.unix_timestamp;
+
+        let bettors_condition: bool = match self.escrow.bettors.as_ref() {
+            Some(bettors) => bettors.contains(&self.signer.key()),
+            None => false,
+        };
+
+        let stake_condition: bool = self.market.stake_mint.is_none() || (self.token_program.is_some() && self.fee_account.is_some() && stake_accounts_condition(
+            self.market.stake_mint,
+            self.stake_mint.as_ref(),
+            self.vault.as_ref(),
+            self.escrow.key(),
+            self.stake_account.as_ref(),
+            self.signer.key(),
+        ));
+
+        // Requirements:                                                        |   Implemented:
+        //  - Market should be in a betting state                               |       √
+        //  - Stake accounts should be provided for a token market              |       √
+        //  - Market should contain the given facet                             |       √
+        //  - The outcome must exist for the given facet                        |       √
+        //  - The token must be the same as that which instantiated the market  |       √
+        //  - Treasury authority should be the same as treasury_auth            |       √
+        //  - Signer should be a bettor in the escrow                           |       √
+        //  - Amount should be non-zero                                         |       √
+        require!(self.market.state == MarketState::Betting, BettingError::MarketNotInBettingState);
+        require!(stake_condition, StakeError::StakeAccountsMissing);
+        require!(self.market.facets.contains(&params.facet), FacetError::FacetNotInMarket);
+        require!(outcome < params.facet.outcomes(), FacetError::OutcomeOutOfRange);
+        require!(self.market.token == params.authensus_token, TokenError::NotTheSameToken);
+        require!(self.treasury_auth.key() == self.treasury.authority, TreasuryError::TreasuryAuthoritiesDontMatch);
+        require!(bettors_condition, ResultsError::NotABettor);
+        require!(amount > 0, BettingError::NothingToWithdraw);
+
+        // If the market has timed out then abort the withdrawal after setting the market state to MarketState::Voting
+        if self.market.betting_deadline() < time {
+
+            self.market.state = MarketState::Voting;
+
+            return Ok(())
+        }
+
+        // A large withdrawal late in betting moves the odds as much as a large bet, so it extends the deadline in the same way
+        let pool: u64 = self.escrow.tot_outcomes.iter().sum::<u64>() + self.escrow.tot_underdog;
+
+        if let Some(rule) = self.market.config.anti_sniping.as_ref() {
+            self.market.extension = sniping_extension(rule, self.market.extension, self.market.betting_deadline() - time, amount, pool);
+        }
+
+        // Take the amount off the position along with the matching escrow total, and the same fraction of its time-weighted stake
+        match bet_type {
+            BetType::Normal => {
+                let index: usize = outcome as usize;
+
+                require!(self.bettor.tot_outcomes[index] >= amount, BettingError::WithdrawalTooLarge);
+
+                let weighted_amount: u64 = mul_div(self.bettor.weighted_outcomes[index], amount, self.bettor.tot_outcomes[index]);
+
+                self.bettor.tot_outcomes[index] -= amount;
+                self.bettor.weighted_outcomes[index] -= weighted_amount;
+                self.escrow.tot_outcomes[index] -= amount;
+                self.escrow.weighted_outcomes[index] -= weighted_amount;
+            },
+            BetType::Underdog => {
+                require!(self.bettor.tot_underdog >= amount, BettingError::WithdrawalTooLarge);
+
+                let weighted_amount: u64 = mul_div(self.bettor.weighted_underdog, amount, self.bettor.tot_underdog);
+
+                self.bettor.tot_underdog -= amount;
+                self.bettor.weighted_underdog -= weighted_amount;
+                self.escrow.tot_underdog -= amount;
+                self.escrow.weighted_underdog -= weighted_amount;
+            },
+        }
+
+        // A position is either withdrawn in full or left no smaller than a bet could be
+        let remainder: u64 = match bet_type {
+            BetType::Normal => self.bettor.tot_outcomes[outcome as usize],
+            BetType::Underdog => self.bettor.tot_underdog,
+        };
+
+        require!(remainder == 0 || remainder >= self.market.config.min_bet, BettingError::RemainderTooSmall);
+
+        // The exit fee goes to the treasury's fee ledger, or to its fee account for a token market
+        let fee: u64 = mul_div(amount, self.market.config.exit_fee.into(), BASIS_POINTS);
+
+        match self.market.stake_mint {
+            Some(_) => {
+                self.return_token_wager(params, amount - fee)?;
+
+                if fee > 0 {
+                    self.collect_token_exit_fee(params, fee)?;
+                }
+            },
+            None => {
+                self.return_sol_wager(params, amount - fee)?;
+
+                if fee > 0 {
+                    self.collect_exit_fee(params, fee)?;
+                }
+            },
+        }
+
+        // Bettors with nothing left in the market no longer count towards its wagers
+        if self.bettor.tot_outcomes.iter().all(|tot| *tot == 0) && self.bettor.tot_underdog == 0 {
+            let signer: Pubkey = self.signer.key();
+
+            if let Some(bettors) = self.escrow.bettors.as_mut() {
+                bettors.retain(|bettor| *bettor != signer);
+            }
+        }
+
+        Ok(())
+
+    }
+
+    fn return_sol_wager(&self, params: &MarketParams, amount: u64) -> Result<()> {
+
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = Reimburse {
+            payer: self.signer.to_account_info(),
+            caller: self.market.to_account_info(),
+            coparty: self.signer.to_account_info(),
+            treasury: self.treasury.to_account_info(),
+            payable: self.payable.to_account_info(),
+            ledger: self.stakes_ledger.to_account_info(),
+            iou: self.iou.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        };
+
+        // The market signs as its PDA, so only this program can draw on what the round is owed
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        // Withdrawals come before the round is resolved, so there are no claims to share out yet
+        reimburse(cpi_ctx, self.market.signer(), self.market.round, params.facet.to_string(), LedgerPurpose::Stakes, amount, 0)
+
+    }
+
+    fn return_token_wager(&self, params: &MarketParams, amount: u64) -> Result<()> {
+
+        self.transfer_from_vault(params, self.stake_account.as_ref().unwrap().to_account_info(), amount)
+
+    }
+
+    fn collect_token_exit_fee(&self, params: &MarketParams, amount: u64) -> Result<()> {
+
+        // The fee leaves the vault, so it is no longer owed to any bettor of the round
+        self.transfer_from_vault(params, self.fee_account.as_ref().unwrap().to_account_info(), amount)
+
+    }
+
+    fn transfer_from_vault(&self, params: &MarketParams, to: AccountInfo<'info_ww>, amount: u64) -> Result<()> {
+
+        let facet: String = params.facet.to_string();
+        let seeds: &[&[u8]] = &[
+            b"escrow",
+            params.authensus_token.as_ref(),
+            facet.as_bytes(),
+            &[self.escrow.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        // The stake accounts have already been checked against the market's stake mint
+        let accounts = TransferChecked {
+            from: self.vault.as_ref().unwrap().to_account_info(),
+            mint: self.stake_mint.as_ref().unwrap().to_account_info(),
+            to,
+            authority: self.escrow.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.as_ref().unwrap().to_account_info(), accounts, signer_seeds);
+
+        token::transfer_checked(cpi_ctx, amount, self.market.stake_decimals)
+
+    }
+
+    fn collect_exit_fee(&self, params: &MarketParams, amount: u64) -> Result<()> {
+
+        let seeds: &[&[u8]] = &[
+            b"market",
+            self.market.token.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = CollectFee {
+            payer: self.signer.to_account_info(),
+            caller: self.market.to_account_info(),
+            treasury: self.treasury.to_account_info(),
+            payable: self.payable.to_account_info(),
+            ledger: self.stakes_ledger.to_account_info(),
+            fee_ledger: self.fee_ledger.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        };
+
+        // The fee moves from the round's stakes to its fees, so it is no longer owed to a bettor
+        let cpi_ctx = CpiContext::new_with_signer(self.treasury_program.to_account_info(), cpi_accounts, signer_seeds);
+
+        collect_fee(cpi_ctx, self.market.signer(), self.market.round, params.facet.to_string(), LedgerPurpose::Stakes, amount)
+
+    }
+
+}
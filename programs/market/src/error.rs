@@ -12,6 +12,27 @@ pub enum InitError {
     #[msg("The betting timeout is smaller than the minimum allowed (1 day)")]
     TimeoutTooSmall,
 
+    #[msg("The exit fee is larger than the maximum allowed (10%)")]
+    ExitFeeTooLarge,
+
+    #[msg("The anti-sniping rule has an empty window, extension or threshold")]
+    InvalidAntiSnipingRule,
+
+    #[msg("The anti-sniping extension is larger than the maximum allowed (1 day)")]
+    SnipingExtensionTooLarge,
+
+    #[msg("The max bet must be at least the min bet and the max pool share must be between 0 and 100%")]
+    InvalidBetLimits,
+
+    #[msg("Outcome tokens are only available in markets denominated in an SPL token")]
+    OutcomeTokensNeedStakeMint,
+
+    #[msg("The initial time weight must be between 1x and the maximum allowed (3x)")]
+    InvalidTimeWeighting,
+
+    #[msg("The underdog bonus must be between 1x and what the fee can fund")]
+    InvalidUnderdogBonus,
+
 }
 
 #[error_code]
@@ -23,6 +44,18 @@ pub enum FacetError {
     #[msg("The poll and escrow must be for the same facet")]
     NotTheSameFacet,
 
+    #[msg("The selected outcome does not exist for the given facet")]
+    OutcomeOutOfRange,
+
+    #[msg("Votes on a scalar facet must be cast with a value")]
+    ScalarFacet,
+
+    #[msg("Only scalar facets can be voted on with a value")]
+    NotAScalarFacet,
+
+    #[msg("The value is outside the range of the scalar facet")]
+    ValueOutOfRange,
+
 }
 
 #[error_code]
@@ -37,6 +70,9 @@ pub enum TreasuryError {
     #[msg("The account supplied is not for the Treasury Program")]
     NotTheRightTreasuryProgramPK,
 
+    #[msg("The round's payable and ledger accounts must be supplied for a market staked in SOL")]
+    LedgerAccountsMissing,
+
 }
 
 #[error_code]
@@ -87,15 +123,81 @@ pub enum BettingError {
     #[msg("There need to be standard wagers placed before an underdog bet can be placed")]
     UnderdogBetTooEarly,
 
-    #[msg("Cannot place underdog bet with regular bet already in place")]
-    UnderdogWithOtherBet,
-
-    #[msg("Cannot place another bet when underdog bet is in place")]
-    BetWithUnderdogBet,
-
     #[msg("Too many bettors in the market")]
     TooManyBettors,
 
+    #[msg("The amount to withdraw must be greater than zero")]
+    NothingToWithdraw,
+
+    #[msg("Cannot withdraw more than has been wagered")]
+    WithdrawalTooLarge,
+
+    #[msg("The projected payout is below the minimum accepted")]
+    SlippageExceeded,
+
+    #[msg("The bet is smaller than the minimum allowed in this market")]
+    BetTooSmall,
+
+    #[msg("The bet is larger than the maximum allowed in this market")]
+    BetTooLarge,
+
+    #[msg("The bet would give the bettor too large a share of the pool")]
+    PoolShareTooLarge,
+
+    #[msg("The withdrawal would leave a position smaller than the minimum bet")]
+    RemainderTooSmall,
+
+}
+
+#[error_code]
+pub enum StakeError {
+
+    #[msg("The stake mint, vault and token accounts for this market must all be provided")]
+    StakeAccountsMissing,
+
+}
+
+#[error_code]
+pub enum OutcomeTokenError {
+
+    #[msg("Outcome tokens are not enabled for this market")]
+    OutcomeTokensDisabled,
+
+    #[msg("Cannot tokenise more than the position held on that outcome")]
+    PositionTooSmall,
+
+    #[msg("The amount of outcome tokens must be greater than zero")]
+    NothingToRedeem,
+
+}
+
+#[error_code]
+pub enum LmsrError {
+
+    #[msg("The liquidity parameter must be between the minimum and maximum allowed")]
+    InvalidLiquidity,
+
+    #[msg("Market makers are only available in markets denominated in SOL")]
+    NotASolMarket,
+
+    #[msg("The number of shares must be greater than zero")]
+    NoShares,
+
+    #[msg("The cost of the shares is above the maximum accepted")]
+    CostTooHigh,
+
+    #[msg("The refund for the shares is below the minimum accepted")]
+    RefundTooLow,
+
+    #[msg("Cannot sell more shares than are held")]
+    InsufficientShares,
+
+    #[msg("The market maker does not hold enough to cover this payout")]
+    InsufficientPoolFunds,
+
+    #[msg("The trade would take the shares of an outcome beyond what the market maker can price")]
+    SharesOutOfRange,
+
 }
 
 #[error_code]
@@ -139,6 +241,87 @@ pub enum VotingError {
 
 }
 
+#[error_code]
+pub enum AppealError {
+
+    #[msg("The poll has not yet reached its threshold")]
+    PollNotClosed,
+
+    #[msg("The appeal window for this poll has closed")]
+    AppealWindowClosed,
+
+    #[msg("The appeal window for this poll is still open")]
+    AppealWindowOpen,
+
+    #[msg("The outcome of this poll has already been appealed")]
+    AlreadyAppealed,
+
+    #[msg("A tied poll cannot be appealed")]
+    CannotAppealTie,
+
+    #[msg("The appeal must argue for a different outcome to the poll")]
+    SameOutcomeAsPoll,
+
+    #[msg("The appeal bond is smaller than the minimum allowed")]
+    BondTooLow,
+
+    #[msg("The appeal account must be provided for an appealed poll")]
+    AppealAccountMissing,
+
+    #[msg("The appeal is not for this round of the market")]
+    WrongRound,
+
+    #[msg("The appeal poll has not yet reached its threshold")]
+    AppealNotFinished,
+
+    #[msg("The appeal has not yet been resolved")]
+    AppealNotResolved,
+
+    #[msg("The appeal has already been resolved")]
+    AppealAlreadyResolved,
+
+    #[msg("An appeal's bond and rent can only be returned to its appellant")]
+    WrongAppellant,
+
+    #[msg("The appellant cannot vote in their own appeal")]
+    AppellantCannotVote,
+
+}
+
+#[error_code]
+pub enum EvidenceError {
+
+    #[msg("Evidence was provided without an account to store it in")]
+    EvidenceAccountMissing,
+
+    #[msg("An evidence account was provided without any evidence")]
+    EvidenceMissing,
+
+    #[msg("The evidence URI cannot be empty")]
+    EmptyUri,
+
+    #[msg("The evidence URI is longer than the maximum allowed")]
+    UriTooLong,
+
+}
+
+#[error_code]
+pub enum ScoreError {
+
+    #[msg("A weight must be given for every facet in the market")]
+    MissingFacetWeight,
+
+    #[msg("Each facet can only be given one weight")]
+    DuplicateFacetWeight,
+
+    #[msg("At least one facet must have a non-zero weight")]
+    AllWeightsZero,
+
+    #[msg("The score account does not correspond to the market")]
+    WrongScoreAccount,
+
+}
+
 #[error_code]
 pub enum ResultsError {
 
@@ -172,6 +355,29 @@ pub enum ResultsError {
     #[msg("Not all of the votes have been consolidated")]
     NotAllVotesConsolidated,
 
+    #[msg("The remaining accounts must be whole groups of accounts for each participant")]
+    BatchAccountsMismatch,
+
+    #[msg("Too many participants to settle in one call")]
+    BatchTooLarge,
+
+}
+
+#[error_code]
+pub enum CloseError {
+
+    #[msg("The bettor still has bets in the round which have not been consolidated")]
+    BettorNotConsolidated,
+
+    #[msg("The voter still has votes in the poll which have not been consolidated")]
+    VoterNotConsolidated,
+
+    #[msg("The round can only be closed once the market has been called")]
+    RoundNotCalled,
+
+    #[msg("Rent can only be returned to the account which paid it")]
+    WrongRentPayer,
+
 }
 
 #[error_code]
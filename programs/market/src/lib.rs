@@ -25,6 +25,7 @@ pub mod parimutuel_market {
         authensus_token: Pubkey,
         facets: Vec<Facet>,
         timeout: i64,
+        config: MarketConfig,
     ) -> Result<()> {
 
         ctx.accounts.init_market(
@@ -32,6 +33,33 @@ pub mod parimutuel_market {
             authensus_token,
             facets,
             timeout,
+            config,
+        )
+
+    }
+
+    pub fn set_score_weights(
+        ctx: Context<SetScoreWeights>,
+        authensus_token: Pubkey,
+        weights: Vec<FacetWeight>,
+    ) -> Result<()> {
+
+        ctx.accounts.set_weights(
+            authensus_token,
+            weights,
+        )
+
+    }
+
+    pub fn configure_market(
+        ctx: Context<ConfigureMarket>,
+        authensus_token: Pubkey,
+        config: MarketConfig,
+    ) -> Result<()> {
+
+        ctx.accounts.set_config(
+            authensus_token,
+            config,
         )
 
     }
@@ -40,7 +68,7 @@ pub mod parimutuel_market {
         ctx: Context<StartMarket>,
         params: MarketParams,
         amount: u64,
-        direction: bool,
+        outcome: u8,
     ) -> Result<()> {
 
         ctx.accounts.start(
@@ -52,7 +80,7 @@ pub mod parimutuel_market {
             &ctx.bumps,
             &params,
             amount,
-            direction
+            outcome
         )
 
     }
@@ -61,14 +89,16 @@ pub mod parimutuel_market {
         ctx: Context<Wager>,
         params: MarketParams,
         amount: u64,
-        direction: bool,
+        outcome: u8,
+        min_multiplier: Option<u64>,
     ) -> Result<()> {
 
         ctx.accounts.place_wager(
             &ctx.bumps,
             &params,
             amount,
-            direction,
+            outcome,
+            min_multiplier,
         )
 
     }
@@ -77,38 +107,193 @@ pub mod parimutuel_market {
         ctx: Context<Wager>,
         params: MarketParams,
         amount: u64,
+        min_multiplier: Option<u64>,
     ) -> Result<()> {
 
         ctx.accounts.underdog_bet(
             &ctx.bumps,
             &params,
-            amount
+            amount,
+            min_multiplier,
         )
         
     }
 
+    pub fn withdraw_wager(
+        ctx: Context<WithdrawWager>,
+        params: MarketParams,
+        amount: u64,
+        outcome: u8,
+        bet_type: BetType,
+    ) -> Result<()> {
+
+        ctx.accounts.withdraw(
+            &params,
+            amount,
+            outcome,
+            bet_type,
+        )
+
+    }
+
+    pub fn tokenise_position(
+        ctx: Context<TokenisePosition>,
+        params: MarketParams,
+        amount: u64,
+        outcome: u8,
+    ) -> Result<()> {
+
+        ctx.accounts.tokenise(
+            &params,
+            amount,
+            outcome,
+        )
+
+    }
+
+    pub fn quote(
+        ctx: Context<GetQuote>,
+        params: MarketParams,
+        amount: u64,
+        outcome: u8,
+        bet_type: BetType,
+    ) -> Result<Quote> {
+
+        ctx.accounts.get_quote(
+            &params,
+            amount,
+            outcome,
+            bet_type,
+        )
+
+    }
+
+    pub fn open_lmsr(
+        ctx: Context<OpenLmsr>,
+        params: MarketParams,
+        liquidity: u64,
+    ) -> Result<()> {
+
+        ctx.accounts.open(
+            &ctx.bumps,
+            &params,
+            liquidity,
+        )
+
+    }
+
+    pub fn buy_shares(
+        ctx: Context<TradeLmsr>,
+        params: MarketParams,
+        outcome: u8,
+        amount: u64,
+        max_cost: u64,
+    ) -> Result<()> {
+
+        ctx.accounts.buy(
+            &ctx.bumps,
+            &params,
+            outcome,
+            amount,
+            max_cost,
+        )
+
+    }
+
+    pub fn sell_shares(
+        ctx: Context<TradeLmsr>,
+        params: MarketParams,
+        outcome: u8,
+        amount: u64,
+        min_refund: u64,
+    ) -> Result<()> {
+
+        ctx.accounts.sell(
+            &params,
+            outcome,
+            amount,
+            min_refund,
+        )
+
+    }
+
+    pub fn get_lmsr_prices(
+        ctx: Context<GetLmsrPrices>,
+        _params: MarketParams,
+        _round: u16,
+    ) -> Result<Vec<u16>> {
+
+        ctx.accounts.get_prices()
+
+    }
+
     pub fn vote(
         ctx: Context<Vote>,
         params: MarketParams,
         amount: u64,
-        direction: bool,
+        outcome: u8,
+        evidence: Option<EvidenceParams>,
     ) -> Result<()> {
 
         ctx.accounts.add_vote(
             &ctx.bumps,
             &params,
             amount,
-            direction
+            outcome,
+            evidence,
         )
         
     }
 
+    pub fn scalar_vote(
+        ctx: Context<Vote>,
+        params: MarketParams,
+        amount: u64,
+        value: u16,
+        evidence: Option<EvidenceParams>,
+    ) -> Result<()> {
+
+        ctx.accounts.add_scalar_vote(
+            &ctx.bumps,
+            &params,
+            amount,
+            value,
+            evidence,
+        )
+
+    }
+
+    pub fn open_appeal(
+        ctx: Context<OpenAppeal>,
+        params: MarketParams,
+        bond: u64,
+        outcome: u8,
+    ) -> Result<()> {
+
+        ctx.accounts.open_appeal(
+            &ctx.bumps,
+            &params,
+            bond,
+            outcome,
+        )
+
+    }
+
+    pub fn resolve_appeal(
+        ctx: Context<ResolveAppeal>,
+        params: MarketParams,
+    ) -> Result<()> {
+
+        ctx.accounts.resolve(&params)
+
+    }
+
     pub fn voter_results(
         ctx: Context<VoterResult>,
         params: MarketParams,
     ) -> Result<()> {
 
-        ctx.accounts.distribute_sol_to_voter(&params)
+        ctx.accounts.distribute_votes_to_voter(&params)
 
     }
 
@@ -121,14 +306,108 @@ pub mod parimutuel_market {
 
     }
 
+    pub fn batch_voter_results<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchVoterResults<'info>>,
+        params: MarketParams,
+    ) -> Result<u8> {
+
+        ctx.accounts.settle_voters(&params, ctx.remaining_accounts)
+
+    }
+
+    pub fn batch_wager_results<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchWagerResults<'info>>,
+        params: MarketParams,
+    ) -> Result<u8> {
+
+        ctx.accounts.settle_bettors(&params, ctx.remaining_accounts)
+
+    }
+
+    pub fn redeem_outcome_tokens(
+        ctx: Context<RedeemOutcomeTokens>,
+        params: MarketParams,
+        round: u16,
+        outcome: u8,
+        amount: u64,
+    ) -> Result<()> {
+
+        ctx.accounts.redeem(
+            &ctx.bumps,
+            &params,
+            round,
+            outcome,
+            amount,
+        )
+
+    }
+
+    pub fn redeem_lmsr_shares(
+        ctx: Context<RedeemLmsr>,
+        params: MarketParams,
+        _round: u16,
+    ) -> Result<()> {
+
+        ctx.accounts.redeem(&params)
+
+    }
+
+    pub fn sweep_lmsr(
+        ctx: Context<SweepLmsr>,
+        params: MarketParams,
+        round: u16,
+    ) -> Result<u64> {
+
+        ctx.accounts.sweep(&params, round)
+
+    }
+
     pub fn call_market(
         ctx: Context<CallMarket>,
         params: MarketParams,
     ) -> Result<()> {
 
         ctx.accounts.end(
+            &ctx.bumps,
             &params,
         )
 
     }
+
+    pub fn close_bettor(
+        ctx: Context<CloseBettor>,
+        _params: MarketParams,
+    ) -> Result<()> {
+
+        ctx.accounts.close()
+
+    }
+
+    pub fn close_voter(
+        ctx: Context<CloseVoter>,
+        _params: MarketParams,
+    ) -> Result<()> {
+
+        ctx.accounts.close()
+
+    }
+
+    pub fn close_round(
+        ctx: Context<CloseRound>,
+        _params: MarketParams,
+    ) -> Result<()> {
+
+        ctx.accounts.close()
+
+    }
+
+    pub fn get_verdict(
+        ctx: Context<GetVerdict>,
+        _params: MarketParams,
+        _round: u16,
+    ) -> Result<Verdict> {
+
+        ctx.accounts.get_verdict()
+
+    }
 }
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use super::Facet;
+use crate::constants::{APPEAL_VOTE_THRESHOLD, MAX_OUTCOMES, MAX_SCALAR_POINTS};
+
+#[account]
+#[derive(InitSpace)]
+pub struct Appeal {
+    pub bump: u8,                                   // Bump
+    pub market: Pubkey,                             // Authensus token of the market (not the market PDA)
+    pub facet: Facet,                               // The facet whose poll outcome is being appealed
+    pub round: u16,                                 // The round of the market in which the appeal was lodged
+    pub appellant: Pubkey,                          // The person who posted the appeal bond
+    pub bond: u64,                                  // Size of the appeal bond (lamports)
+    pub outcome: u8,                                // The outcome argued for by the appellant (different to the original poll)
+    pub opened_at: i64,                             // Time at which the appeal was lodged
+    pub closed_at: i64,                             // Time at which the appeal poll reached its threshold
+    #[max_len(APPEAL_VOTE_THRESHOLD)]
+    pub voters: Option<Vec<Pubkey>>,                // Everyone who has placed a vote in the appeal poll
+    #[max_len(APPEAL_VOTE_THRESHOLD)]
+    pub voters_consolidated: Option<Vec<Pubkey>>,   // Count of the number of appeal voters whose winnings have been calculated and reimbursed
+    #[max_len(MAX_OUTCOMES)]
+    pub total_outcomes: Vec<u64>,                   // Total appeal votes for each outcome of the facet
+    #[max_len(MAX_SCALAR_POINTS)]
+    pub value_weights: Vec<u64>,                    // Total appeal vote amount for each value of a scalar facet
+    pub resolved: bool,                             // Whether the bond has been settled and results can be consolidated
+    pub overturned: bool,                           // Whether the appeal overrode the original poll outcome
+}
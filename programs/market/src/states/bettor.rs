@@ -1,6 +1,7 @@
-use anchor_lang::prelude::*;
+use anchor_lang::prelude::{borsh::{BorshSerialize, BorshDeserialize}, *};
 
 use crate::states::Facet;
+use crate::constants::MAX_OUTCOMES;
 
 #[account]
 #[derive(InitSpace)]
@@ -9,12 +10,21 @@ pub struct Bettor {
     pub pk: Pubkey,
     pub market: Pubkey,
     pub facet: Facet,
-    pub tot_for: u64,
-    pub tot_against: u64,
+    #[max_len(MAX_OUTCOMES)]
+    pub tot_outcomes: Vec<u64>,
     pub tot_underdog: u64,
+    #[max_len(MAX_OUTCOMES)]
+    pub weighted_outcomes: Vec<u64>,
+    pub weighted_underdog: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, InitSpace, PartialEq)]
+pub enum BetType {
+    Normal,     // A bet on a single outcome
+    Underdog,   // A bet assigned at settlement to whichever outcome had the smallest pool at close
 }
 
 // impl Space for Bettor {
-//     // Discriminator (8) + pk (32) + market (32) + facet (?) + tot_for (8) + tot_against (8) + tot_underdog (8)
-//     const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8;  // INCOMPLETE
+//     // Discriminator (8) + pk (32) + market (32) + facet (?) + tot_outcomes (?) + tot_underdog (8)
+//     const INIT_SPACE: usize = 8 + 32 + 32 + 8;  // INCOMPLETE
 // }
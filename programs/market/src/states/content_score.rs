@@ -0,0 +1,42 @@
+use anchor_lang::prelude::{borsh::{BorshSerialize, BorshDeserialize}, *};
+
+use super::Facet;
+use crate::constants::SCORE_HISTORY_LENGTH;
+
+#[account]
+#[derive(InitSpace)]
+pub struct ContentScore {
+    pub bump: u8,                       // Bump
+    pub token: Pubkey,                  // Authensus token to which the score corresponds
+    #[max_len(8)]
+    pub weights: Vec<FacetWeight>,      // Weight given to each facet of the market in the composite score
+    #[max_len(8)]
+    pub facet_scores: Vec<FacetScore>,  // Most recent score of each facet that has been settled
+    pub score: u16,                     // Weighted composite score across all settled facets (basis points)
+    #[max_len(SCORE_HISTORY_LENGTH)]
+    pub history: Vec<ScoreRecord>,      // Ring buffer of the most recent settlements
+    pub head: u16,                      // Index in the history at which the next record will be written
+    pub updated_at: i64,                // Time at which the score was last updated
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, InitSpace, PartialEq)]
+pub struct FacetWeight {
+    pub facet: Facet,
+    pub weight: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, InitSpace, PartialEq)]
+pub struct FacetScore {
+    pub facet: Facet,
+    pub round: u16,
+    pub score: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, InitSpace, PartialEq)]
+pub struct ScoreRecord {
+    pub facet: Facet,       // The facet which was settled
+    pub round: u16,         // The round of the market which was settled
+    pub facet_score: u16,   // The score given to the facet in that round (basis points)
+    pub score: u16,         // The composite score after the settlement (basis points)
+    pub timestamp: i64,     // Time of the settlement
+}
@@ -1,20 +1,24 @@
 use anchor_lang::prelude::*;
 
 use crate::states::Facet;
-use crate::constants::MAX_WAGERS;
+use crate::constants::{MAX_OUTCOMES, MAX_WAGERS};
 
 #[account]
 #[derive(InitSpace)]
 pub struct Escrow {
     pub bump: u8,                                   // Bump
     pub initialiser: Pubkey,                        // The pubkey of the person who initialised this round of the market
-    pub market: Pubkey,                             // The pubkey of the market account
+    pub rent_payer: Pubkey,                         // The pubkey of the person who paid for the escrow and poll, to whom close_round returns their rent
+    pub market: Pubkey,                             // Authensus token of the market (not the market PDA)
     pub facet: Facet,                               // The facet for which the escrow exists within the market
     #[max_len(MAX_WAGERS)]
     pub bettors: Option<Vec<Pubkey>>,               // Everyone who has placed a bet in escrow
     #[max_len(MAX_WAGERS)]
     pub bettors_consolidated: Option<Vec<Pubkey>>,  // Count of the number of bettors whose winnings have been calculated and reimbursed
-    pub tot_for: u64,                               // Total amount in normal bets for
-    pub tot_against: u64,                           // Total amount in normal bets against
+    #[max_len(MAX_OUTCOMES)]
+    pub tot_outcomes: Vec<u64>,                     // Total amount in normal bets on each outcome
     pub tot_underdog: u64,                          // Total amount in underdog bets
+    #[max_len(MAX_OUTCOMES)]
+    pub weighted_outcomes: Vec<u64>,                // Total time-weighted amount in normal bets on each outcome
+    pub weighted_underdog: u64,                     // Total time-weighted amount in underdog bets
 }
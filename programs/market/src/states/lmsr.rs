@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use super::Facet;
+use crate::constants::MAX_OUTCOMES;
+
+#[account]
+#[derive(InitSpace)]
+pub struct LmsrPool {
+    pub bump: u8,                   // Bump
+    pub market: Pubkey,             // Authensus token of the market (not the market PDA)
+    pub facet: Facet,               // The facet whose outcomes are traded
+    pub round: u16,                 // The round of the market in which the pool trades
+    pub liquidity: u64,             // Liquidity parameter b of the scoring rule (lamports)
+    pub subsidy: u64,               // Amount seeded by the treasury, covering the maker's worst-case loss of b*ln(n)
+    #[max_len(MAX_OUTCOMES)]
+    pub shares: Vec<u64>,           // Outstanding shares of each outcome, each paying one lamport if the outcome wins
+    pub opened_at: i64,             // Time at which the treasury seeded the pool
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct LmsrPosition {
+    pub bump: u8,                   // Bump
+    pub pk: Pubkey,                 // The pubkey of the trader
+    pub market: Pubkey,             // Authensus token of the market (not the market PDA)
+    pub facet: Facet,               // The facet whose outcomes are traded
+    pub round: u16,                 // The round of the market in which the shares were bought
+    #[max_len(MAX_OUTCOMES)]
+    pub shares: Vec<u64>,           // Shares held in each outcome
+}
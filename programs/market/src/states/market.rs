@@ -1,16 +1,70 @@
 use anchor_lang::prelude::{borsh::{BorshSerialize, BorshDeserialize}, *};
+use treasury::MarketSigner;
 
 #[account]
 #[derive(InitSpace)]
 pub struct Market {
-    pub bump: u8,           // Bump
-    pub token: Pubkey,      // Authensus token to which the market corresponds
+    pub bump: u8,                   // Bump
+    pub token: Pubkey,              // Authensus token to which the market corresponds
     #[max_len(8)]
-    pub facets: Vec<Facet>, // Vector of Facets around which wagers can be made and votes must be cast
-    pub start_time: i64,    // Time at which the most recent wagers markets started
-    pub timeout: i64,       // Total time for which the wagers markets will operate
-    pub state: MarketState, // Current state of the market
-    pub round: u16,         // Number of this round of the market
+    pub facets: Vec<Facet>,         // Vector of Facets around which wagers can be made and votes must be cast
+    pub start_time: i64,            // Time at which the most recent wagers markets started
+    pub timeout: i64,               // Total time for which the wagers markets will operate
+    pub extension: i64,             // Time by which late bets have pushed back the end of this round's betting
+    pub state: MarketState,         // Current state of the market
+    pub round: u16,                 // Number of this round of the market
+    pub config: MarketConfig,       // Rules under which wagers are placed and withdrawn
+    pub stake_mint: Option<Pubkey>, // Mint of the SPL token in which stakes are denominated (None for native SOL)
+    pub stake_decimals: u8,         // Decimals of the stake denomination
+}
+
+impl Market {
+    pub fn betting_deadline(&self) -> i64 {
+        self.start_time + self.timeout + self.extension
+    }
+
+    // Lets the treasury rebuild the market's address when it signs a CPI
+    pub fn signer(&self) -> MarketSigner {
+        MarketSigner { token: self.token, bump: self.bump }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, InitSpace, PartialEq)]
+pub struct MarketConfig {
+    pub exit_fee: u16,                          // Fee kept from a wager withdrawn during betting (basis points)
+    pub anti_sniping: Option<AntiSnipingRule>,  // Extension of the betting deadline for large, late bets
+    pub min_bet: u64,                           // Min amount of a single bet
+    pub max_bet: Option<u64>,                   // Max amount of a single bet
+    pub max_pool_share: Option<u16>,            // Max share of any one pool which a single bettor can hold (basis points)
+    pub outcome_tokens: bool,                   // Whether positions can be minted as transferable outcome tokens
+    pub time_weighting: Option<TimeWeighting>,  // Extra share of the winnings for stakes placed early in betting
+    pub underdog_bonus: u16,                    // Multiplier on the winnings of underdog bets, funded from the fee (basis points)
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, InitSpace, PartialEq)]
+pub struct TimeWeighting {
+    pub initial_weight: u16,    // Weight of a stake placed as betting opens, falling to 1x at the deadline (basis points)
+    pub curve: WeightCurve,     // Shape of the fall in weight over the betting period
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, InitSpace, PartialEq)]
+pub enum WeightCurve {
+    Linear,     // Weight falls evenly over the betting period
+    Quadratic,  // Weight falls slowly at first and quickly towards the deadline
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, InitSpace, PartialEq)]
+pub struct AntiSnipingRule {
+    pub window: i64,                // Time before the deadline within which large bets extend it (ms)
+    pub extension: i64,             // Time added to the deadline by each large, late bet (ms)
+    pub max_extension: i64,         // Max total time by which the deadline can be extended in a round (ms)
+    pub threshold: SnipeThreshold,  // Size above which a bet counts as large
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, InitSpace, PartialEq)]
+pub enum SnipeThreshold {
+    Absolute(u64),  // Amount staked
+    PoolShare(u16), // Share of the facet's pools after the bet (basis points)
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, InitSpace, PartialEq)]
@@ -33,15 +87,60 @@ pub enum Facet {
     Truthfulness,
     Originality,
     Authenticity,
+    Credibility,
     // TODO: Finish this
 }
 
-impl ToString for Facet {
-    fn to_string(&self) -> String {
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, InitSpace, PartialEq)]
+pub struct ScalarRange {
+    pub min: u16,       // Lowest value which can be voted for
+    pub max: u16,       // Highest value which can be voted for
+    pub buckets: u8,    // Number of equally-sized ranges on which wagers can be placed
+}
+
+impl Facet {
+    // Number of outcomes on which wagers and votes can be placed for the facet
+    // Outcomes are ordered from least to most affirmative, so for binary facets 0 is false and 1 is true
+    pub fn outcomes(&self) -> u8 {
+        match self {
+            Facet::Truthfulness => 2,   // False / True
+            Facet::Originality => 3,    // Plagiarised / Derivative / Original
+            Facet::Authenticity => 2,   // Inauthentic / Authentic
+            Facet::Credibility => 5,    // Ranges of the credibility score (see scalar_range)
+        }
+    }
+
+    // Scalar facets are voted on with a value in the range, and wagered on with the bucket containing it
+    pub fn scalar_range(&self) -> Option<ScalarRange> {
+        match self {
+            Facet::Credibility => Some(ScalarRange { min: 0, max: 100, buckets: 5 }),
+            _ => None,
+        }
+    }
+}
+
+impl ScalarRange {
+    // Number of distinct values which can be voted for
+    pub fn points(&self) -> usize {
+        (self.max - self.min) as usize + 1
+    }
+
+    pub fn contains(&self, value: u16) -> bool {
+        value >= self.min && value <= self.max
+    }
+
+    pub fn bucket(&self, value: u16) -> u8 {
+        ((value - self.min) as usize * self.buckets as usize / self.points()) as u8
+    }
+}
+
+impl std::fmt::Display for Facet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Facet::Truthfulness => "truthfulness".to_string(),
-            Facet::Originality => "originality".to_string(),
-            Facet::Authenticity => "authenticity".to_string(),
+            Facet::Truthfulness => write!(f, "truthfulness"),
+            Facet::Originality => write!(f, "originality"),
+            Facet::Authenticity => write!(f, "authenticity"),
+            Facet::Credibility => write!(f, "credibility"),
         }
     }
 }
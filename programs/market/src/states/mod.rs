@@ -3,9 +3,21 @@ pub mod escrow;
 pub mod poll;
 pub mod bettor;
 pub mod voter;
+pub mod vote_evidence;
+pub mod appeal;
+pub mod verdict;
+pub mod content_score;
+pub mod quote;
+pub mod lmsr;
 
 pub use market::*;
 pub use escrow::*;
 pub use poll::*;
 pub use bettor::*;
 pub use voter::*;
+pub use vote_evidence::*;
+pub use appeal::*;
+pub use verdict::*;
+pub use content_score::*;
+pub use quote::*;
+pub use lmsr::*;
@@ -1,18 +1,22 @@
 use anchor_lang::prelude::*;
 
 use super::Facet;
-use crate::constants::VOTE_THRESHOLD;
+use crate::constants::{MAX_OUTCOMES, MAX_SCALAR_POINTS, VOTE_THRESHOLD};
 
 #[account]
 #[derive(InitSpace)]
 pub struct Poll {
     pub bump: u8,                                   // Bump
-    pub market: Pubkey,                             // The pubkey of the market account
+    pub market: Pubkey,                             // Authensus token of the market (not the market PDA)
     pub facet: Facet,                               // The facet for which the poll exists within the market
     #[max_len(VOTE_THRESHOLD)]
     pub voters: Option<Vec<Pubkey>>,                // Everyone who has placed a vote in the poll
     #[max_len(VOTE_THRESHOLD)]
     pub voters_consolidated: Option<Vec<Pubkey>>,   // Count of the number of voters whose winnings have been calculated and reimbursed
-    pub total_for: u64,                             // Total votes for facet
-    pub total_against: u64,                         // Total votes against facet
+    #[max_len(MAX_OUTCOMES)]
+    pub total_outcomes: Vec<u64>,                   // Total votes for each outcome of the facet
+    #[max_len(MAX_SCALAR_POINTS)]
+    pub value_weights: Vec<u64>,                    // Total vote amount for each value of a scalar facet
+    pub closed_at: i64,                             // Time at which the poll reached its threshold, opening the appeal window
+    pub appealed: bool,                             // Whether the outcome of the poll has been appealed
 }
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::{borsh::{BorshSerialize, BorshDeserialize}, *};
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Quote {
+    pub bet_returned: u64,          // Stake returned if the quoted outcome wins
+    pub winnings: u64,              // Winnings after the market fee if the quoted outcome wins
+    pub implied_probability: u16,   // Share of the pools backing the quoted outcome, including the bet (basis points)
+}
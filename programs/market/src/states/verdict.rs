@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use super::Facet;
+use crate::constants::MAX_OUTCOMES;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Verdict {
+    pub bump: u8,                         // Bump
+    pub market: Pubkey,                   // Authensus token of the market (not the market PDA)
+    pub facet: Facet,                     // The facet which was judged
+    pub round: u16,                       // The round of the market to which the verdict corresponds
+    pub outcome: Option<u8>,              // The winning outcome (None in the case of a tie)
+    pub value: Option<u16>,               // The resolved (stake-weighted median) value of a scalar facet
+    #[max_len(MAX_OUTCOMES)]
+    pub total_outcomes: Vec<u64>,         // Total votes for each outcome in the poll
+    pub appealed: bool,                   // Whether the poll outcome was appealed
+    pub overturned: bool,                 // Whether the appeal overrode the poll outcome
+    pub forfeited_bond: u64,              // Bond of a failed appeal which the winning pool shares, including outcome token holders (lamports)
+    #[max_len(MAX_OUTCOMES)]
+    pub appeal_total_outcomes: Vec<u64>,  // Total votes for each outcome in the appeal poll
+    #[max_len(MAX_OUTCOMES)]
+    pub pool_outcomes: Vec<u64>,          // Total amount in normal bets on each outcome
+    pub pool_underdog: u64,               // Total amount in underdog bets
+    #[max_len(MAX_OUTCOMES)]
+    pub pool_weighted_outcomes: Vec<u64>, // Total time-weighted amount in normal bets on each outcome
+    pub pool_weighted_underdog: u64,      // Total time-weighted amount in underdog bets
+    pub start_time: i64,                  // Time at which betting started
+    pub betting_closed_at: i64,           // Time at which betting timed out
+    pub voting_closed_at: i64,            // Time at which the (final) poll reached its threshold
+    pub settled_at: i64,                  // Time at which the round was called
+}
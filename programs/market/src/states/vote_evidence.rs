@@ -0,0 +1,37 @@
+use anchor_lang::prelude::{borsh::{BorshSerialize, BorshDeserialize}, *};
+
+use super::Facet;
+use crate::constants::MAX_EVIDENCE_URI_LENGTH;
+
+#[account]
+#[derive(InitSpace)]
+pub struct VoteEvidence {
+    pub bump: u8,                   // Bump
+    pub voter: Pubkey,              // The pubkey of the voter who submitted the evidence
+    pub market: Pubkey,             // Authensus token of the market (not the market PDA)
+    pub facet: Facet,               // The facet on which the vote was cast
+    pub round: u16,                 // The round of the market in which the vote was cast
+    pub outcome: u8,                // The outcome of the vote the evidence supports
+    #[max_len(MAX_EVIDENCE_URI_LENGTH)]
+    pub uri: String,                // Location of the evidence (e.g. IPFS/Arweave/HTTPS)
+    pub hash: [u8; 32],             // Hash of the evidence content, so that the URI target can be checked later
+    pub rationale: RationaleCode,   // Short code summarising why the voter judged the facet as they did
+    pub submitted_at: i64,          // Time at which the evidence was submitted
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub struct EvidenceParams {
+    pub uri: String,
+    pub hash: [u8; 32],
+    pub rationale: RationaleCode,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, InitSpace, PartialEq)]
+pub enum RationaleCode {
+    Corroborated,       // Independent sources confirm the facet
+    Contradicted,       // Independent sources contradict the facet
+    Manipulated,        // The content shows signs of editing or fabrication
+    Misattributed,      // The content is credited to the wrong source
+    Unverifiable,       // Not enough information exists to judge the facet
+    Other,
+}
@@ -10,10 +10,12 @@ pub struct Voter {
     pub market: Pubkey,
     pub facet: Facet,
     pub amount: u64,
-    pub direction: bool,
+    pub outcome: u8,
+    pub value: Option<u16>,
+    pub appeal: bool,
 }
 
 // impl Space for Voter {
-//     // Discriminator (8) + pk (32) + market (32) + facet (?) + amount (8) + direction (1)
+//     // Discriminator (8) + pk (32) + market (32) + facet (?) + amount (8) + outcome (1) + value (3)
 //     const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 1;  // INCOMPLETE
 // }
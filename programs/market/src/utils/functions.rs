@@ -1,58 +1,728 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use treasury::LedgerPurpose;
 
-use crate::constants::DIV_BUFFER;
+use crate::constants::{BASIS_POINTS, MAX_BATCH_SETTLEMENTS, MAX_EXIT_FEE, MAX_LMSR_EXPONENT, MAX_SCORE, MAX_SNIPING_EXTENSION, MAX_TIME_WEIGHT, MAX_UNDERDOG_BONUS, PERCENTAGE_WINNINGS_KEPT, SCORE_HISTORY_LENGTH};
+use crate::error::{BettingError, InitError, LmsrError, ResultsError};
+use crate::states::{AntiSnipingRule, Appeal, BetType, Escrow, Facet, FacetScore, FacetWeight, Market, MarketConfig, Poll, Quote, ScalarRange, ScoreRecord, SnipeThreshold, TimeWeighting, WeightCurve};
+
+pub fn check_market_config(
+    config: &MarketConfig,
+) -> Result<()> {
+
+    require!(config.exit_fee <= MAX_EXIT_FEE, InitError::ExitFeeTooLarge);
+
+    if let Some(rule) = &config.anti_sniping {
+        let threshold_condition: bool = match rule.threshold {
+            SnipeThreshold::Absolute(amount) => amount > 0,
+            SnipeThreshold::PoolShare(share) => share > 0 && u64::from(share) <= BASIS_POINTS,
+        };
+
+        require!(rule.window > 0 && rule.extension > 0 && threshold_condition, InitError::InvalidAntiSnipingRule);
+        require!(rule.extension <= rule.max_extension, InitError::InvalidAntiSnipingRule);
+        require!(rule.max_extension <= MAX_SNIPING_EXTENSION, InitError::SnipingExtensionTooLarge);
+    }
+
+    let max_bet_condition: bool = config.max_bet.is_none_or(|max_bet| max_bet >= config.min_bet && max_bet > 0);
+    let share_condition: bool = config.max_pool_share.is_none_or(|share| share > 0 && u64::from(share) <= BASIS_POINTS);
+
+    require!(max_bet_condition && share_condition, InitError::InvalidBetLimits);
+
+    if let Some(weighting) = &config.time_weighting {
+        let weight: u64 = weighting.initial_weight.into();
+
+        require!((BASIS_POINTS..=MAX_TIME_WEIGHT).contains(&weight), InitError::InvalidTimeWeighting);
+    }
+
+    require!((BASIS_POINTS..=MAX_UNDERDOG_BONUS).contains(&config.underdog_bonus.into()), InitError::InvalidUnderdogBonus);
+
+    Ok(())
+
+}
+
+pub fn check_bet_limits(
+    config: &MarketConfig,
+    amount: u64,
+    position: u64,
+    pool: u64,
+) -> Result<()> {
+
+    // Position and pool are the bettor's stake and the total stake on the same side, both including the bet
+    require!(amount >= config.min_bet, BettingError::BetTooSmall);
+    require!(config.max_bet.is_none_or(|max_bet| amount <= max_bet), BettingError::BetTooLarge);
+
+    // Only the bet which opens a pool is exempt from the share limit, otherwise nobody could open it
+    if let Some(share) = config.max_pool_share {
+        require!(pool == amount || mul_div(position, BASIS_POINTS, pool) <= share.into(), BettingError::PoolShareTooLarge);
+    }
+
+    Ok(())
+
+}
+
+// Underdog stakes join the smallest pool at close, so they count towards its share limit along with its normal stakes.
+// Stakes passed in should already include the bet being placed.
+pub fn pool_position(
+    bettor_outcomes: &[u64],
+    bettor_underdog: u64,
+    pool_outcomes: &[u64],
+    pool_underdog: u64,
+    outcome: u8,
+) -> (u64, u64) {
+
+    let index: usize = outcome as usize;
+    let (position, pool): (u64, u64) = (bettor_outcomes.get(index).copied().unwrap_or(0), pool_outcomes[index]);
+
+    match underdog_outcome(pool_outcomes) == outcome {
+        true => (position + bettor_underdog, pool + pool_underdog),
+        false => (position, pool),
+    }
+
+}
+
+pub fn is_snipe(
+    rule: &AntiSnipingRule,
+    amount: u64,
+    pool: u64,
+) -> bool {
+
+    match rule.threshold {
+        SnipeThreshold::Absolute(min_amount) => amount >= min_amount,
+        SnipeThreshold::PoolShare(min_share) => pool > 0 && mul_div(amount, BASIS_POINTS, pool) >= min_share.into(),
+    }
+
+}
+
+// Only large stakes moving in the final window of betting push the deadline back, up to the max extension
+pub fn sniping_extension(
+    rule: &AntiSnipingRule,
+    extension: i64,
+    time_left: i64,
+    amount: u64,
+    pool: u64,
+) -> i64 {
+
+    match time_left <= rule.window && is_snipe(rule, amount, pool) {
+        true => (extension + rule.extension).min(rule.max_extension),
+        false => extension,
+    }
+
+}
+
+pub fn stake_accounts_condition(
+    stake_mint: Option<Pubkey>,
+    mint: Option<&Account<Mint>>,
+    vault: Option<&Account<TokenAccount>>,
+    vault_authority: Pubkey,
+    stake_account: Option<&Account<TokenAccount>>,
+    owner: Pubkey,
+) -> bool {
+
+    let stake_mint: Pubkey = match stake_mint {
+        Some(stake_mint) => stake_mint,
+        None => return true,
+    };
+
+    // Token markets need the mint, the round's vault (held by the escrow) and the participant's own token account
+    match (mint, vault, stake_account) {
+        (Some(mint), Some(vault), Some(stake_account)) => {
+            mint.key() == stake_mint
+                && vault.mint == stake_mint
+                && vault.owner == vault_authority
+                && stake_account.mint == stake_mint
+                && stake_account.owner == owner
+        },
+        _ => false,
+    }
+
+}
+
+// Remaining accounts of a batch come in one group per participant, and the number of participants is returned
+pub fn batch_size(
+    accounts: usize,
+    group: usize,
+) -> Result<usize> {
+
+    // Requirements:                                                    |   Implemented:
+    //  - Remaining accounts should be whole groups, one per participant|       √
+    //  - There should be no more participants than the max per batch   |       √
+    require!(accounts > 0 && group > 0 && accounts.is_multiple_of(group), ResultsError::BatchAccountsMismatch);
+    require!(accounts / group <= MAX_BATCH_SETTLEMENTS.into(), ResultsError::BatchTooLarge);
+
+    Ok(accounts / group)
+
+}
+
+// Participants may have left the round or settled themselves since a batch was put together, which shouldn't fail the others
+pub fn batch_entry_pending(
+    participant: &Pubkey,
+    participants: &Option<Vec<Pubkey>>,
+    consolidated: &Option<Vec<Pubkey>>,
+) -> bool {
+
+    participants.as_ref().is_some_and(|participants| participants.contains(participant))
+        && !consolidated.as_ref().is_some_and(|consolidated| consolidated.contains(participant))
+
+}
+
+pub fn scale_decimals(
+    amount: u64,
+    from_decimals: u8,
+    to_decimals: u8,
+) -> u64 {
+
+    if from_decimals <= to_decimals {
+        amount.saturating_mul(10_u64.pow((to_decimals - from_decimals).into()))
+    } else {
+        amount/10_u64.pow((from_decimals - to_decimals).into())
+    }
+
+}
+
+pub struct Stakes<'a> {
+    pub outcomes: &'a [u64],            // Amount staked on each outcome
+    pub underdog: u64,                  // Amount staked in underdog bets
+    pub weighted_outcomes: &'a [u64],   // Time-weighted amount staked on each outcome
+    pub weighted_underdog: u64,         // Time-weighted amount staked in underdog bets
+}
 
 pub fn compute_returns(
-    direction: bool,
-    escrow_tot_for: u64,
-    escrow_tot_against: u64,
-    escrow_tot_underdog: u64,
-    bettor_tot_for: u64,
-    bettor_tot_against: u64,
-    bettor_tot_underdog: u64,
+    outcome: u8,
+    escrow: &Stakes,
+    bettor: &Stakes,
+    underdog_bonus: u64,
 ) -> (u64, u64) {
-    let for_multiplier: u64 = match direction {
-        true => 1_u64,
-        false => 0_u64,
+
+    let winner: usize = outcome as usize;
+
+    // Escrow totals for each outcome, with underdog bets assigned to the smallest pool at close
+    let final_tot_outcomes: Vec<u64> = final_pools(
+        escrow.outcomes,
+        escrow.underdog,
+    );
+    let final_tot_winner: u64 = final_tot_outcomes.get(winner).copied().unwrap_or(0);
+    let final_tot_losers: u64 = final_tot_outcomes.iter().sum::<u64>() - final_tot_winner;
+
+    // Underdog bets only count when the underdog won
+    let underdog_won: bool = underdog_outcome(escrow.outcomes) == outcome;
+    let (underdog_winner, weighted_underdog_winner, bettor_weighted_underdog_winner) = match underdog_won {
+        true => (bettor.underdog, escrow.weighted_underdog, bettor.weighted_underdog),
+        false => (0, 0, 0),
     };
-    let against_multiplier: u64 = 1_u64 - for_multiplier;
 
-    // Escrow totals for and against with appropriate shares from underdog bets
-    let final_tot_for: u64 = (DIV_BUFFER*escrow_tot_for + (DIV_BUFFER*escrow_tot_underdog*escrow_tot_against)/(escrow_tot_for + escrow_tot_against))/DIV_BUFFER;
-    let final_tot_against: u64 = (DIV_BUFFER*escrow_tot_against + (DIV_BUFFER*escrow_tot_underdog*escrow_tot_for)/(escrow_tot_for + escrow_tot_against))/DIV_BUFFER;
+    let bet_returned: u64 = bettor.outcomes.get(winner).copied().unwrap_or(0) + underdog_winner;
 
-    // Final bets for and against from user underdog bets
-    let underdog_for: u64 = ((DIV_BUFFER*bettor_tot_underdog*final_tot_against)/(final_tot_against + final_tot_against))/DIV_BUFFER;
-    let underdog_against: u64 = ((DIV_BUFFER*bettor_tot_underdog*final_tot_for)/(final_tot_against + final_tot_against))/DIV_BUFFER;
+    // Weighted stakes on the winning outcome
+    let weighted_winner: u64 = escrow.weighted_outcomes.get(winner).copied().unwrap_or(0) + weighted_underdog_winner;
+    let bettor_weighted_winner: u64 = bettor.weighted_outcomes.get(winner).copied().unwrap_or(0);
 
-    // Winnings for and against from user normal bets
-    let winnings_for: u64 = ((DIV_BUFFER*final_tot_against*bettor_tot_for)/final_tot_for)/DIV_BUFFER;
-    let winnings_against: u64 = ((DIV_BUFFER*final_tot_for*bettor_tot_against)/final_tot_against)/DIV_BUFFER;
+    if weighted_winner == 0 {
+        return (bet_returned, 0)
+    }
+
+    // The losing pools are shared out among the winners in proportion to their time-weighted stake,
+    // with the underdog share scaled up by the bonus, which comes out of the fee
+    let winnings_pre: u64 = mul_div(final_tot_losers, bettor_weighted_winner, weighted_winner)
+        + mul_div(mul_div(final_tot_losers, bettor_weighted_underdog_winner, weighted_winner), underdog_bonus, BASIS_POINTS);
+
+    (bet_returned, winnings_pre)
 
-    // Winnings for and against from user underdog bets
-    let underdog_winnings_for: u64 = ((DIV_BUFFER*final_tot_against*underdog_for)/final_tot_for)/DIV_BUFFER;
-    let underdog_winnings_against: u64 = ((DIV_BUFFER*final_tot_for*underdog_against)/final_tot_against)/DIV_BUFFER;
+}
+
+pub fn time_weight(
+    market: &Market,
+    time: i64,
+) -> u64 {
 
-    let bet_returned: u64 = for_multiplier*(bettor_tot_for + underdog_for)
-                            + against_multiplier*(bettor_tot_against + underdog_against);
-    let winnings_pre: u64 = for_multiplier*(winnings_for + underdog_winnings_for)
-                            + against_multiplier*(winnings_against + underdog_winnings_against);
+    let weighting: &TimeWeighting = match market.config.time_weighting.as_ref() {
+        Some(weighting) => weighting,
+        None => return BASIS_POINTS,
+    };
+
+    // Fraction of the scheduled betting period which has passed, so bets during any extension are unweighted
+    let elapsed: u64 = (time - market.start_time).clamp(0, market.timeout) as u64;
+    let timeout: u64 = market.timeout.max(1) as u64;
+    let bonus: u64 = u64::from(weighting.initial_weight).saturating_sub(BASIS_POINTS);
+
+    let decay: u64 = match weighting.curve {
+        WeightCurve::Linear => mul_div(bonus, elapsed, timeout),
+        WeightCurve::Quadratic => mul_div(mul_div(bonus, elapsed, timeout), elapsed, timeout),
+    };
+
+    BASIS_POINTS + bonus - decay
+
+}
+
+pub fn quote_bet(
+    outcome: u8,
+    amount: u64,
+    weight: u64,
+    underdog_bonus: u64,
+    bet_type: BetType,
+    escrow: &Stakes,
+) -> Quote {
+
+    let weighted_amount: u64 = mul_div(amount, weight, BASIS_POINTS);
+
+    let mut tot_outcomes: Vec<u64> = escrow.outcomes.to_vec();
+    let mut tot_underdog: u64 = escrow.underdog;
+    let mut weighted_outcomes: Vec<u64> = escrow.weighted_outcomes.to_vec();
+    let mut weighted_underdog: u64 = escrow.weighted_underdog;
+    let mut bettor_outcomes: Vec<u64> = vec![0; escrow.outcomes.len()];
+    let mut bettor_weighted_outcomes: Vec<u64> = vec![0; escrow.outcomes.len()];
+    let mut bettor_underdog: u64 = 0;
+    let mut bettor_weighted_underdog: u64 = 0;
+
+    // The bet is quoted as though it had already been placed
+    match bet_type {
+        BetType::Normal => {
+            tot_outcomes[outcome as usize] += amount;
+            weighted_outcomes[outcome as usize] += weighted_amount;
+            bettor_outcomes[outcome as usize] = amount;
+            bettor_weighted_outcomes[outcome as usize] = weighted_amount;
+        },
+        BetType::Underdog => {
+            tot_underdog += amount;
+            weighted_underdog += weighted_amount;
+            bettor_underdog = amount;
+            bettor_weighted_underdog = weighted_amount;
+        },
+    }
+
+    let (bet_returned, winnings_pre) = compute_returns(
+        outcome,
+        &Stakes {
+            outcomes: &tot_outcomes,
+            underdog: tot_underdog,
+            weighted_outcomes: &weighted_outcomes,
+            weighted_underdog,
+        },
+        &Stakes {
+            outcomes: &bettor_outcomes,
+            underdog: bettor_underdog,
+            weighted_outcomes: &bettor_weighted_outcomes,
+            weighted_underdog: bettor_weighted_underdog,
+        },
+        underdog_bonus,
+    );
+
+    let pools: Vec<u64> = final_pools(&tot_outcomes, tot_underdog);
+    let total: u64 = pools.iter().sum();
+
+    let implied_probability: u16 = match total {
+        0 => 0_u16,
+        _ => mul_div(pools[outcome as usize], BASIS_POINTS, total) as u16,
+    };
+
+    Quote {
+        bet_returned,
+        winnings: (PERCENTAGE_WINNINGS_KEPT*winnings_pre)/100,
+        implied_probability,
+    }
+
+}
+
+pub fn payout_multiplier(
+    quote: &Quote,
+    amount: u64,
+) -> u64 {
+
+    if amount == 0 {
+        return 0
+    }
 
-    return (bet_returned, winnings_pre)
+    // Total projected payout per unit staked (basis points, so 10_000 is the stake returned with no winnings)
+    mul_div(quote.bet_returned + quote.winnings, BASIS_POINTS, amount)
+
+}
+
+pub fn underdog_outcome(
+    escrow_tot_outcomes: &[u64],
+) -> u8 {
+
+    // The outcome with the smallest pool, to which underdog bets are assigned (the first on a tie)
+    escrow_tot_outcomes.iter()
+        .enumerate()
+        .min_by_key(|(_, tot)| **tot)
+        .map_or(0, |(i, _)| i as u8)
+
+}
+
+pub fn final_pools(
+    escrow_tot_outcomes: &[u64],
+    escrow_tot_underdog: u64,
+) -> Vec<u64> {
+
+    let underdog: usize = underdog_outcome(escrow_tot_outcomes).into();
+
+    escrow_tot_outcomes.iter()
+        .enumerate()
+        .map(|(i, tot)| if i == underdog { tot + escrow_tot_underdog } else { *tot })
+        .collect()
+
+}
+
+pub fn mul_div(
+    a: u64,
+    b: u64,
+    c: u64,
+) -> u64 {
+
+    // Widen to avoid overflow when multiplying two lamport amounts
+    ((a as u128 * b as u128)/c as u128) as u64
+
+}
+
+pub fn calc_bond_share(
+    bond: u64,
+    bet_returned: u64,
+    winning_pool: u64,
+) -> u64 {
+
+    if winning_pool == 0 {
+        return 0
+    }
+
+    mul_div(bond, bet_returned, winning_pool)
+
+}
+
+// Winning bettors share the bond of a failed appeal in proportion to what they get back from the winning pool
+pub fn forfeited_bond_share(
+    poll: &Poll,
+    appeal: Option<&Appeal>,
+    escrow: &Escrow,
+    outcome: u8,
+    bet_returned: u64,
+) -> u64 {
+
+    let appeal: &Appeal = match appeal {
+        Some(appeal) if poll.appealed && !appeal.overturned => appeal,
+        _ => return 0,
+    };
+
+    calc_bond_share(appeal.bond, bet_returned, winning_pool(escrow, outcome))
+
+}
+
+// Bond of a failed appeal left for the winning pool to share, which the treasury keeps instead when nobody backed the outcome
+pub fn forfeited_bond(
+    poll: &Poll,
+    appeal: Option<&Appeal>,
+    escrow: &Escrow,
+) -> u64 {
+
+    let appeal: &Appeal = match appeal {
+        Some(appeal) if poll.appealed && !appeal.overturned => appeal,
+        _ => return 0,
+    };
+
+    match final_outcome(poll, Some(appeal)) {
+        Some(outcome) if winning_pool(escrow, outcome) > 0 => appeal.bond,
+        _ => 0,
+    }
+
+}
+
+pub fn winning_pool(
+    escrow: &Escrow,
+    outcome: u8,
+) -> u64 {
+
+    final_pools(&escrow.tot_outcomes, escrow.tot_underdog)
+        .get(outcome as usize)
+        .copied()
+        .unwrap_or(0)
+
+}
+
+// Total a resolved round pays back out of its stakes or bonds, which leaves out stakes on losing outcomes
+pub fn round_claims(
+    poll: &Poll,
+    appeal: Option<&Appeal>,
+    escrow: &Escrow,
+    purpose: LedgerPurpose,
+) -> u64 {
+
+    match purpose {
+        // A failed appeal's bond is shared among the winners, and a successful one's returned to the appellant
+        LedgerPurpose::Bonds => appeal.filter(|_| poll.appealed).map_or(0, |appeal| appeal.bond),
+        _ => match final_outcome(poll, appeal) {
+            Some(outcome) => winning_pool(escrow, outcome),
+            None => escrow.tot_outcomes.iter().sum::<u64>() + escrow.tot_underdog,
+        },
+    }
+
+}
+
+pub fn winning_outcome(
+    total_outcomes: &[u64],
+) -> Option<u8> {
+
+    let max: u64 = total_outcomes.iter().copied().max()?;
+
+    // Ties between the leading outcomes (including an empty poll) have no winner
+    match total_outcomes.iter().filter(|t| **t == max).count() {
+        1 => total_outcomes.iter().position(|t| *t == max).map(|i| i as u8),
+        _ => None,
+    }
+
+}
+
+pub fn median_value(
+    range: &ScalarRange,
+    value_weights: &[u64],
+) -> Option<u16> {
+
+    let total: u64 = value_weights.iter().sum();
+
+    if total == 0 {
+        return None
+    }
+
+    // The stake-weighted median is the lowest value with at least half of the total weight at or below it
+    let mut cumulative: u64 = 0;
+
+    for (i, weight) in value_weights.iter().enumerate() {
+        cumulative += weight;
+
+        if 2*cumulative >= total {
+            return Some(range.min + i as u16)
+        }
+    }
+
+    None
+
+}
+
+pub fn poll_outcome(
+    facet: &Facet,
+    total_outcomes: &[u64],
+    value_weights: &[u64],
+) -> Option<u8> {
+
+    // Scalar facets resolve to the bucket containing the median value rather than the most popular bucket
+    match facet.scalar_range() {
+        Some(range) => median_value(&range, value_weights).map(|value| range.bucket(value)),
+        None => winning_outcome(total_outcomes),
+    }
+
+}
+
+pub fn final_outcome(
+    poll: &Poll,
+    appeal: Option<&Appeal>,
+) -> Option<u8> {
+
+    // A successful appeal overrides the outcome of the original poll
+    if let Some(appeal) = appeal {
+        if poll.appealed && appeal.overturned {
+            return Some(appeal.outcome)
+        }
+    }
+
+    poll_outcome(&poll.facet, &poll.total_outcomes, &poll.value_weights)
+
+}
+
+pub fn final_value(
+    poll: &Poll,
+    appeal: Option<&Appeal>,
+) -> Option<u16> {
+
+    let range: ScalarRange = poll.facet.scalar_range()?;
+
+    if let Some(appeal) = appeal {
+        if poll.appealed && appeal.overturned {
+            return median_value(&range, &appeal.value_weights)
+        }
+    }
+
+    median_value(&range, &poll.value_weights)
 
 }
 
 pub fn calc_winnings_from_votes(
-    poll_direction: bool,
-    voter_direction: bool,
+    poll_outcome: u8,
+    voter_outcome: u8,
     amount: u64
 ) -> u64 {
-    match !(poll_direction ^ voter_direction) {
+    match poll_outcome == voter_outcome {
         true => amount,
         false => 0,
     }
 }
 
+pub fn calc_facet_score(
+    total_outcomes: &[u64],
+) -> u16 {
+
+    let total: u64 = total_outcomes.iter().sum();
+    let n: u64 = total_outcomes.len() as u64;
+
+    // An empty poll is scored neutrally
+    if total == 0 || n < 2 {
+        return MAX_SCORE/2
+    }
+
+    // Each vote counts towards the score according to how affirmative its outcome is,
+    // so for binary facets this is the share of the votes judging the facet to be true
+    let weighted: u64 = total_outcomes.iter()
+        .enumerate()
+        .map(|(i, t)| t*i as u64)
+        .sum();
+
+    mul_div(MAX_SCORE.into(), weighted, (n - 1)*total) as u16
+
+}
+
+pub fn calc_scalar_score(
+    range: &ScalarRange,
+    value: u16,
+) -> u16 {
+
+    if range.max == range.min {
+        return MAX_SCORE/2
+    }
+
+    mul_div(MAX_SCORE.into(), (value - range.min).into(), (range.max - range.min).into()) as u16
+
+}
+
+pub fn calc_weighted_score(
+    weights: &[FacetWeight],
+    facet_scores: &[FacetScore],
+) -> u16 {
+
+    let mut weighted_sum: u64 = 0;
+    let mut weight_sum: u64 = 0;
+
+    for facet_score in facet_scores {
+        let weight: u64 = weights.iter()
+            .find(|w| w.facet == facet_score.facet)
+            .map_or(0_u64, |w| w.weight.into());
+
+        weighted_sum += weight*facet_score.score as u64;
+        weight_sum += weight;
+    }
+
+    if weight_sum == 0 {
+        return 0
+    }
+
+    (weighted_sum/weight_sum) as u16
+
+}
+
+pub fn push_score_record(
+    history: &mut Vec<ScoreRecord>,
+    head: u16,
+    record: ScoreRecord,
+) -> u16 {
+
+    // Fill the buffer first, then overwrite the oldest record
+    if history.len() < SCORE_HISTORY_LENGTH.into() {
+        history.push(record);
+    } else {
+        history[head as usize] = record;
+    }
+
+    (head + 1) % SCORE_HISTORY_LENGTH
+
+}
+
+pub fn lmsr_cost(
+    shares: &[u64],
+    liquidity: u64,
+) -> f64 {
+
+    // C(q) = b*ln(sum(exp(q_i/b))), with the largest exponent factored out to keep exp() in range
+    let b: f64 = liquidity as f64;
+    let max: f64 = shares.iter().max().copied().unwrap_or(0) as f64/b;
+    let sum: f64 = shares.iter().map(|q| (*q as f64/b - max).exp()).sum();
+
+    b*(max + sum.ln())
+
+}
+
+pub fn lmsr_subsidy(
+    liquidity: u64,
+    outcomes: u8,
+) -> u64 {
+
+    // The maker can lose at most b*ln(n), which is the cost of the empty book
+    lmsr_cost(&vec![0; outcomes.into()], liquidity).ceil() as u64
+
+}
+
+pub fn lmsr_buy_cost(
+    shares: &[u64],
+    liquidity: u64,
+    outcome: u8,
+    amount: u64,
+) -> Result<u64> {
+
+    let mut after: Vec<u64> = shares.to_vec();
+
+    // Shares are capped relative to the liquidity so that the cost stays within range
+    after[outcome as usize] = after[outcome as usize]
+        .checked_add(amount)
+        .filter(|q| *q <= liquidity.saturating_mul(MAX_LMSR_EXPONENT))
+        .ok_or(LmsrError::SharesOutOfRange)?;
+
+    // Rounded up so that the maker never undercharges
+    Ok((lmsr_cost(&after, liquidity) - lmsr_cost(shares, liquidity)).ceil() as u64)
+
+}
+
+pub fn lmsr_sell_refund(
+    shares: &[u64],
+    liquidity: u64,
+    outcome: u8,
+    amount: u64,
+) -> Result<u64> {
+
+    let mut after: Vec<u64> = shares.to_vec();
+
+    after[outcome as usize] = after[outcome as usize]
+        .checked_sub(amount)
+        .ok_or(LmsrError::InsufficientShares)?;
+
+    // Rounded down so that the maker never overpays
+    Ok((lmsr_cost(shares, liquidity) - lmsr_cost(&after, liquidity)).floor().max(0.0) as u64)
+
+}
+
+pub fn lmsr_prices(
+    shares: &[u64],
+    liquidity: u64,
+) -> Vec<u16> {
+
+    // Instantaneous price of each outcome, exp(q_i/b)/sum(exp(q_j/b)) (basis points)
+    let b: f64 = liquidity as f64;
+    let max: f64 = shares.iter().max().copied().unwrap_or(0) as f64/b;
+    let weights: Vec<f64> = shares.iter().map(|q| (*q as f64/b - max).exp()).collect();
+    let sum: f64 = weights.iter().sum();
+
+    weights.iter()
+        .map(|w| (w/sum*BASIS_POINTS as f64).round() as u16)
+        .collect()
+
+}
+
+pub fn lmsr_payout(
+    shares: &[u64],
+    outcome: Option<u8>,
+) -> u64 {
+
+    match outcome {
+        Some(outcome) => shares.get(outcome as usize).copied().unwrap_or(0),
+        // A tie pays every share out at 1/n
+        None => shares.iter().sum::<u64>()/(shares.len().max(1) as u64),
+    }
+
+}
+
 pub fn vec_eq(
     v1: &mut Vec<Pubkey>,
     v2: &mut Vec<Pubkey>,
@@ -64,3 +734,566 @@ pub fn vec_eq(
     v1 == v2
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::MarketState;
+
+    #[test]
+    fn batch_size_counts_whole_groups() {
+        assert_eq!(batch_size(8, 4), Ok(2));
+        assert_eq!(batch_size(15, 5), Ok(3));
+    }
+
+    #[test]
+    fn batch_size_rejects_partial_or_empty_groups() {
+        assert_eq!(batch_size(0, 4), Err(ResultsError::BatchAccountsMismatch.into()));
+        assert_eq!(batch_size(9, 4), Err(ResultsError::BatchAccountsMismatch.into()));
+        assert_eq!(batch_size(4, 0), Err(ResultsError::BatchAccountsMismatch.into()));
+    }
+
+    #[test]
+    fn batch_size_rejects_more_than_the_max() {
+        let max: usize = MAX_BATCH_SETTLEMENTS.into();
+
+        assert_eq!(batch_size(3*max, 3), Ok(max));
+        assert_eq!(batch_size(3*(max + 1), 3), Err(ResultsError::BatchTooLarge.into()));
+    }
+
+    #[test]
+    fn batch_entry_pending_skips_stale_entries() {
+        let listed: Pubkey = Pubkey::new_unique();
+        let settled: Pubkey = Pubkey::new_unique();
+        let departed: Pubkey = Pubkey::new_unique();
+
+        let participants: Option<Vec<Pubkey>> = Some(vec![listed, settled]);
+        let consolidated: Option<Vec<Pubkey>> = Some(vec![settled]);
+
+        assert!(batch_entry_pending(&listed, &participants, &consolidated));
+        assert!(batch_entry_pending(&listed, &participants, &None));
+        assert!(!batch_entry_pending(&settled, &participants, &consolidated));
+        assert!(!batch_entry_pending(&departed, &participants, &consolidated));
+        assert!(!batch_entry_pending(&listed, &None, &None));
+    }
+
+    fn poll(facet: Facet, total_outcomes: Vec<u64>, value_weights: Vec<u64>, appealed: bool) -> Poll {
+        Poll {
+            bump: 0,
+            market: Pubkey::default(),
+            facet,
+            voters: None,
+            voters_consolidated: None,
+            total_outcomes,
+            value_weights,
+            closed_at: 0,
+            appealed,
+        }
+    }
+
+    fn appeal(bond: u64, outcome: u8, overturned: bool) -> Appeal {
+        Appeal {
+            bump: 0,
+            market: Pubkey::default(),
+            facet: Facet::Truthfulness,
+            round: 0,
+            appellant: Pubkey::default(),
+            bond,
+            outcome,
+            opened_at: 0,
+            closed_at: 0,
+            voters: None,
+            voters_consolidated: None,
+            total_outcomes: vec![0; 2],
+            value_weights: vec![],
+            resolved: true,
+            overturned,
+        }
+    }
+
+    fn escrow(tot_outcomes: Vec<u64>, tot_underdog: u64) -> Escrow {
+        Escrow {
+            bump: 0,
+            initialiser: Pubkey::default(),
+            rent_payer: Pubkey::default(),
+            market: Pubkey::default(),
+            facet: Facet::Truthfulness,
+            bettors: None,
+            bettors_consolidated: None,
+            weighted_outcomes: tot_outcomes.clone(),
+            tot_outcomes,
+            tot_underdog,
+            weighted_underdog: tot_underdog,
+        }
+    }
+
+    #[test]
+    fn calc_bond_share_is_proportional_to_the_stake_returned() {
+        assert_eq!(calc_bond_share(1_000, 250, 1_000), 250);
+        assert_eq!(calc_bond_share(1_000, 1_000, 1_000), 1_000);
+        assert_eq!(calc_bond_share(1_000, 0, 0), 0);
+    }
+
+    #[test]
+    fn winning_pool_includes_underdog_bets_on_the_smallest_pool() {
+        let escrow: Escrow = escrow(vec![300, 100], 50);
+
+        assert_eq!(winning_pool(&escrow, 0), 300);
+        assert_eq!(winning_pool(&escrow, 1), 150);
+        assert_eq!(winning_pool(&escrow, 2), 0);
+    }
+
+    #[test]
+    fn forfeited_bond_share_only_pays_out_a_failed_appeal() {
+        let escrow: Escrow = escrow(vec![400, 100], 0);
+        let failed: Appeal = appeal(200, 1, false);
+        let overturned: Appeal = appeal(200, 1, true);
+
+        // A bettor holding a quarter of the winning pool gets a quarter of the bond
+        assert_eq!(forfeited_bond_share(&poll(Facet::Truthfulness, vec![5, 1], vec![], true), Some(&failed), &escrow, 0, 100), 50);
+        assert_eq!(forfeited_bond_share(&poll(Facet::Truthfulness, vec![5, 1], vec![], true), Some(&overturned), &escrow, 1, 100), 0);
+        assert_eq!(forfeited_bond_share(&poll(Facet::Truthfulness, vec![5, 1], vec![], false), Some(&failed), &escrow, 0, 100), 0);
+        assert_eq!(forfeited_bond_share(&poll(Facet::Truthfulness, vec![5, 1], vec![], true), None, &escrow, 0, 100), 0);
+    }
+
+    #[test]
+    fn forfeited_bond_is_kept_by_the_treasury_without_winners() {
+        let failed: Appeal = appeal(200, 1, false);
+        let overturned: Appeal = appeal(200, 1, true);
+        let appealed: Poll = poll(Facet::Truthfulness, vec![5, 1], vec![], true);
+
+        assert_eq!(forfeited_bond(&appealed, Some(&failed), &escrow(vec![400, 100], 0)), 200);
+        assert_eq!(forfeited_bond(&appealed, Some(&overturned), &escrow(vec![400, 100], 0)), 0);
+        assert_eq!(forfeited_bond(&appealed, None, &escrow(vec![400, 100], 0)), 0);
+        assert_eq!(forfeited_bond(&poll(Facet::Truthfulness, vec![5, 1], vec![], false), Some(&failed), &escrow(vec![400, 100], 0)), 0);
+
+        // Nobody backed the winning outcome
+        assert_eq!(forfeited_bond(&appealed, Some(&failed), &escrow(vec![0, 100], 0)), 0);
+    }
+
+    #[test]
+    fn redeemed_outcome_tokens_share_the_bond_like_a_bettor() {
+        let escrow: Escrow = escrow(vec![400, 100], 0);
+        let failed: Appeal = appeal(200, 1, false);
+        let appealed: Poll = poll(Facet::Truthfulness, vec![5, 1], vec![], true);
+
+        // Tokens redeemed for a quarter of the winning pool get the same bond share as a quarter stake left with the bettor
+        let pool: Stakes = Stakes { outcomes: &escrow.tot_outcomes, underdog: 0, weighted_outcomes: &escrow.weighted_outcomes, weighted_underdog: 0 };
+        let tokens: Stakes = Stakes { outcomes: &[100, 0], underdog: 0, weighted_outcomes: &[100, 0], weighted_underdog: 0 };
+        let (bet_returned, winnings) = compute_returns(0, &pool, &tokens, BASIS_POINTS);
+        let holder_share: u64 = calc_bond_share(forfeited_bond(&appealed, Some(&failed), &escrow), bet_returned, final_pools(&escrow.tot_outcomes, 0)[0]);
+
+        assert_eq!((bet_returned, winnings), (100, 25));
+        assert_eq!(holder_share, 50);
+        assert_eq!(holder_share, forfeited_bond_share(&appealed, Some(&failed), &escrow, 0, bet_returned));
+    }
+
+    #[test]
+    fn final_outcome_follows_an_overturning_appeal() {
+        let appealed: Poll = poll(Facet::Truthfulness, vec![5, 1], vec![], true);
+
+        assert_eq!(final_outcome(&appealed, Some(&appeal(200, 1, true))), Some(1));
+        assert_eq!(final_outcome(&appealed, Some(&appeal(200, 1, false))), Some(0));
+        assert_eq!(final_outcome(&poll(Facet::Truthfulness, vec![5, 1], vec![], false), Some(&appeal(200, 1, true))), Some(0));
+    }
+
+    #[test]
+    fn calc_facet_score_weights_votes_by_how_affirmative_they_are() {
+        assert_eq!(calc_facet_score(&[1, 3]), 7_500);
+        assert_eq!(calc_facet_score(&[0, 4]), MAX_SCORE);
+        assert_eq!(calc_facet_score(&[1, 2, 1]), 5_000);
+        assert_eq!(calc_facet_score(&[0, 0]), MAX_SCORE/2);
+    }
+
+    #[test]
+    fn calc_weighted_score_ignores_unweighted_facets() {
+        let weights: Vec<FacetWeight> = vec![
+            FacetWeight { facet: Facet::Truthfulness, weight: 3 },
+            FacetWeight { facet: Facet::Originality, weight: 1 },
+        ];
+        let facet_scores: Vec<FacetScore> = vec![
+            FacetScore { facet: Facet::Truthfulness, round: 1, score: 10_000 },
+            FacetScore { facet: Facet::Originality, round: 1, score: 2_000 },
+            FacetScore { facet: Facet::Authenticity, round: 1, score: 0 },
+        ];
+
+        assert_eq!(calc_weighted_score(&weights, &facet_scores), 8_000);
+        assert_eq!(calc_weighted_score(&weights, &facet_scores[2..]), 0);
+    }
+
+    #[test]
+    fn push_score_record_overwrites_the_oldest_record_once_full() {
+        let record = |round: u16| ScoreRecord { facet: Facet::Truthfulness, round, facet_score: 0, score: 0, timestamp: 0 };
+        let mut history: Vec<ScoreRecord> = vec![];
+        let mut head: u16 = 0;
+
+        for round in 0..SCORE_HISTORY_LENGTH + 2 {
+            head = push_score_record(&mut history, head, record(round));
+        }
+
+        assert_eq!(history.len(), SCORE_HISTORY_LENGTH as usize);
+        assert_eq!(head, 2);
+        assert_eq!(history[0].round, SCORE_HISTORY_LENGTH);
+        assert_eq!(history[1].round, SCORE_HISTORY_LENGTH + 1);
+        assert_eq!(history[2].round, 2);
+    }
+
+    #[test]
+    fn winning_outcome_has_no_winner_on_a_tie() {
+        assert_eq!(winning_outcome(&[1, 5, 2]), Some(1));
+        assert_eq!(winning_outcome(&[4, 1, 4]), None);
+        assert_eq!(winning_outcome(&[0, 0]), None);
+        assert_eq!(winning_outcome(&[]), None);
+    }
+
+    #[test]
+    fn poll_outcome_takes_the_most_voted_outcome_of_a_categorical_facet() {
+        assert_eq!(poll_outcome(&Facet::Originality, &[1, 1, 3], &[]), Some(2));
+        assert_eq!(poll_outcome(&Facet::Truthfulness, &[2, 2], &[]), None);
+    }
+
+    #[test]
+    fn calc_winnings_from_votes_only_pays_the_poll_outcome() {
+        assert_eq!(calc_winnings_from_votes(2, 2, 100), 100);
+        assert_eq!(calc_winnings_from_votes(2, 0, 100), 0);
+    }
+
+    fn value_weights(votes: &[(u16, u64)]) -> Vec<u64> {
+        let mut weights: Vec<u64> = vec![0; Facet::Credibility.scalar_range().unwrap().points()];
+
+        for (value, amount) in votes {
+            weights[*value as usize] += amount;
+        }
+
+        weights
+    }
+
+    #[test]
+    fn median_value_is_the_lowest_value_with_half_the_weight_below_it() {
+        let range: ScalarRange = Facet::Credibility.scalar_range().unwrap();
+
+        assert_eq!(median_value(&range, &value_weights(&[(10, 1), (50, 1), (90, 1)])), Some(50));
+        assert_eq!(median_value(&range, &value_weights(&[(10, 1), (90, 1)])), Some(10));
+        assert_eq!(median_value(&range, &value_weights(&[(10, 1), (50, 1), (90, 5)])), Some(90));
+        assert_eq!(median_value(&range, &value_weights(&[])), None);
+    }
+
+    #[test]
+    fn poll_outcome_takes_the_bucket_of_the_median_for_a_scalar_facet() {
+        // The bucket holding the most votes (0) loses to the bucket holding the median
+        let weights: Vec<u64> = value_weights(&[(0, 3), (30, 1), (60, 1), (90, 2)]);
+
+        assert_eq!(poll_outcome(&Facet::Credibility, &[3, 1, 1, 0, 2], &weights), Some(1));
+        assert_eq!(poll_outcome(&Facet::Credibility, &[0; 5], &value_weights(&[])), None);
+    }
+
+    #[test]
+    fn final_value_follows_an_overturning_appeal() {
+        let mut overturned: Appeal = appeal(200, 4, true);
+        overturned.value_weights = value_weights(&[(95, 3)]);
+
+        let poll: Poll = poll(Facet::Credibility, vec![0, 0, 3, 0, 0], value_weights(&[(50, 3)]), true);
+
+        assert_eq!(final_value(&poll, None), Some(50));
+        assert_eq!(final_value(&poll, Some(&overturned)), Some(95));
+    }
+
+    #[test]
+    fn calc_scalar_score_scales_the_value_across_the_range() {
+        let range: ScalarRange = Facet::Credibility.scalar_range().unwrap();
+
+        assert_eq!(calc_scalar_score(&range, 0), 0);
+        assert_eq!(calc_scalar_score(&range, 25), 2_500);
+        assert_eq!(calc_scalar_score(&range, 100), MAX_SCORE);
+        assert_eq!(calc_scalar_score(&ScalarRange { min: 5, max: 5, buckets: 1 }, 5), MAX_SCORE/2);
+    }
+
+    fn config() -> MarketConfig {
+        MarketConfig {
+            exit_fee: 0,
+            anti_sniping: None,
+            min_bet: 1,
+            max_bet: None,
+            max_pool_share: None,
+            outcome_tokens: false,
+            time_weighting: None,
+            underdog_bonus: BASIS_POINTS as u16,
+        }
+    }
+
+    #[test]
+    fn check_market_config_limits_the_exit_fee() {
+        assert_eq!(check_market_config(&MarketConfig { exit_fee: MAX_EXIT_FEE, ..config() }), Ok(()));
+        assert_eq!(check_market_config(&MarketConfig { exit_fee: MAX_EXIT_FEE + 1, ..config() }), Err(InitError::ExitFeeTooLarge.into()));
+    }
+
+    #[test]
+    fn quote_bet_prices_the_bet_as_though_it_were_placed() {
+        let escrow: Stakes = Stakes { outcomes: &[300, 100], underdog: 0, weighted_outcomes: &[300, 100], weighted_underdog: 0 };
+        let quote: Quote = quote_bet(1, 100, BASIS_POINTS, BASIS_POINTS, BetType::Normal, &escrow);
+
+        // The bet takes half of the winning pool, so half of the losing pool less the fee
+        assert_eq!(quote.bet_returned, 100);
+        assert_eq!(quote.winnings, 142);
+        assert_eq!(quote.implied_probability, 4_000);
+    }
+
+    #[test]
+    fn quote_bet_of_an_empty_market_has_no_winnings() {
+        let escrow: Stakes = Stakes { outcomes: &[0, 0], underdog: 0, weighted_outcomes: &[0, 0], weighted_underdog: 0 };
+        let quote: Quote = quote_bet(0, 100, BASIS_POINTS, BASIS_POINTS, BetType::Normal, &escrow);
+
+        assert_eq!(quote.bet_returned, 100);
+        assert_eq!(quote.winnings, 0);
+        assert_eq!(quote.implied_probability, BASIS_POINTS as u16);
+    }
+
+    #[test]
+    fn payout_multiplier_counts_the_stake_and_winnings_per_unit_staked() {
+        let quote: Quote = Quote { bet_returned: 100, winnings: 142, implied_probability: 4_000 };
+
+        assert_eq!(payout_multiplier(&quote, 100), 24_200);
+        assert_eq!(payout_multiplier(&Quote { winnings: 0, ..quote }, 100), BASIS_POINTS);
+        assert_eq!(payout_multiplier(&quote, 0), 0);
+    }
+
+    fn anti_sniping(threshold: SnipeThreshold) -> AntiSnipingRule {
+        AntiSnipingRule { window: 60_000, extension: 30_000, max_extension: 120_000, threshold }
+    }
+
+    #[test]
+    fn is_snipe_compares_the_bet_with_the_threshold() {
+        assert!(is_snipe(&anti_sniping(SnipeThreshold::Absolute(500)), 500, 0));
+        assert!(!is_snipe(&anti_sniping(SnipeThreshold::Absolute(500)), 499, 0));
+        assert!(is_snipe(&anti_sniping(SnipeThreshold::PoolShare(2_000)), 200, 1_000));
+        assert!(!is_snipe(&anti_sniping(SnipeThreshold::PoolShare(2_000)), 199, 1_000));
+        assert!(!is_snipe(&anti_sniping(SnipeThreshold::PoolShare(2_000)), 0, 0));
+    }
+
+    #[test]
+    fn sniping_extension_only_extends_for_large_late_stakes() {
+        let rule: AntiSnipingRule = anti_sniping(SnipeThreshold::Absolute(500));
+
+        assert_eq!(sniping_extension(&rule, 0, 60_000, 500, 0), 30_000);
+        assert_eq!(sniping_extension(&rule, 0, 60_001, 500, 0), 0);
+        assert_eq!(sniping_extension(&rule, 0, 10_000, 499, 0), 0);
+        assert_eq!(sniping_extension(&rule, 100_000, 10_000, 500, 0), 120_000);
+    }
+
+    #[test]
+    fn check_market_config_rejects_invalid_anti_sniping_rules() {
+        let valid: AntiSnipingRule = anti_sniping(SnipeThreshold::PoolShare(2_000));
+
+        assert_eq!(check_market_config(&MarketConfig { anti_sniping: Some(valid.clone()), ..config() }), Ok(()));
+
+        for (rule, error) in [
+            (AntiSnipingRule { window: 0, ..valid.clone() }, InitError::InvalidAntiSnipingRule),
+            (AntiSnipingRule { extension: 200_000, ..valid.clone() }, InitError::InvalidAntiSnipingRule),
+            (AntiSnipingRule { threshold: SnipeThreshold::Absolute(0), ..valid.clone() }, InitError::InvalidAntiSnipingRule),
+            (AntiSnipingRule { threshold: SnipeThreshold::PoolShare(10_001), ..valid.clone() }, InitError::InvalidAntiSnipingRule),
+            (AntiSnipingRule { max_extension: MAX_SNIPING_EXTENSION + 1, ..valid.clone() }, InitError::SnipingExtensionTooLarge),
+        ] {
+            assert_eq!(check_market_config(&MarketConfig { anti_sniping: Some(rule), ..config() }), Err(error.into()));
+        }
+    }
+
+    #[test]
+    fn check_bet_limits_enforces_the_bet_size_and_pool_share() {
+        let limits: MarketConfig = MarketConfig { min_bet: 10, max_bet: Some(1_000), max_pool_share: Some(5_000), ..config() };
+
+        assert_eq!(check_bet_limits(&limits, 10, 10, 100), Ok(()));
+        assert_eq!(check_bet_limits(&limits, 9, 9, 100), Err(BettingError::BetTooSmall.into()));
+        assert_eq!(check_bet_limits(&limits, 1_001, 1_001, 5_000), Err(BettingError::BetTooLarge.into()));
+        assert_eq!(check_bet_limits(&limits, 500, 500, 1_000), Ok(()));
+        assert_eq!(check_bet_limits(&limits, 501, 501, 1_000), Err(BettingError::PoolShareTooLarge.into()));
+    }
+
+    #[test]
+    fn check_bet_limits_lets_the_first_bettor_open_a_pool() {
+        let limits: MarketConfig = MarketConfig { max_pool_share: Some(1_000), ..config() };
+
+        assert_eq!(check_bet_limits(&limits, 100, 100, 100), Ok(()));
+    }
+
+    #[test]
+    fn check_bet_limits_holds_the_opener_of_a_pool_to_the_share_limit() {
+        let limits: MarketConfig = MarketConfig { max_pool_share: Some(5_000), ..config() };
+
+        // Adding to a pool the bettor holds outright is no longer the bet that opens it
+        assert_eq!(check_bet_limits(&limits, 100, 200, 200), Err(BettingError::PoolShareTooLarge.into()));
+        assert_eq!(check_bet_limits(&limits, 100, 200, 400), Ok(()));
+    }
+
+    #[test]
+    fn pool_position_counts_underdog_stakes_on_the_smallest_pool() {
+        assert_eq!(pool_position(&[100, 50], 30, &[400, 200], 60, 1), (80, 260));
+        assert_eq!(pool_position(&[100, 50], 30, &[400, 200], 60, 0), (100, 400));
+        assert_eq!(pool_position(&[], 30, &[400, 200], 60, 1), (30, 260));
+    }
+
+    #[test]
+    fn check_market_config_rejects_inconsistent_bet_limits() {
+        assert_eq!(check_market_config(&MarketConfig { min_bet: 10, max_bet: Some(9), ..config() }), Err(InitError::InvalidBetLimits.into()));
+        assert_eq!(check_market_config(&MarketConfig { min_bet: 0, max_bet: Some(0), ..config() }), Err(InitError::InvalidBetLimits.into()));
+        assert_eq!(check_market_config(&MarketConfig { max_pool_share: Some(0), ..config() }), Err(InitError::InvalidBetLimits.into()));
+        assert_eq!(check_market_config(&MarketConfig { max_pool_share: Some(10_001), ..config() }), Err(InitError::InvalidBetLimits.into()));
+    }
+
+    #[test]
+    fn scale_decimals_converts_between_denominations() {
+        assert_eq!(scale_decimals(1_500_000, 6, 9), 1_500_000_000);
+        assert_eq!(scale_decimals(1_500_000_000, 9, 6), 1_500_000);
+        assert_eq!(scale_decimals(1_999, 9, 6), 1);
+        assert_eq!(scale_decimals(42, 6, 6), 42);
+        assert_eq!(scale_decimals(u64::MAX, 0, 9), u64::MAX);
+    }
+
+    #[test]
+    fn lmsr_subsidy_covers_the_makers_worst_case_loss() {
+        // b*ln(n), rounded up
+        assert_eq!(lmsr_subsidy(1_000, 2), 694);
+        assert_eq!(lmsr_subsidy(1_000, 5), 1_610);
+    }
+
+    #[test]
+    fn lmsr_trades_round_in_the_makers_favour() {
+        let cost: u64 = lmsr_buy_cost(&[0, 0], 1_000, 0, 1_000).unwrap();
+        let refund: u64 = lmsr_sell_refund(&[1_000, 0], 1_000, 0, 1_000).unwrap();
+
+        // 1000*(ln(e + 1) - ln(2)) = 620.1
+        assert_eq!(cost, 621);
+        assert_eq!(refund, 620);
+        assert!(cost <= 1_000);
+    }
+
+    #[test]
+    fn lmsr_trades_reject_shares_out_of_range() {
+        assert_eq!(lmsr_sell_refund(&[10, 0], 1_000, 0, 11), Err(LmsrError::InsufficientShares.into()));
+        assert_eq!(lmsr_buy_cost(&[0, 0], 1_000, 0, 1_000*MAX_LMSR_EXPONENT + 1), Err(LmsrError::SharesOutOfRange.into()));
+        assert!(lmsr_buy_cost(&[0, 0], 1_000, 0, 1_000*MAX_LMSR_EXPONENT).is_ok());
+    }
+
+    #[test]
+    fn lmsr_prices_follow_the_shares_bought() {
+        assert_eq!(lmsr_prices(&[0, 0], 1_000), vec![5_000, 5_000]);
+        assert_eq!(lmsr_prices(&[0, 0, 0, 0], 1_000), vec![2_500; 4]);
+
+        let prices: Vec<u16> = lmsr_prices(&[1_000, 0], 1_000);
+
+        // e/(e + 1) = 0.7311
+        assert_eq!(prices, vec![7_311, 2_689]);
+        assert_eq!(lmsr_prices(&[1_000*MAX_LMSR_EXPONENT, 0], 1_000), vec![10_000, 0]);
+    }
+
+    #[test]
+    fn lmsr_payout_splits_shares_on_a_tie() {
+        assert_eq!(lmsr_payout(&[300, 100], Some(0)), 300);
+        assert_eq!(lmsr_payout(&[300, 100], Some(1)), 100);
+        assert_eq!(lmsr_payout(&[300, 100], None), 200);
+        assert_eq!(lmsr_payout(&[], None), 0);
+    }
+
+    fn market(time_weighting: Option<TimeWeighting>) -> Market {
+        Market {
+            bump: 0,
+            token: Pubkey::default(),
+            facets: vec![Facet::Truthfulness],
+            start_time: 1_000,
+            timeout: 1_000,
+            extension: 0,
+            state: MarketState::Betting,
+            round: 1,
+            config: MarketConfig { time_weighting, ..config() },
+            stake_mint: None,
+            stake_decimals: 9,
+        }
+    }
+
+    #[test]
+    fn time_weight_falls_to_one_over_the_betting_period() {
+        let linear: Market = market(Some(TimeWeighting { initial_weight: 20_000, curve: WeightCurve::Linear }));
+        let quadratic: Market = market(Some(TimeWeighting { initial_weight: 20_000, curve: WeightCurve::Quadratic }));
+
+        assert_eq!(time_weight(&linear, 1_000), 20_000);
+        assert_eq!(time_weight(&linear, 1_500), 15_000);
+        assert_eq!(time_weight(&linear, 2_000), BASIS_POINTS);
+        assert_eq!(time_weight(&quadratic, 1_500), 17_500);
+
+        // Bets outside the scheduled period, including any extension, are clamped to it
+        assert_eq!(time_weight(&linear, 500), 20_000);
+        assert_eq!(time_weight(&linear, 2_500), BASIS_POINTS);
+        assert_eq!(time_weight(&market(None), 1_000), BASIS_POINTS);
+    }
+
+    #[test]
+    fn compute_returns_shares_winnings_by_time_weighted_stake() {
+        // Two equal stakes on the winner, the first placed at twice the weight of the second
+        let escrow: Stakes = Stakes { outcomes: &[100, 100], underdog: 0, weighted_outcomes: &[150, 100], weighted_underdog: 0 };
+        let early: Stakes = Stakes { outcomes: &[50, 0], underdog: 0, weighted_outcomes: &[100, 0], weighted_underdog: 0 };
+        let late: Stakes = Stakes { outcomes: &[50, 0], underdog: 0, weighted_outcomes: &[50, 0], weighted_underdog: 0 };
+
+        assert_eq!(compute_returns(0, &escrow, &early, BASIS_POINTS), (50, 66));
+        assert_eq!(compute_returns(0, &escrow, &late, BASIS_POINTS), (50, 33));
+        assert_eq!(compute_returns(1, &escrow, &early, BASIS_POINTS), (0, 0));
+    }
+
+    #[test]
+    fn check_market_config_limits_the_initial_weight() {
+        let weighting = |initial_weight: u16| Some(TimeWeighting { initial_weight, curve: WeightCurve::Linear });
+
+        assert_eq!(check_market_config(&MarketConfig { time_weighting: weighting(MAX_TIME_WEIGHT as u16), ..config() }), Ok(()));
+        assert_eq!(check_market_config(&MarketConfig { time_weighting: weighting(9_999), ..config() }), Err(InitError::InvalidTimeWeighting.into()));
+        assert_eq!(check_market_config(&MarketConfig { time_weighting: weighting(MAX_TIME_WEIGHT as u16 + 1), ..config() }), Err(InitError::InvalidTimeWeighting.into()));
+    }
+
+    #[test]
+    fn underdog_bets_join_the_smallest_pool() {
+        assert_eq!(underdog_outcome(&[300, 100, 200]), 1);
+        assert_eq!(underdog_outcome(&[100, 300, 100]), 0);
+        assert_eq!(final_pools(&[300, 100, 200], 50), vec![300, 150, 200]);
+    }
+
+    #[test]
+    fn compute_returns_scales_underdog_winnings_by_the_bonus() {
+        let escrow: Stakes = Stakes { outcomes: &[300, 100], underdog: 100, weighted_outcomes: &[300, 100], weighted_underdog: 100 };
+        let underdog: Stakes = Stakes { outcomes: &[0, 0], underdog: 100, weighted_outcomes: &[0, 0], weighted_underdog: 100 };
+        let normal: Stakes = Stakes { outcomes: &[0, 100], underdog: 0, weighted_outcomes: &[0, 100], weighted_underdog: 0 };
+        let favourite: Stakes = Stakes { outcomes: &[300, 0], underdog: 0, weighted_outcomes: &[300, 0], weighted_underdog: 0 };
+
+        // Both share the losing pool equally, with the underdog bet's share raised by half
+        assert_eq!(compute_returns(1, &escrow, &underdog, 15_000), (100, 225));
+        assert_eq!(compute_returns(1, &escrow, &normal, 15_000), (100, 150));
+
+        // Underdog bets lose with the underdog, and count towards the losing pool
+        assert_eq!(compute_returns(0, &escrow, &underdog, 15_000), (0, 0));
+        assert_eq!(compute_returns(0, &escrow, &favourite, 15_000), (300, 200));
+    }
+
+    #[test]
+    fn check_market_config_limits_the_underdog_bonus() {
+        assert_eq!(check_market_config(&MarketConfig { underdog_bonus: MAX_UNDERDOG_BONUS as u16, ..config() }), Ok(()));
+        assert_eq!(check_market_config(&MarketConfig { underdog_bonus: 9_999, ..config() }), Err(InitError::InvalidUnderdogBonus.into()));
+        assert_eq!(check_market_config(&MarketConfig { underdog_bonus: MAX_UNDERDOG_BONUS as u16 + 1, ..config() }), Err(InitError::InvalidUnderdogBonus.into()));
+    }
+
+    #[test]
+    fn round_claims_leave_out_stakes_on_losing_outcomes() {
+        let escrow: Escrow = escrow(vec![300, 100], 50);
+
+        assert_eq!(round_claims(&poll(Facet::Truthfulness, vec![5, 1], vec![], false), None, &escrow, LedgerPurpose::Stakes), 300);
+        assert_eq!(round_claims(&poll(Facet::Truthfulness, vec![1, 5], vec![], false), None, &escrow, LedgerPurpose::Stakes), 150);
+
+        // A tie returns every stake
+        assert_eq!(round_claims(&poll(Facet::Truthfulness, vec![3, 3], vec![], false), None, &escrow, LedgerPurpose::Stakes), 450);
+    }
+
+    #[test]
+    fn round_claims_of_bonds_are_the_appeal_bond() {
+        let escrow: Escrow = escrow(vec![300, 100], 0);
+        let appeal: Appeal = appeal(200, 1, false);
+
+        assert_eq!(round_claims(&poll(Facet::Truthfulness, vec![5, 1], vec![], true), Some(&appeal), &escrow, LedgerPurpose::Bonds), 200);
+        assert_eq!(round_claims(&poll(Facet::Truthfulness, vec![5, 1], vec![], false), Some(&appeal), &escrow, LedgerPurpose::Bonds), 0);
+        assert_eq!(round_claims(&poll(Facet::Truthfulness, vec![5, 1], vec![], true), None, &escrow, LedgerPurpose::Bonds), 0);
+    }
+}
@@ -1,3 +1,18 @@
 
 pub const VOTING_TOKENS_PROGRAM_ID: &str = "8MrQHajcffRco93T4kR5FiLnrCYA7nj1yYXoauHRdg5d";  // 
-pub const VOTING_TOKENS_MINT_ID: &str = "mintpubkey";                                       // 
+pub const VOTING_TOKENS_MINT_ID: &str = "EckPjWPUpkvdWSJmuUMcPfkg2V2oX62VFm4E8Bt5GaWX";  // 
+pub const MARKET_PROGRAM_ID: &str = "H4jYJQJhPSy7ANZwDZDkvE4Q9x5oQDz1tKaB2GRjrDpY";         // Program whose PDAs can accrue and draw on payables
+
+// MULTISIG
+pub const MAX_MULTISIG_SIGNERS: u8 = 10;    // Max number of keys which can share authority over the treasury
+
+// LEDGER
+pub const MAX_FACET_LENGTH: u8 = 32;        // Max length of the facet name on a ledger account
+
+// AUTHORITY
+pub const MAX_AUTHORITY_TIMELOCK: i64 = 30*24*60*60;    // Max wait before a proposed authority can accept: 30 days (s)
+
+// FEES
+pub const MAX_FEE_DESTINATIONS: u8 = 5;                     // Max number of keys to which fees can be withdrawn
+pub const MIN_FEE_WITHDRAWAL_DELAY: i64 = 24*60*60;         // Min wait before a scheduled fee withdrawal can execute: 1 day (s)
+pub const MAX_FEE_WITHDRAWAL_DELAY: i64 = 30*24*60*60;      // Max wait before a scheduled fee withdrawal can execute: 30 days (s)
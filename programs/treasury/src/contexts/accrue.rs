@@ -0,0 +1,174 @@
+use std::str::FromStr;
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer}
+};
+
+use crate::states::{LedgerPurpose, MarketSigner, Payable, Treasury, TreasuryAccount};
+use crate::constants::{MARKET_PROGRAM_ID, MAX_FACET_LENGTH};
+use crate::error::{LedgerError, TransactionError};
+
+#[derive(Accounts)]
+#[instruction(_market: MarketSigner, round: u16, facet: String, purpose: LedgerPurpose)]
+pub struct Accrue<'info_ac> {
+    #[account(mut)]
+    pub payer: Signer<'info_ac>,
+    pub caller: Signer<'info_ac>,   // Market PDA passed in, which only the market program can sign for
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_ac, Treasury>,
+    #[account(
+        init_if_needed,
+        space = 8 + Payable::INIT_SPACE,
+        payer = payer,
+        seeds = [b"payable", caller.key().as_ref(), round.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub payable: Account<'info_ac, Payable>,
+    #[account(
+        init_if_needed,
+        space = 8 + TreasuryAccount::INIT_SPACE,
+        payer = payer,
+        seeds = [b"ledger", caller.key().as_ref(), facet.as_bytes(), round.to_le_bytes().as_ref(), purpose.seed().as_ref()],
+        bump,
+    )]
+    pub ledger: Account<'info_ac, TreasuryAccount>,
+    pub system_program: Program<'info_ac, System>,
+}
+
+impl<'info_ac> Accrue<'info_ac> {
+
+    pub fn accrue(
+        &mut self,
+        bumps: &AccrueBumps,
+        market: MarketSigner,
+        round: u16,
+        facet: String,
+        purpose: LedgerPurpose,
+        amount: u64,
+    ) -> Result<()> {
+
+        // Requirements:                                                    |   Implemented:
+        //  - Caller should be the PDA of the market of the token           |       √
+        //  - Markets cannot accrue to the reserve or a subsidy              |       √
+        //  - Facet name should fit on the ledger account                   |       √
+        require!(is_market_pda(&self.caller, &market), TransactionError::CallerNotMarket);
+        require!(purpose != LedgerPurpose::Reserve && purpose != LedgerPurpose::Subsidy, TransactionError::WrongPurpose);
+        require!(facet.len() <= MAX_FACET_LENGTH.into(), LedgerError::FacetTooLong);
+
+        if self.payable.caller == Pubkey::default() {
+            self.payable.set_inner(
+                Payable {
+                    bump: bumps.payable,        // u8
+                    caller: self.caller.key(),  // Pubkey
+                    round,                      // u16
+                    owed: 0_u64,                // u64
+                    paid: 0_u64,                // u64
+                    deferred: 0_u64,            // u64
+                }
+            );
+        }
+
+        if self.ledger.is_new() {
+            self.ledger.set_inner(
+                TreasuryAccount {
+                    bump: bumps.ledger,         // u8
+                    market: self.caller.key(),  // Pubkey
+                    facet,                      // String
+                    round,                      // u16
+                    purpose,                    // LedgerPurpose
+                    balance: 0_u64,             // u64
+                    claims: 0_u64,              // u64
+                    claimed: 0_u64,             // u64
+                }
+            );
+        }
+
+        // The lamports are held by the treasury itself, so the ledger always adds up to its balance
+        self.transfer_sol(amount)?;
+        self.ledger.credit(&mut self.treasury, amount);
+
+        // Fees belong to the treasury, so only stakes and bonds are owed back to the round
+        if purpose != LedgerPurpose::Fees {
+            self.payable.owed += amount;
+        }
+
+        Ok(())
+
+    }
+
+    fn transfer_sol(&self, amount: u64) -> Result<()> {
+
+        let accounts = Transfer {
+            from: self.payer.to_account_info(),
+            to: self.treasury.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+
+        transfer(cpi_ctx, amount)
+
+    }
+
+}
+
+// Only the market program can sign for a market PDA, and deriving it from the token rules out its other PDAs.
+// The market passes its bump, so the address is rebuilt directly rather than searched for
+pub fn is_market_pda(caller: &AccountInfo, market: &MarketSigner) -> bool {
+    let address = Pubkey::create_program_address(&[b"market", market.token.as_ref(), &[market.bump]], &Pubkey::from_str(MARKET_PROGRAM_ID).unwrap());
+
+    address.is_ok_and(|address| caller.is_signer && caller.key() == address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pda(seed: &[u8], token: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[seed, token.as_ref()], &Pubkey::from_str(MARKET_PROGRAM_ID).unwrap())
+    }
+
+    fn is_caller_market_pda(caller: Pubkey, is_signer: bool, token: Pubkey, bump: u8) -> bool {
+        let mut lamports: u64 = 0;
+        let mut data: Vec<u8> = vec![];
+        let owner: Pubkey = Pubkey::default();
+
+        is_market_pda(&AccountInfo::new(&caller, is_signer, false, &mut lamports, &mut data, &owner, false, 0), &MarketSigner { token, bump })
+    }
+
+    #[test]
+    fn is_market_pda_accepts_the_signing_market_of_the_token() {
+        let token: Pubkey = Pubkey::new_unique();
+        let (market, bump) = pda(b"market", &token);
+
+        assert!(is_caller_market_pda(market, true, token, bump));
+        assert!(!is_caller_market_pda(market, false, token, bump));
+    }
+
+    #[test]
+    fn is_market_pda_rejects_other_tokens_and_pdas() {
+        let token: Pubkey = Pubkey::new_unique();
+        let other: Pubkey = Pubkey::new_unique();
+        let (market, bump) = pda(b"market", &token);
+        let (other_market, other_bump) = pda(b"market", &other);
+        let (escrow, escrow_bump) = pda(b"escrow", &token);
+
+        assert!(!is_caller_market_pda(other_market, true, token, other_bump));
+        assert!(!is_caller_market_pda(escrow, true, token, escrow_bump));
+        assert!(!is_caller_market_pda(Pubkey::new_unique(), true, token, bump));
+        assert!(!is_caller_market_pda(market, true, other, bump));
+    }
+
+    #[test]
+    fn is_market_pda_rejects_the_wrong_bump() {
+        let token: Pubkey = Pubkey::new_unique();
+        let (market, bump) = pda(b"market", &token);
+
+        // Any other bump derives a different address, if it derives one at all
+        assert!(!is_caller_market_pda(market, true, token, bump.wrapping_sub(1)));
+        assert!(!is_caller_market_pda(market, true, token, bump.wrapping_add(1)));
+    }
+}
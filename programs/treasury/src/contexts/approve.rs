@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Multisig, Proposal, Treasury};
+use crate::error::MultisigError;
+
+#[derive(Accounts)]
+pub struct Approve<'info_a> {
+    pub signer: Signer<'info_a>,
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_a, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info_a, Proposal>,
+}
+
+impl<'info_a> Approve<'info_a> {
+
+    pub fn approve(&mut self) -> Result<()> {
+
+        let multisig: &Multisig = match self.treasury.multisig.as_ref() {
+            Some(multisig) => multisig,
+            None => return err!(MultisigError::MultisigNotSet),
+        };
+
+        // Requirements:                                        |   Implemented:
+        //  - Treasury should be controlled by a multisig       |       √
+        //  - Signer should be one of the multisig signers      |       √
+        //  - Proposal should not have been executed            |       √
+        //  - Signer should not have approved already           |       √
+        require!(multisig.signers.contains(&self.signer.key()), MultisigError::NotASigner);
+        require!(!self.proposal.executed, MultisigError::AlreadyExecuted);
+        require!(!self.proposal.approvals.contains(&self.signer.key()), MultisigError::AlreadyApproved);
+
+        self.proposal.approvals.push(self.signer.key());
+
+        Ok(())
+
+    }
+
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{LedgerPurpose, MarketSigner, Payable, Treasury, TreasuryAccount};
+use crate::error::TransactionError;
+use crate::contexts::is_market_pda;
+
+#[derive(Accounts)]
+#[instruction(_market: MarketSigner, round: u16, facet: String, purpose: LedgerPurpose)]
+pub struct CollectFee<'info_cf> {
+    #[account(mut)]
+    pub payer: Signer<'info_cf>,
+    pub caller: Signer<'info_cf>,   // Market PDA passed in, which only the market program can sign for
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_cf, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"payable", caller.key().as_ref(), round.to_le_bytes().as_ref()],
+        bump = payable.bump,
+    )]
+    pub payable: Account<'info_cf, Payable>,
+    #[account(
+        mut,
+        seeds = [b"ledger", caller.key().as_ref(), facet.as_bytes(), round.to_le_bytes().as_ref(), purpose.seed().as_ref()],
+        bump = ledger.bump,
+    )]
+    pub ledger: Account<'info_cf, TreasuryAccount>,   // Stakes or bonds ledger from which the fee is kept
+    #[account(
+        init_if_needed,
+        space = 8 + TreasuryAccount::INIT_SPACE,
+        payer = payer,
+        seeds = [b"ledger", caller.key().as_ref(), facet.as_bytes(), round.to_le_bytes().as_ref(), LedgerPurpose::Fees.seed().as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Account<'info_cf, TreasuryAccount>,
+    pub system_program: Program<'info_cf, System>,
+}
+
+impl<'info_cf> CollectFee<'info_cf> {
+
+    pub fn collect_fee(
+        &mut self,
+        bumps: &CollectFeeBumps,
+        market: MarketSigner,
+        round: u16,
+        facet: String,
+        purpose: LedgerPurpose,
+        amount: u64,
+    ) -> Result<()> {
+
+        let purpose_condition: bool = purpose == LedgerPurpose::Stakes || purpose == LedgerPurpose::Bonds;
+
+        // Requirements:                                                    |   Implemented:
+        //  - Caller should be the PDA of the market of the token           |       √
+        //  - Fees should only be kept from stakes and bonds                |       √
+        //  - Amount should not be more than the round has left to pay      |       √
+        require!(is_market_pda(&self.caller, &market), TransactionError::CallerNotMarket);
+        require!(purpose_condition, TransactionError::WrongPurpose);
+        require!(self.payable.remaining() >= amount, TransactionError::PayableExceeded);
+
+        if self.fee_ledger.is_new() {
+            self.fee_ledger.set_inner(
+                TreasuryAccount {
+                    bump: bumps.fee_ledger,       // u8
+                    market: self.caller.key(),    // Pubkey
+                    facet,                        // String
+                    round,                        // u16
+                    purpose: LedgerPurpose::Fees, // LedgerPurpose
+                    balance: 0_u64,               // u64
+                    claims: 0_u64,                // u64
+                    claimed: 0_u64,               // u64
+                }
+            );
+        }
+
+        // The fee stays in the treasury but is no longer owed to the round
+        self.ledger.debit(&mut self.treasury, amount)?;
+        self.fee_ledger.credit(&mut self.treasury, amount);
+        self.payable.owed -= amount;
+
+        Ok(())
+
+    }
+
+}
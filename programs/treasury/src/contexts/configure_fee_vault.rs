@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{FeeVault, FeeVaultConfig, LedgerPurpose, Treasury, TreasuryAccount};
+use crate::constants::{MAX_FEE_DESTINATIONS, MAX_FEE_WITHDRAWAL_DELAY, MIN_FEE_WITHDRAWAL_DELAY};
+use crate::error::{FeeError, TransactionError};
+
+#[derive(Accounts)]
+pub struct ConfigureFeeVault<'info_cv> {
+    #[account(mut)]
+    pub signer: Signer<'info_cv>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_cv, Treasury>,
+    #[account(
+        init_if_needed,
+        space = 8 + FeeVault::INIT_SPACE,
+        payer = signer,
+        seeds = [b"fee_vault"],
+        bump,
+    )]
+    pub fee_vault: Account<'info_cv, FeeVault>,
+    #[account(
+        init_if_needed,
+        space = 8 + TreasuryAccount::INIT_SPACE,
+        payer = signer,
+        seeds = [b"ledger".as_ref(), b"fees".as_ref()],
+        bump,
+    )]
+    pub vault_ledger: Account<'info_cv, TreasuryAccount>,   // Ledger account for the fees swept out of market rounds
+    pub system_program: Program<'info_cv, System>,
+}
+
+impl<'info_cv> ConfigureFeeVault<'info_cv> {
+
+    pub fn configure_fee_vault(
+        &mut self,
+        bumps: &ConfigureFeeVaultBumps,
+        delay: i64,
+        destinations: Vec<Pubkey>,
+    ) -> Result<()> {
+
+        // Requirements:                                                    |   Implemented:
+        //  - Signer should be the treasury authority                       |       √
+        //  - Treasury should not be controlled by a multisig               |       √
+        require!(self.signer.key() == self.treasury.authority, TransactionError::SignerNotAuthority);
+        require!(self.treasury.multisig.is_none(), FeeError::MultisigControlled);
+
+        // Only the first configuration takes effect at once, since there are no fees to protect until then
+        if self.fee_vault.is_new() {
+            check_config(delay, &destinations)?;
+
+            self.fee_vault.set_inner(
+                FeeVault {
+                    bump: bumps.fee_vault,      // u8
+                    delay,                      // i64
+                    destinations,               // Vec<Pubkey>
+                    pending: None,              // Option<FeeWithdrawal>
+                    pending_config: None,       // Option<FeeVaultConfig>
+                }
+            );
+
+            msg!("Fee vault configured with a delay of {}s and destinations {:?}", delay, self.fee_vault.destinations);
+        } else {
+            schedule_config(&mut self.fee_vault, delay, destinations)?;
+        }
+
+        if self.vault_ledger.is_new() {
+            self.vault_ledger.set_inner(
+                TreasuryAccount {
+                    bump: bumps.vault_ledger,           // u8
+                    market: Pubkey::default(),          // Pubkey
+                    facet: String::new(),               // String
+                    round: 0_u16,                       // u16
+                    purpose: LedgerPurpose::Fees,       // LedgerPurpose
+                    balance: 0_u64,                     // u64
+                    claims: 0_u64,                      // u64
+                    claimed: 0_u64,                     // u64
+                }
+            );
+        }
+
+        Ok(())
+
+    }
+
+    // Anyone can apply a configuration once the delay has passed, since it is already fixed
+    pub fn apply_fee_vault_config(&mut self) -> Result<()> {
+
+        let time: i64 = Clock::get()?.unix_timestamp;
+
+        let config: FeeVaultConfig = match self.fee_vault.pending_config.clone() {
+            Some(config) => config,
+            None => return err!(FeeError::NoPendingConfig),
+        };
+
+        // Requirements:                                                    |   Implemented:
+        //  - There should be a pending configuration                       |       √
+        //  - Delay should have passed                                      |       √
+        require!(config.executable_after <= time, FeeError::DelayNotPassed);
+
+        self.fee_vault.delay = config.delay;
+        self.fee_vault.destinations = config.destinations;
+        self.fee_vault.pending_config = None;
+
+        msg!("Fee vault configured with a delay of {}s and destinations {:?}", self.fee_vault.delay, self.fee_vault.destinations);
+
+        Ok(())
+
+    }
+
+    pub fn cancel_fee_vault_config(&mut self) -> Result<()> {
+
+        // Requirements:                                                    |   Implemented:
+        //  - Signer should be the treasury authority                       |       √
+        //  - Treasury should not be controlled by a multisig               |       √
+        //  - There should be a pending configuration                       |       √
+        require!(self.signer.key() == self.treasury.authority, TransactionError::SignerNotAuthority);
+        require!(self.treasury.multisig.is_none(), FeeError::MultisigControlled);
+        require!(self.fee_vault.pending_config.is_some(), FeeError::NoPendingConfig);
+
+        self.fee_vault.pending_config = None;
+
+        msg!("Pending fee vault configuration cancelled");
+
+        Ok(())
+
+    }
+
+}
+
+fn check_config(delay: i64, destinations: &[Pubkey]) -> Result<()> {
+
+    let distinct: bool = destinations.iter().enumerate().all(|(i, destination)| !destinations[..i].contains(destination));
+    let destinations_condition: bool = distinct && !destinations.is_empty() && destinations.len() <= MAX_FEE_DESTINATIONS.into();
+
+    // Requirements:                                                    |   Implemented:
+    //  - Delay should be within limits                                 |       √
+    //  - Destinations should be distinct and within limits             |       √
+    require!((MIN_FEE_WITHDRAWAL_DELAY..=MAX_FEE_WITHDRAWAL_DELAY).contains(&delay), FeeError::InvalidDelay);
+    require!(destinations_condition, FeeError::InvalidDestinations);
+
+    Ok(())
+
+}
+
+// Changes wait on the current delay, so the allowlist cannot be swapped out from under a withdrawal's review.
+// A multisig treasury schedules them through a proposal.
+pub fn schedule_config(
+    fee_vault: &mut FeeVault,
+    delay: i64,
+    destinations: Vec<Pubkey>,
+) -> Result<()> {
+
+    let executable_after: i64 = Clock::get()?.unix_timestamp + fee_vault.delay;
+
+    // Requirements:                                                    |   Implemented:
+    //  - No configuration should be pending                            |       √
+    require!(fee_vault.pending_config.is_none(), FeeError::ConfigPending);
+
+    check_config(delay, &destinations)?;
+
+    msg!("Fee vault configuration with a delay of {}s and destinations {:?} scheduled for {}", delay, destinations, executable_after);
+
+    fee_vault.pending_config = Some(
+        FeeVaultConfig {
+            delay,                      // i64
+            destinations,               // Vec<Pubkey>
+            executable_after,           // i64
+        }
+    );
+
+    Ok(())
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn destinations(n: usize) -> Vec<Pubkey> {
+        (0..n).map(|_| Pubkey::new_unique()).collect()
+    }
+
+    #[test]
+    fn check_config_limits_the_delay() {
+        assert_eq!(check_config(MIN_FEE_WITHDRAWAL_DELAY, &destinations(1)), Ok(()));
+        assert_eq!(check_config(MAX_FEE_WITHDRAWAL_DELAY, &destinations(1)), Ok(()));
+        assert_eq!(check_config(MIN_FEE_WITHDRAWAL_DELAY - 1, &destinations(1)), Err(FeeError::InvalidDelay.into()));
+        assert_eq!(check_config(MAX_FEE_WITHDRAWAL_DELAY + 1, &destinations(1)), Err(FeeError::InvalidDelay.into()));
+    }
+
+    #[test]
+    fn check_config_limits_the_destinations() {
+        let mut repeated: Vec<Pubkey> = destinations(2);
+        repeated.push(repeated[0]);
+
+        assert_eq!(check_config(MIN_FEE_WITHDRAWAL_DELAY, &destinations(MAX_FEE_DESTINATIONS.into())), Ok(()));
+        assert_eq!(check_config(MIN_FEE_WITHDRAWAL_DELAY, &destinations(0)), Err(FeeError::InvalidDestinations.into()));
+        assert_eq!(check_config(MIN_FEE_WITHDRAWAL_DELAY, &destinations(usize::from(MAX_FEE_DESTINATIONS) + 1)), Err(FeeError::InvalidDestinations.into()));
+        assert_eq!(check_config(MIN_FEE_WITHDRAWAL_DELAY, &repeated), Err(FeeError::InvalidDestinations.into()));
+    }
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+
+use crate::contexts::{pay_iou, schedule_config, schedule_withdrawal};
+use crate::states::{FeeVault, Iou, Multisig, Proposal, ProposalAction, Treasury, TreasuryAccount};
+use crate::error::{FeeError, IouError, LedgerError, MultisigError};
+
+#[derive(Accounts)]
+pub struct Execute<'info_e> {
+    pub signer: Signer<'info_e>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_e, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info_e, Proposal>,
+    /// CHECK: Only receives lamports, and must match the recipient of a withdrawal or the key an IOU is owed to
+    #[account(mut)]
+    pub recipient: Option<UncheckedAccount<'info_e>>,
+    #[account(
+        mut,
+        seeds = [b"ledger".as_ref(), b"reserve".as_ref()],
+        bump,
+    )]
+    pub reserve: Option<Account<'info_e, TreasuryAccount>>,  // Only required for withdrawals and IOUs, which come out of the reserve
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = fee_vault.bump,
+    )]
+    pub fee_vault: Option<Account<'info_e, FeeVault>>,       // Only required for fee withdrawals and configuration
+    #[account(
+        seeds = [b"ledger".as_ref(), b"fees".as_ref()],
+        bump = vault_ledger.bump,
+    )]
+    pub vault_ledger: Option<Account<'info_e, TreasuryAccount>>,
+    #[account(
+        mut,
+        seeds = [b"iou", iou.caller.as_ref(), iou.round.to_le_bytes().as_ref(), iou.coparty.as_ref()],
+        bump = iou.bump,
+    )]
+    pub iou: Option<Account<'info_e, Iou>>,                  // Only required to settle an IOU
+}
+
+impl<'info_e> Execute<'info_e> {
+
+    pub fn execute(&mut self) -> Result<()> {
+
+        let multisig: &Multisig = match self.treasury.multisig.as_ref() {
+            Some(multisig) => multisig,
+            None => return err!(MultisigError::MultisigNotSet),
+        };
+
+        let approvals_condition: bool = multisig.approvals(&self.proposal.approvals) >= multisig.threshold.into();
+
+        // Requirements:                                                    |   Implemented:
+        //  - Treasury should be controlled by a multisig                   |       √
+        //  - Signer should be one of the multisig signers                  |       √
+        //  - Proposal should not have been executed                        |       √
+        //  - Proposal should be approved by a threshold of current signers |       √
+        require!(multisig.signers.contains(&self.signer.key()), MultisigError::NotASigner);
+        require!(!self.proposal.executed, MultisigError::AlreadyExecuted);
+        require!(approvals_condition, MultisigError::NotEnoughApprovals);
+
+        self.proposal.executed = true;
+
+        match self.proposal.action.clone() {
+            ProposalAction::Withdraw { recipient, amount } => self.withdraw(recipient, amount),
+            ProposalAction::SetAuthority { authority, multisig } => {
+                self.treasury.authority = authority;
+                self.treasury.multisig = multisig;
+                self.treasury.pending_authority = None;
+
+                Ok(())
+            },
+            ProposalAction::ScheduleFeeWithdrawal { destination, amount } => {
+                match (self.fee_vault.as_mut(), self.vault_ledger.as_ref()) {
+                    (Some(fee_vault), Some(vault_ledger)) => schedule_withdrawal(fee_vault, vault_ledger, destination, amount),
+                    _ => err!(MultisigError::AccountsMissing),
+                }
+            },
+            ProposalAction::CancelFeeWithdrawal => {
+                let fee_vault: &mut Account<'info_e, FeeVault> = match self.fee_vault.as_mut() {
+                    Some(fee_vault) => fee_vault,
+                    None => return err!(MultisigError::AccountsMissing),
+                };
+
+                require!(fee_vault.pending.is_some(), FeeError::NoPendingWithdrawal);
+
+                fee_vault.pending = None;
+
+                msg!("Pending fee withdrawal cancelled");
+
+                Ok(())
+            },
+            ProposalAction::ConfigureFeeVault { delay, destinations } => {
+                match self.fee_vault.as_mut() {
+                    Some(fee_vault) => schedule_config(fee_vault, delay, destinations),
+                    None => err!(MultisigError::AccountsMissing),
+                }
+            },
+            ProposalAction::CancelFeeVaultConfig => {
+                let fee_vault: &mut Account<'info_e, FeeVault> = match self.fee_vault.as_mut() {
+                    Some(fee_vault) => fee_vault,
+                    None => return err!(MultisigError::AccountsMissing),
+                };
+
+                require!(fee_vault.pending_config.is_some(), FeeError::NoPendingConfig);
+
+                fee_vault.pending_config = None;
+
+                msg!("Pending fee vault configuration cancelled");
+
+                Ok(())
+            },
+            ProposalAction::SettleIou { iou, amount } => self.settle_iou(iou, amount),
+            ProposalAction::SetSubsidyBudget { amount } => {
+                self.treasury.subsidy_budget = amount;
+
+                msg!("Subsidy budget set to {} lamports", amount);
+
+                Ok(())
+            },
+        }
+
+    }
+
+    fn withdraw(&mut self, recipient: Pubkey, amount: u64) -> Result<()> {
+
+        let to: AccountInfo<'info_e> = match self.recipient.as_ref() {
+            Some(to) if to.key() == recipient => to.to_account_info(),
+            _ => return err!(MultisigError::WrongRecipient),
+        };
+
+        let treasury: AccountInfo<'info_e> = self.treasury.to_account_info();
+        let rent: u64 = Rent::get()?.minimum_balance(treasury.data_len());
+
+        require!(treasury.lamports() >= rent + amount, MultisigError::InsufficientFunds);
+
+        match self.reserve.as_mut() {
+            Some(reserve) => reserve.debit(&mut self.treasury, amount)?,
+            None => return err!(LedgerError::InsufficientBalance),
+        }
+
+        // The treasury is owned by this program, so its lamports can be moved directly
+        **treasury.try_borrow_mut_lamports()? -= amount;
+        **to.try_borrow_mut_lamports()? += amount;
+
+        Ok(())
+
+    }
+
+    fn settle_iou(&mut self, key: Pubkey, amount: u64) -> Result<()> {
+
+        let coparty: AccountInfo<'info_e> = match self.recipient.as_ref() {
+            Some(coparty) => coparty.to_account_info(),
+            None => return err!(MultisigError::AccountsMissing),
+        };
+
+        match (self.reserve.as_mut(), self.iou.as_mut()) {
+            (Some(reserve), Some(iou)) => {
+                require!(iou.key() == key, IouError::NotAnIou);
+
+                pay_iou(&mut self.treasury, reserve, iou, &coparty, amount)
+            },
+            _ => err!(MultisigError::AccountsMissing),
+        }
+
+    }
+
+}
@@ -14,7 +14,7 @@ pub struct Initialise<'info_i> {
     pub signer: Signer<'info_i>,
     #[account(
         init,
-        space = Treasury::INIT_SPACE,
+        space = 8 + Treasury::INIT_SPACE,
         payer = signer,
         seeds = [b"treasury"],
         bump,
@@ -49,6 +49,14 @@ impl<'info_i> Initialise<'info_i> {
             Treasury { 
                 bump: bumps.treasury,           //u8
                 authority: self.signer.key(),   // Pubkey
+                multisig: None,                 // Option<Multisig>
+                proposal_count: 0_u64,          // u64
+                pending_authority: None,        // Option<Pubkey>
+                pending_after: 0_i64,           // i64
+                ledger_total: 0_u64,            // u64
+                owed_total: 0_u64,              // u64
+                iou_total: 0_u64,               // u64
+                subsidy_budget: 0_u64,          // u64
             }
         );
 
@@ -1,5 +1,39 @@
 pub mod initialise;
 pub mod transact;
+pub mod set_multisig;
+pub mod propose;
+pub mod approve;
+pub mod execute;
+pub mod rotate_authority;
+pub mod accrue;
+pub mod reimburse;
+pub mod collect_fee;
+pub mod reconcile;
+pub mod sync_reserve;
+pub mod configure_fee_vault;
+pub mod sweep_fees;
+pub mod withdraw_fees;
+pub mod voting_token_custody;
+pub mod settle_iou;
+pub mod solvency;
+pub mod subsidise;
 
 pub use initialise::*;
 pub use transact::*;
+pub use set_multisig::*;
+pub use propose::*;
+pub use approve::*;
+pub use execute::*;
+pub use rotate_authority::*;
+pub use accrue::*;
+pub use reimburse::*;
+pub use collect_fee::*;
+pub use reconcile::*;
+pub use sync_reserve::*;
+pub use configure_fee_vault::*;
+pub use sweep_fees::*;
+pub use withdraw_fees::*;
+pub use voting_token_custody::*;
+pub use settle_iou::*;
+pub use solvency::*;
+pub use subsidise::*;
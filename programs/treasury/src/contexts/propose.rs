@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Multisig, Proposal, ProposalAction, Treasury};
+use crate::error::MultisigError;
+
+#[derive(Accounts)]
+pub struct Propose<'info_p> {
+    #[account(mut)]
+    pub signer: Signer<'info_p>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_p, Treasury>,
+    #[account(
+        init,
+        space = 8 + Proposal::INIT_SPACE,
+        payer = signer,
+        seeds = [b"proposal", treasury.proposal_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info_p, Proposal>,
+    pub system_program: Program<'info_p, System>,
+}
+
+impl<'info_p> Propose<'info_p> {
+
+    pub fn propose(
+        &mut self,
+        bumps: &ProposeBumps,
+        action: ProposalAction,
+    ) -> Result<()> {
+
+        let multisig: &Multisig = match self.treasury.multisig.as_ref() {
+            Some(multisig) => multisig,
+            None => return err!(MultisigError::MultisigNotSet),
+        };
+
+        let action_condition: bool = match &action {
+            ProposalAction::Withdraw { amount, .. } => *amount > 0,
+            ProposalAction::SetAuthority { multisig, .. } => multisig.as_ref().is_none_or(|multisig| multisig.is_valid()),
+            ProposalAction::ScheduleFeeWithdrawal { amount, .. } => *amount > 0,
+            ProposalAction::CancelFeeWithdrawal => true,
+            ProposalAction::ConfigureFeeVault { .. } => true,
+            ProposalAction::CancelFeeVaultConfig => true,
+            ProposalAction::SettleIou { amount, .. } => *amount > 0,
+            ProposalAction::SetSubsidyBudget { .. } => true,
+        };
+
+        // Requirements:                                                    |   Implemented:
+        //  - Treasury should be controlled by a multisig                   |       √
+        //  - Signer should be one of the multisig signers                  |       √
+        //  - Payouts should be non-zero and any new multisig valid         |       √
+        require!(multisig.signers.contains(&self.signer.key()), MultisigError::NotASigner);
+        require!(action_condition, MultisigError::InvalidMultisig);
+
+        self.proposal.set_inner(
+            Proposal {
+                bump: bumps.proposal,                       // u8
+                id: self.treasury.proposal_count,           // u64
+                proposer: self.signer.key(),                // Pubkey
+                action,                                     // ProposalAction
+                approvals: vec![self.signer.key()],         // Vec<Pubkey>
+                executed: false,                            // bool
+                created_at: Clock::get()?.unix_timestamp,   // i64
+            }
+        );
+
+        self.treasury.proposal_count += 1;
+
+        Ok(())
+
+    }
+
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::states::Treasury;
+use crate::error::LedgerError;
+
+#[derive(Accounts)]
+pub struct Reconcile<'info_rc> {
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_rc, Treasury>,
+}
+
+impl<'info_rc> Reconcile<'info_rc> {
+
+    // Lamports sent straight to the treasury are on no ledger until sync_reserve records them in the reserve
+    pub fn reconcile(&self) -> Result<u64> {
+
+        let treasury: AccountInfo<'info_rc> = self.treasury.to_account_info();
+        let rent: u64 = Rent::get()?.minimum_balance(treasury.data_len());
+
+        // Requirements:                                                    |   Implemented:
+        //  - Lamports above rent should equal the ledger balances          |       √
+        require!(self.treasury.unrecorded(treasury.lamports().saturating_sub(rent))? == 0, LedgerError::LedgerMismatch);
+
+        Ok(self.treasury.ledger_total)
+
+    }
+
+}
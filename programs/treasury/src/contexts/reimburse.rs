@@ -0,0 +1,160 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{create_account, CreateAccount}
+};
+
+use crate::states::{Iou, LedgerPurpose, MarketSigner, Payable, Treasury, TreasuryAccount};
+use crate::error::{IouError, TransactionError};
+use crate::contexts::is_market_pda;
+
+#[derive(Accounts)]
+#[instruction(_market: MarketSigner, round: u16, facet: String, purpose: LedgerPurpose)]
+pub struct Reimburse<'info_r> {
+    #[account(mut)]
+    pub payer: Signer<'info_r>,     // Pays for the IOU account if the round cannot pay the claim in full
+    pub caller: Signer<'info_r>,    // Market PDA passed in, which only the market program can sign for
+    /// CHECK: Only receives lamports
+    #[account(mut)]
+    pub coparty: UncheckedAccount<'info_r>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_r, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"payable", caller.key().as_ref(), round.to_le_bytes().as_ref()],
+        bump = payable.bump,
+    )]
+    pub payable: Account<'info_r, Payable>,
+    #[account(
+        mut,
+        seeds = [b"ledger", caller.key().as_ref(), facet.as_bytes(), round.to_le_bytes().as_ref(), purpose.seed().as_ref()],
+        bump = ledger.bump,
+    )]
+    pub ledger: Account<'info_r, TreasuryAccount>,
+    /// CHECK: Only created by this program, and only once a claim falls short
+    #[account(
+        mut,
+        seeds = [b"iou", caller.key().as_ref(), round.to_le_bytes().as_ref(), coparty.key().as_ref()],
+        bump,
+    )]
+    pub iou: UncheckedAccount<'info_r>,
+    pub system_program: Program<'info_r, System>,
+}
+
+impl<'info_r> Reimburse<'info_r> {
+
+    pub fn reimburse(
+        &mut self,
+        bumps: &ReimburseBumps,
+        market: MarketSigner,
+        round: u16,
+        purpose: LedgerPurpose,
+        amount: u64,
+        claims: u64,
+    ) -> Result<()> {
+
+        let treasury: AccountInfo<'info_r> = self.treasury.to_account_info();
+        let rent: u64 = Rent::get()?.minimum_balance(treasury.data_len());
+
+        let purpose_condition: bool = purpose == LedgerPurpose::Stakes || purpose == LedgerPurpose::Bonds;
+
+        // Requirements:                                                    |   Implemented:
+        //  - Caller should be the PDA of the market of the token           |       √
+        //  - Payouts should only be drawn from stakes and bonds            |       √
+        //  - Amount should not be more than the round has left to pay      |       √
+        require!(is_market_pda(&self.caller, &market), TransactionError::CallerNotMarket);
+        require!(purpose_condition, TransactionError::WrongPurpose);
+        require!(self.payable.remaining() >= amount, TransactionError::PayableExceeded);
+
+        let available: u64 = self.ledger.balance.min(treasury.lamports().saturating_sub(rent));
+
+        // The market passes the total of the claims on the ledger once the round is resolved, which leaves out
+        // stakes on losing outcomes, so a short round is shared out among the claims it actually has to pay
+        if claims > 0 {
+            self.ledger.claims = claims;
+        }
+
+        let paid: u64 = self.ledger.payout(amount, available);
+
+        if paid < amount {
+            msg!("Round {} of {} is short, paying {} of a claim of {} lamports", round, self.caller.key(), paid, amount);
+        }
+
+        // Only what is paid out counts as paid, with any remainder owed by an IOU
+        if self.ledger.claims > 0 {
+            self.ledger.claimed += amount;
+        }
+
+        self.payable.paid += paid;
+        self.payable.deferred += amount - paid;
+        self.ledger.debit(&mut self.treasury, paid)?;
+
+        // The treasury is owned by this program, so its lamports can be moved directly
+        **treasury.try_borrow_mut_lamports()? -= paid;
+        **self.coparty.to_account_info().try_borrow_mut_lamports()? += paid;
+
+        if paid < amount {
+            self.record_iou(bumps.iou, round, amount - paid)?;
+        }
+
+        Ok(())
+
+    }
+
+    fn record_iou(
+        &mut self,
+        bump: u8,
+        round: u16,
+        amount: u64,
+    ) -> Result<()> {
+
+        let iou: AccountInfo<'info_r> = self.iou.to_account_info();
+
+        let mut record: Iou = match iou.data_is_empty() {
+            true => {
+                let space: usize = 8 + Iou::INIT_SPACE;
+                let round_bytes: [u8; 2] = round.to_le_bytes();
+                let caller: Pubkey = self.caller.key();
+                let coparty: Pubkey = self.coparty.key();
+                let seeds: &[&[u8]] = &[b"iou", caller.as_ref(), round_bytes.as_ref(), coparty.as_ref(), &[bump]];
+                let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+                let accounts = CreateAccount {
+                    from: self.payer.to_account_info(),
+                    to: iou.clone(),
+                };
+
+                let cpi_ctx = CpiContext::new_with_signer(self.system_program.to_account_info(), accounts, signer_seeds);
+
+                create_account(cpi_ctx, Rent::get()?.minimum_balance(space), space as u64, &crate::ID)?;
+
+                Iou {
+                    bump,               // u8
+                    caller,             // Pubkey
+                    round,              // u16
+                    coparty,            // Pubkey
+                    amount: 0_u64,      // u64
+                }
+            },
+            false => {
+                require!(iou.owner == &crate::ID, IouError::NotAnIou);
+
+                Iou::try_deserialize(&mut &iou.try_borrow_data()?[..])?
+            },
+        };
+
+        record.amount += amount;
+        self.treasury.iou_total += amount;
+
+        msg!("IOU of {} lamports recorded for {}", amount, record.coparty);
+
+        let mut data = iou.try_borrow_mut_data()?;
+
+        record.try_serialize(&mut &mut data[..])
+
+    }
+
+}
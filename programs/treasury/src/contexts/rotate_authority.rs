@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::states::Treasury;
+use crate::constants::MAX_AUTHORITY_TIMELOCK;
+use crate::error::{AuthorityError, TransactionError};
+
+#[derive(Accounts)]
+pub struct RotateAuthority<'info_ra> {
+    pub signer: Signer<'info_ra>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_ra, Treasury>,
+}
+
+impl<'info_ra> RotateAuthority<'info_ra> {
+
+    pub fn propose_authority(
+        &mut self,
+        authority: Pubkey,
+        timelock: i64,
+    ) -> Result<()> {
+
+        // Requirements:                                                    |   Implemented:
+        //  - Signer should be the treasury authority                       |       √
+        //  - Treasury should not be controlled by a multisig               |       √
+        //  - Timelock should be within limits                              |       √
+        require!(self.signer.key() == self.treasury.authority, TransactionError::SignerNotAuthority);
+        require!(self.treasury.multisig.is_none(), AuthorityError::MultisigControlled);
+        require!((0..=MAX_AUTHORITY_TIMELOCK).contains(&timelock), AuthorityError::InvalidTimelock);
+
+        // Any earlier proposal is replaced, restarting the timelock
+        self.treasury.pending_authority = Some(authority);
+        self.treasury.pending_after = Clock::get()?.unix_timestamp + timelock;
+
+        Ok(())
+
+    }
+
+    pub fn accept_authority(&mut self) -> Result<()> {
+
+        let time: i64 = Clock::get()?.unix_timestamp;
+
+        // Requirements:                                                    |   Implemented:
+        //  - Treasury should not be controlled by a multisig               |       √
+        //  - Signer should be the pending authority                        |       √
+        //  - Timelock should have passed                                   |       √
+        require!(self.treasury.multisig.is_none(), AuthorityError::MultisigControlled);
+        require!(self.treasury.pending_authority.is_some(), AuthorityError::NoPendingAuthority);
+        require!(self.treasury.pending_authority == Some(self.signer.key()), AuthorityError::SignerNotPendingAuthority);
+        require!(self.treasury.pending_after <= time, AuthorityError::TimelockNotPassed);
+
+        self.treasury.authority = self.signer.key();
+        self.treasury.pending_authority = None;
+
+        Ok(())
+
+    }
+
+    pub fn cancel_authority(&mut self) -> Result<()> {
+
+        // Requirements:                                                    |   Implemented:
+        //  - Signer should be the treasury authority                       |       √
+        //  - There should be a pending authority                           |       √
+        require!(self.signer.key() == self.treasury.authority, TransactionError::SignerNotAuthority);
+        require!(self.treasury.pending_authority.is_some(), AuthorityError::NoPendingAuthority);
+
+        self.treasury.pending_authority = None;
+
+        Ok(())
+
+    }
+
+}
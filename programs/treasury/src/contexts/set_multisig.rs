@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Multisig, Treasury};
+use crate::error::{MultisigError, TransactionError};
+
+#[derive(Accounts)]
+pub struct SetMultisig<'info_sm> {
+    #[account(mut)]
+    pub signer: Signer<'info_sm>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_sm, Treasury>,
+}
+
+impl<'info_sm> SetMultisig<'info_sm> {
+
+    pub fn set_multisig(
+        &mut self,
+        multisig: Multisig,
+    ) -> Result<()> {
+
+        // Requirements:                                                    |   Implemented:
+        //  - Signer should be the treasury authority                       |       √
+        //  - Treasury should not already be controlled by a multisig       |       √
+        //  - Multisig should have distinct signers and a valid threshold   |       √
+        require!(self.signer.key() == self.treasury.authority, TransactionError::SignerNotAuthority);
+        require!(self.treasury.multisig.is_none(), MultisigError::MultisigAlreadySet);
+        require!(multisig.is_valid(), MultisigError::InvalidMultisig);
+
+        // From here on the multisig can only be changed by a proposal which it has approved
+        self.treasury.multisig = Some(multisig);
+
+        Ok(())
+
+    }
+
+}
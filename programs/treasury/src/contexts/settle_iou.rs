@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Iou, Treasury, TreasuryAccount};
+use crate::error::{IouError, TransactionError};
+
+#[derive(Accounts)]
+pub struct SettleIou<'info_si> {
+    pub signer: Signer<'info_si>,
+    /// CHECK: Only receives lamports, and must be the key the IOU is owed to
+    #[account(mut)]
+    pub coparty: UncheckedAccount<'info_si>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_si, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"iou", iou.caller.as_ref(), iou.round.to_le_bytes().as_ref(), coparty.key().as_ref()],
+        bump = iou.bump,
+    )]
+    pub iou: Account<'info_si, Iou>,
+    #[account(
+        mut,
+        seeds = [b"ledger".as_ref(), b"reserve".as_ref()],
+        bump = reserve.bump,
+    )]
+    pub reserve: Account<'info_si, TreasuryAccount>,    // IOUs are settled out of funds held outside of any market
+}
+
+impl<'info_si> SettleIou<'info_si> {
+
+    pub fn settle_iou(&mut self, amount: u64) -> Result<()> {
+
+        // Requirements:                                                    |   Implemented:
+        //  - Signer should be the treasury authority                       |       √
+        //  - Treasury should not be controlled by a multisig               |       √
+        require!(self.signer.key() == self.treasury.authority, TransactionError::SignerNotAuthority);
+        require!(self.treasury.multisig.is_none(), TransactionError::MultisigControlled);
+
+        pay_iou(
+            &mut self.treasury,
+            &mut self.reserve,
+            &mut self.iou,
+            &self.coparty.to_account_info(),
+            amount,
+        )
+
+    }
+
+}
+
+// A multisig treasury settles IOUs through a proposal, which pays out in the same way
+pub fn pay_iou<'info>(
+    treasury: &mut Account<'info, Treasury>,
+    reserve: &mut Account<'info, TreasuryAccount>,
+    iou: &mut Account<'info, Iou>,
+    coparty: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+
+    let treasury_info: AccountInfo<'info> = treasury.to_account_info();
+    let rent: u64 = Rent::get()?.minimum_balance(treasury_info.data_len());
+
+    // Requirements:                                                    |   Implemented:
+    //  - Coparty should be the key the IOU is owed to                  |       √
+    //  - Amount should not be more than the IOU                        |       √
+    //  - Treasury should hold the amount above its rent                |       √
+    require!(coparty.key() == iou.coparty, IouError::NotAnIou);
+    require!(iou.amount >= amount, IouError::ExceedsIou);
+    require!(treasury_info.lamports() >= rent + amount, TransactionError::InsufficientFunds);
+
+    reserve.debit(treasury, amount)?;
+    iou.amount -= amount;
+    treasury.iou_total -= amount;
+
+    // The treasury is owned by this program, so its lamports can be moved directly
+    **treasury_info.try_borrow_mut_lamports()? -= amount;
+    **coparty.try_borrow_mut_lamports()? += amount;
+
+    Ok(())
+
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::states::Treasury;
+use crate::error::LedgerError;
+
+#[derive(Accounts)]
+pub struct Solvency<'info_sv> {
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_sv, Treasury>,
+}
+
+impl<'info_sv> Solvency<'info_sv> {
+
+    // Free balance is what the treasury holds above rent once everything it owes is set aside,
+    // which is negative when it cannot cover its obligations
+    pub fn solvency(&self) -> Result<i64> {
+
+        let treasury: AccountInfo<'info_sv> = self.treasury.to_account_info();
+        let rent: u64 = Rent::get()?.minimum_balance(treasury.data_len());
+
+        let obligations: u64 = self.treasury.owed_total.checked_add(self.treasury.iou_total).ok_or(LedgerError::Overflow)?;
+        let free: i64 = free_balance(treasury.lamports(), rent, obligations)?;
+
+        msg!("Treasury free balance is {} lamports, with {} lamports owed", free, obligations);
+
+        Ok(free)
+
+    }
+
+}
+
+// Worked out in i128, which holds any difference of u64s, so it can only fail if the result is out of range of an i64
+pub fn free_balance(lamports: u64, rent: u64, obligations: u64) -> Result<i64> {
+    let free: i128 = i128::from(lamports) - i128::from(rent) - i128::from(obligations);
+
+    i64::try_from(free).map_err(|_| error!(LedgerError::Overflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_balance_is_negative_when_obligations_exceed_the_balance() {
+        assert_eq!(free_balance(1_500, 100, 1_000), Ok(400));
+        assert_eq!(free_balance(1_000, 100, 1_000), Ok(-100));
+    }
+
+    #[test]
+    fn free_balance_rejects_results_out_of_range() {
+        assert_eq!(free_balance(u64::MAX, 0, 0), Err(LedgerError::Overflow.into()));
+        assert_eq!(free_balance(0, 0, u64::MAX), Err(LedgerError::Overflow.into()));
+        assert_eq!(free_balance(i64::MAX as u64, 0, 0), Ok(i64::MAX));
+    }
+}
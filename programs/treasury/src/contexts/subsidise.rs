@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{LedgerPurpose, MarketSigner, Treasury, TreasuryAccount};
+use crate::constants::MAX_FACET_LENGTH;
+use crate::error::{LedgerError, TransactionError};
+use crate::contexts::is_market_pda;
+
+#[derive(Accounts)]
+#[instruction(_market: MarketSigner, round: u16, facet: String)]
+pub struct Subsidise<'info_sb> {
+    #[account(mut)]
+    pub payer: Signer<'info_sb>,
+    pub authority: Signer<'info_sb>,    // Treasury authority, who decides how much of the reserve a market maker gets
+    pub caller: Signer<'info_sb>,       // Market PDA passed in, which only the market program can sign for
+    /// CHECK: Only receives lamports, and is the market maker's pool account
+    #[account(mut)]
+    pub maker: UncheckedAccount<'info_sb>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_sb, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"ledger".as_ref(), b"reserve".as_ref()],
+        bump = reserve.bump,
+    )]
+    pub reserve: Account<'info_sb, TreasuryAccount>,    // Subsidies are lent out of funds held outside of any market
+    #[account(
+        init_if_needed,
+        space = 8 + TreasuryAccount::INIT_SPACE,
+        payer = payer,
+        seeds = [b"ledger", caller.key().as_ref(), facet.as_bytes(), round.to_le_bytes().as_ref(), LedgerPurpose::Subsidy.seed().as_ref()],
+        bump,
+    )]
+    pub subsidy_ledger: Account<'info_sb, TreasuryAccount>,
+    pub system_program: Program<'info_sb, System>,
+}
+
+impl<'info_sb> Subsidise<'info_sb> {
+
+    pub fn fund_subsidy(
+        &mut self,
+        bumps: &SubsidiseBumps,
+        market: MarketSigner,
+        round: u16,
+        facet: String,
+        amount: u64,
+    ) -> Result<()> {
+
+        let treasury: AccountInfo<'info_sb> = self.treasury.to_account_info();
+        let rent: u64 = Rent::get()?.minimum_balance(treasury.data_len());
+
+        // Requirements:                                                    |   Implemented:
+        //  - Caller should be the PDA of the market of the token           |       √
+        //  - Signer should be the treasury authority                       |       √
+        //  - A multisig treasury should have approved the subsidy          |       √
+        //  - Facet name should fit on the ledger account                   |       √
+        //  - Treasury should hold the amount above its rent                |       √
+        require!(is_market_pda(&self.caller, &market), TransactionError::CallerNotMarket);
+        require!(self.authority.key() == self.treasury.authority, TransactionError::SignerNotAuthority);
+        require!(facet.len() <= MAX_FACET_LENGTH.into(), LedgerError::FacetTooLong);
+        require!(treasury.lamports() >= rent + amount, TransactionError::InsufficientFunds);
+
+        self.treasury.draw_subsidy_budget(amount)?;
+
+        if self.subsidy_ledger.is_new() {
+            self.subsidy_ledger.set_inner(
+                TreasuryAccount {
+                    bump: bumps.subsidy_ledger,         // u8
+                    market: self.caller.key(),          // Pubkey
+                    facet,                              // String
+                    round,                              // u16
+                    purpose: LedgerPurpose::Subsidy,    // LedgerPurpose
+                    balance: 0_u64,                     // u64
+                    claims: 0_u64,                      // u64
+                    claimed: 0_u64,                     // u64
+                }
+            );
+        }
+
+        // The subsidy leaves the reserve but stays on record against the round until it is swept back
+        self.reserve.debit(&mut self.treasury, amount)?;
+        self.subsidy_ledger.credit(&mut self.treasury, amount);
+
+        // The treasury is owned by this program, so its lamports can be moved directly
+        **treasury.try_borrow_mut_lamports()? -= amount;
+        **self.maker.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        Ok(())
+
+    }
+
+    // The market program moves the surplus out of the maker's pool before calling, since only it can debit the pool
+    pub fn return_subsidy(
+        &mut self,
+        market: MarketSigner,
+        amount: u64,
+    ) -> Result<()> {
+
+        let lent: u64 = self.subsidy_ledger.balance;
+
+        // Requirements:                                                    |   Implemented:
+        //  - Caller should be the PDA of the market of the token           |       √
+        //  - Signer should be the treasury authority                       |       √
+        //  - The subsidy should not have been swept already                |       √
+        require!(is_market_pda(&self.caller, &market), TransactionError::CallerNotMarket);
+        require!(self.authority.key() == self.treasury.authority, TransactionError::SignerNotAuthority);
+        require!(!self.subsidy_ledger.is_new() && lent > 0, LedgerError::InsufficientBalance);
+
+        // Whatever the maker made or lost on the subsidy is settled against the reserve
+        self.subsidy_ledger.debit(&mut self.treasury, lent)?;
+        self.reserve.credit(&mut self.treasury, amount);
+
+        msg!("Subsidy of {} lamports returned {} lamports to the reserve", lent, amount);
+
+        Ok(())
+
+    }
+
+}
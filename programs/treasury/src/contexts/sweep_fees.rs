@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{LedgerPurpose, Treasury, TreasuryAccount};
+
+#[derive(Accounts)]
+#[instruction(market: Pubkey, round: u16, facet: String)]
+pub struct SweepFees<'info_sf> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_sf, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"ledger", market.as_ref(), facet.as_bytes(), round.to_le_bytes().as_ref(), LedgerPurpose::Fees.seed().as_ref()],
+        bump = fee_ledger.bump,
+    )]
+    pub fee_ledger: Account<'info_sf, TreasuryAccount>,
+    #[account(
+        mut,
+        seeds = [b"ledger".as_ref(), b"fees".as_ref()],
+        bump = vault_ledger.bump,
+    )]
+    pub vault_ledger: Account<'info_sf, TreasuryAccount>,
+}
+
+impl<'info_sf> SweepFees<'info_sf> {
+
+    // Anyone can sweep, since the lamports stay in the treasury and only move between fee ledgers
+    pub fn sweep_fees(&mut self) -> Result<u64> {
+
+        let amount: u64 = self.fee_ledger.balance;
+
+        self.fee_ledger.debit(&mut self.treasury, amount)?;
+        self.vault_ledger.credit(&mut self.treasury, amount);
+
+        Ok(amount)
+
+    }
+
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{LedgerPurpose, Treasury, TreasuryAccount};
+
+#[derive(Accounts)]
+pub struct SyncReserve<'info_sr> {
+    #[account(mut)]
+    pub payer: Signer<'info_sr>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_sr, Treasury>,
+    #[account(
+        init_if_needed,
+        space = 8 + TreasuryAccount::INIT_SPACE,
+        payer = payer,
+        seeds = [b"ledger".as_ref(), b"reserve".as_ref()],
+        bump,
+    )]
+    pub reserve: Account<'info_sr, TreasuryAccount>,   // Ledger account for funds held outside of any market
+    pub system_program: Program<'info_sr, System>,
+}
+
+impl<'info_sr> SyncReserve<'info_sr> {
+
+    // Anyone can sync, since the lamports are already in the treasury and are only recorded as its own
+    pub fn sync_reserve(
+        &mut self,
+        bumps: &SyncReserveBumps,
+    ) -> Result<u64> {
+
+        let treasury: AccountInfo<'info_sr> = self.treasury.to_account_info();
+        let rent: u64 = Rent::get()?.minimum_balance(treasury.data_len());
+
+        let amount: u64 = self.treasury.unrecorded(treasury.lamports().saturating_sub(rent))?;
+
+        if self.reserve.is_new() {
+            self.reserve.set_inner(
+                TreasuryAccount {
+                    bump: bumps.reserve,                // u8
+                    market: Pubkey::default(),          // Pubkey
+                    facet: String::new(),               // String
+                    round: 0_u16,                       // u16
+                    purpose: LedgerPurpose::Reserve,    // LedgerPurpose
+                    balance: 0_u64,                     // u64
+                    claims: 0_u64,                      // u64
+                    claimed: 0_u64,                     // u64
+                }
+            );
+        }
+
+        self.reserve.credit(&mut self.treasury, amount);
+
+        msg!("Recorded {} lamports sent straight to the treasury in the reserve", amount);
+
+        Ok(amount)
+
+    }
+
+}
@@ -8,7 +8,7 @@ use anchor_spl::{
     token::TokenAccount
 };
 
-use crate::states::Treasury;
+use crate::states::{LedgerPurpose, Treasury, TreasuryAccount};
 use crate::constants::{VOTING_TOKENS_MINT_ID, VOTING_TOKENS_PROGRAM_ID};
 use crate::error::TransactionError;
 
@@ -24,6 +24,14 @@ pub struct Transact<'info_t> {
         bump,
     )]
     pub treasury: Account<'info_t, Treasury>,
+    #[account(
+        init_if_needed,
+        space = 8 + TreasuryAccount::INIT_SPACE,
+        payer = signer,
+        seeds = [b"ledger".as_ref(), b"reserve".as_ref()],
+        bump,
+    )]
+    pub reserve: Account<'info_t, TreasuryAccount>,             // Ledger account for funds held outside of any market
     #[account(mut)]
     pub voting_token_account: Account<'info_t, TokenAccount>,   // This should already be initialised
     pub system_program: Program<'info_t, System>,
@@ -34,6 +42,7 @@ impl<'info_t> Transact<'info_t> {
     
     pub fn deposit(
         &mut self,
+        bumps: &TransactBumps,
         amount: u64,
     ) -> Result<()> {
 
@@ -56,14 +65,32 @@ impl<'info_t> Transact<'info_t> {
             self.treasury.to_account_info(),
             amount,
         )?;
+
+        if self.reserve.is_new() {
+            self.reserve.set_inner(
+                TreasuryAccount {
+                    bump: bumps.reserve,                // u8
+                    market: Pubkey::default(),          // Pubkey
+                    facet: String::new(),               // String
+                    round: 0_u16,                       // u16
+                    purpose: LedgerPurpose::Reserve,    // LedgerPurpose
+                    balance: 0_u64,                     // u64
+                    claims: 0_u64,                      // u64
+                    claimed: 0_u64,                     // u64
+                }
+            );
+        }
+
+        self.reserve.credit(&mut self.treasury, amount);
         
         Ok(())
 
     }
     
-    pub fn reimburse(
+    pub fn reimburse_with_reason(
         &mut self,
         amount: u64,
+        reason: u16,
     ) -> Result<()> {
 
         let mint_pk: Pubkey = Pubkey::from_str(VOTING_TOKENS_MINT_ID).unwrap();
@@ -74,18 +101,27 @@ impl<'info_t> Transact<'info_t> {
              &mint_program_pk,
         );
 
+        let treasury: AccountInfo<'info_t> = self.treasury.to_account_info();
+        let rent: u64 = Rent::get()?.minimum_balance(treasury.data_len());
+
         // Requirements:                                                            |   Implemented
         //  - Signer should be the treasury authority                               |       √
+        //  - Treasury should not be controlled by a multisig                       |       √
         //  - voting_token_account should be derivable as signer voting token ATA   |       √
+        //  - Treasury should hold the amount above its rent                        |       √
         require!(self.signer.key() == self.treasury.authority, TransactionError::SignerNotAuthority);
+        require!(self.treasury.multisig.is_none(), TransactionError::MultisigControlled);
         require!(self.voting_token_account.key() == signer_ata, TransactionError::WrongATA);
+        require!(treasury.lamports() >= rent + amount, TransactionError::InsufficientFunds);
 
-        // Make withdrawal here
-        self.transfer_sol(
-            self.treasury.to_account_info(),
-            self.coparty.to_account_info(),
-            amount,
-        )?;
+        // Payouts outside of a market round are recorded against a reason code for reconciliation
+        msg!("Reimbursing {} lamports to {} with reason code {}", amount, self.coparty.key(), reason);
+
+        self.reserve.debit(&mut self.treasury, amount)?;
+
+        // The treasury is owned by this program, so its lamports can be moved directly
+        **treasury.try_borrow_mut_lamports()? -= amount;
+        **self.coparty.to_account_info().try_borrow_mut_lamports()? += amount;
         
         Ok(())
 
@@ -97,12 +133,6 @@ impl<'info_t> Transact<'info_t> {
 
     }
     
-    pub fn get_voting_token_balance(&mut self) -> Result<u64> {
-        
-        Ok(self.voting_token_account.amount)
-
-    }
-
     fn transfer_sol(
         &self,
         from: AccountInfo<'info_t>,
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{burn, transfer_checked, Burn, Mint, Token, TokenAccount, TransferChecked}
+};
+
+use crate::states::{MarketSigner, Treasury};
+use crate::constants::VOTING_TOKENS_MINT_ID;
+use crate::error::{CustodyError, InitError};
+use crate::contexts::is_market_pda;
+
+#[derive(Accounts)]
+pub struct VotingTokenCustody<'info_vt> {
+    pub signer: Signer<'info_vt>,   // Treasury authority or a market PDA, or any owner depositing tokens
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_vt, Treasury>,
+    #[account(mut)]
+    pub mint: Account<'info_vt, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+    )]
+    pub treasury_voting_token_account: Account<'info_vt, TokenAccount>,    // Created at initialise and owned by the treasury PDA
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub voting_token_account: Option<Account<'info_vt, TokenAccount>>,     // Source of a deposit or destination of a return
+    pub token_program: Program<'info_vt, Token>,
+    pub associated_token_program: Program<'info_vt, AssociatedToken>,
+}
+
+impl<'info_vt> VotingTokenCustody<'info_vt> {
+
+    pub fn deposit_voting_tokens(&mut self, amount: u64) -> Result<()> {
+
+        let from: AccountInfo<'info_vt> = match self.voting_token_account.as_ref() {
+            Some(from) => from.to_account_info(),
+            None => return err!(CustodyError::TokenAccountMissing),
+        };
+
+        // Requirements:                                                    |   Implemented:
+        //  - The mint must be the expected account                         |       √
+        //  - Amount should be non-zero                                     |       √
+        require!(self.mint.key().to_string() == VOTING_TOKENS_MINT_ID, InitError::WrongTokenMint);
+        require!(amount > 0, CustodyError::ZeroAmount);
+
+        // The signer must own the source account, which the token program checks
+        let accounts = TransferChecked {
+            from,
+            mint: self.mint.to_account_info(),
+            to: self.treasury_voting_token_account.to_account_info(),
+            authority: self.signer.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), accounts);
+
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)
+
+    }
+
+    pub fn return_voting_tokens(&mut self, market: Option<MarketSigner>, amount: u64) -> Result<()> {
+
+        let to: AccountInfo<'info_vt> = match self.voting_token_account.as_ref() {
+            Some(to) => to.to_account_info(),
+            None => return err!(CustodyError::TokenAccountMissing),
+        };
+
+        // Requirements:                                                    |   Implemented:
+        //  - The mint must be the expected account                         |       √
+        //  - Signer should be the authority or the market PDA of the token |       √
+        //  - Amount should be non-zero                                     |       √
+        require!(self.mint.key().to_string() == VOTING_TOKENS_MINT_ID, InitError::WrongTokenMint);
+        require!(self.can_release(market), CustodyError::SignerNotAllowed);
+        require!(amount > 0, CustodyError::ZeroAmount);
+
+        let seeds: &[&[u8]] = &[b"treasury", &[self.treasury.bump]];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let accounts = TransferChecked {
+            from: self.treasury_voting_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to,
+            authority: self.treasury.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)
+
+    }
+
+    pub fn burn_voting_tokens(&mut self, market: Option<MarketSigner>, amount: u64) -> Result<()> {
+
+        // Requirements:                                                    |   Implemented:
+        //  - The mint must be the expected account                         |       √
+        //  - Signer should be the authority or the market PDA of the token |       √
+        //  - Amount should be non-zero                                     |       √
+        require!(self.mint.key().to_string() == VOTING_TOKENS_MINT_ID, InitError::WrongTokenMint);
+        require!(self.can_release(market), CustodyError::SignerNotAllowed);
+        require!(amount > 0, CustodyError::ZeroAmount);
+
+        let seeds: &[&[u8]] = &[b"treasury", &[self.treasury.bump]];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let accounts = Burn {
+            mint: self.mint.to_account_info(),
+            from: self.treasury_voting_token_account.to_account_info(),
+            authority: self.treasury.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer_seeds);
+
+        burn(cpi_ctx, amount)
+
+    }
+
+    pub fn get_voting_token_balance(&mut self) -> Result<u64> {
+
+        Ok(self.treasury_voting_token_account.amount)
+
+    }
+
+    // A multisig treasury's authority cannot move tokens alone, but markets can still settle votes
+    fn can_release(&self, market: Option<MarketSigner>) -> bool {
+        match market {
+            Some(market) => is_market_pda(&self.signer, &market),
+            None => self.signer.key() == self.treasury.authority && self.treasury.multisig.is_none(),
+        }
+    }
+
+}
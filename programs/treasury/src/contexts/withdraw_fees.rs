@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{FeeVault, FeeWithdrawal, Treasury, TreasuryAccount};
+use crate::error::{FeeError, LedgerError, TransactionError};
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info_wf> {
+    pub signer: Signer<'info_wf>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info_wf, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = fee_vault.bump,
+    )]
+    pub fee_vault: Account<'info_wf, FeeVault>,
+    #[account(
+        mut,
+        seeds = [b"ledger".as_ref(), b"fees".as_ref()],
+        bump = vault_ledger.bump,
+    )]
+    pub vault_ledger: Account<'info_wf, TreasuryAccount>,
+    /// CHECK: Only receives lamports, and must match the destination of the pending withdrawal
+    #[account(mut)]
+    pub destination: Option<UncheckedAccount<'info_wf>>,   // Only required to execute a withdrawal
+}
+
+impl<'info_wf> WithdrawFees<'info_wf> {
+
+    pub fn schedule_fee_withdrawal(
+        &mut self,
+        destination: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+
+        // Requirements:                                                    |   Implemented:
+        //  - Signer should be the treasury authority                       |       √
+        //  - Treasury should not be controlled by a multisig               |       √
+        require!(self.signer.key() == self.treasury.authority, TransactionError::SignerNotAuthority);
+        require!(self.treasury.multisig.is_none(), FeeError::MultisigControlled);
+
+        schedule_withdrawal(&mut self.fee_vault, &self.vault_ledger, destination, amount)
+
+    }
+
+    // Anyone can execute once the delay has passed, since the destination and amount are already fixed
+    pub fn execute_fee_withdrawal(&mut self) -> Result<()> {
+
+        let time: i64 = Clock::get()?.unix_timestamp;
+
+        let withdrawal: FeeWithdrawal = match self.fee_vault.pending.clone() {
+            Some(withdrawal) => withdrawal,
+            None => return err!(FeeError::NoPendingWithdrawal),
+        };
+
+        let to: AccountInfo<'info_wf> = match self.destination.as_ref() {
+            Some(to) if to.key() == withdrawal.destination => to.to_account_info(),
+            _ => return err!(FeeError::WrongDestination),
+        };
+
+        let treasury: AccountInfo<'info_wf> = self.treasury.to_account_info();
+        let rent: u64 = Rent::get()?.minimum_balance(treasury.data_len());
+
+        // Requirements:                                                    |   Implemented:
+        //  - There should be a pending withdrawal                          |       √
+        //  - Destination should match the pending withdrawal               |       √
+        //  - Delay should have passed                                      |       √
+        //  - Treasury should hold the amount above its rent                |       √
+        require!(withdrawal.executable_after <= time, FeeError::DelayNotPassed);
+        require!(treasury.lamports() >= rent + withdrawal.amount, TransactionError::InsufficientFunds);
+
+        self.fee_vault.pending = None;
+        self.vault_ledger.debit(&mut self.treasury, withdrawal.amount)?;
+
+        // The treasury is owned by this program, so its lamports can be moved directly
+        **treasury.try_borrow_mut_lamports()? -= withdrawal.amount;
+        **to.try_borrow_mut_lamports()? += withdrawal.amount;
+
+        msg!("Fee withdrawal of {} lamports to {} executed", withdrawal.amount, withdrawal.destination);
+
+        Ok(())
+
+    }
+
+    pub fn cancel_fee_withdrawal(&mut self) -> Result<()> {
+
+        // Requirements:                                                    |   Implemented:
+        //  - Signer should be the treasury authority                       |       √
+        //  - Treasury should not be controlled by a multisig               |       √
+        //  - There should be a pending withdrawal                          |       √
+        require!(self.signer.key() == self.treasury.authority, TransactionError::SignerNotAuthority);
+        require!(self.treasury.multisig.is_none(), FeeError::MultisigControlled);
+        require!(self.fee_vault.pending.is_some(), FeeError::NoPendingWithdrawal);
+
+        self.fee_vault.pending = None;
+
+        msg!("Pending fee withdrawal cancelled");
+
+        Ok(())
+
+    }
+
+}
+
+// A multisig treasury schedules fee withdrawals through a proposal, which is held to the same delay
+pub fn schedule_withdrawal(
+    fee_vault: &mut FeeVault,
+    vault_ledger: &TreasuryAccount,
+    destination: Pubkey,
+    amount: u64,
+) -> Result<()> {
+
+    let executable_after: i64 = Clock::get()?.unix_timestamp + fee_vault.delay;
+
+    // Requirements:                                                    |   Implemented:
+    //  - No withdrawal should be pending                               |       √
+    //  - Destination should be on the allowlist                        |       √
+    //  - Fee vault should hold the amount                              |       √
+    require!(fee_vault.pending.is_none(), FeeError::WithdrawalPending);
+    require!(fee_vault.destinations.contains(&destination), FeeError::DestinationNotAllowed);
+    require!(vault_ledger.balance >= amount, LedgerError::InsufficientBalance);
+
+    fee_vault.pending = Some(
+        FeeWithdrawal {
+            destination,                // Pubkey
+            amount,                     // u64
+            executable_after,           // i64
+        }
+    );
+
+    // Logged so that anyone watching the treasury can review the withdrawal before it executes
+    msg!("Fee withdrawal of {} lamports to {} scheduled for {}", amount, destination, executable_after);
+
+    Ok(())
+
+}
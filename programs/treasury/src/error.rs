@@ -17,4 +17,154 @@ pub enum TransactionError {
     #[msg("The ATA supplied is not that of the Treasury")]
     WrongATA,
 
+    #[msg("Only the market program can accrue and draw on payables")]
+    CallerNotMarket,
+
+    #[msg("The payout is more than the round has left to pay")]
+    PayableExceeded,
+
+    #[msg("The treasury does not hold enough SOL above its rent to make the payout")]
+    InsufficientFunds,
+
+    #[msg("Direct reimbursements from a multisig treasury need a proposal")]
+    MultisigControlled,
+
+    #[msg("Lamports with this purpose cannot be moved by this transaction")]
+    WrongPurpose,
+
+    #[msg("A multisig treasury can only lend subsidies out of the budget its signers have approved")]
+    SubsidyBudgetExceeded,
+
+}
+
+#[error_code]
+pub enum AuthorityError {
+
+    #[msg("The authority of a multisig treasury can only be changed by a proposal")]
+    MultisigControlled,
+
+    #[msg("The timelock must be between zero and the max allowed (30 days)")]
+    InvalidTimelock,
+
+    #[msg("There is no pending authority to accept or cancel")]
+    NoPendingAuthority,
+
+    #[msg("The signer is not the pending authority")]
+    SignerNotPendingAuthority,
+
+    #[msg("The timelock on the pending authority has not yet passed")]
+    TimelockNotPassed,
+
+}
+
+#[error_code]
+pub enum LedgerError {
+
+    #[msg("The ledger account does not hold enough to cover the debit")]
+    InsufficientBalance,
+
+    #[msg("The treasury's lamports above rent do not match its ledger balances")]
+    LedgerMismatch,
+
+    #[msg("The facet name is too long for a ledger account")]
+    FacetTooLong,
+
+    #[msg("The treasury's totals are too large to work out its free balance")]
+    Overflow,
+
+}
+
+#[error_code]
+pub enum FeeError {
+
+    #[msg("Fees of a multisig treasury cannot be withdrawn by the authority alone")]
+    MultisigControlled,
+
+    #[msg("The delay must be between the min (1 day) and max (30 days) allowed")]
+    InvalidDelay,
+
+    #[msg("There can be between 1 and the max number of distinct fee destinations")]
+    InvalidDestinations,
+
+    #[msg("The destination is not on the fee vault's allowlist")]
+    DestinationNotAllowed,
+
+    #[msg("A fee withdrawal is already pending")]
+    WithdrawalPending,
+
+    #[msg("There is no pending fee withdrawal to execute or cancel")]
+    NoPendingWithdrawal,
+
+    #[msg("The delay on the pending fee withdrawal has not yet passed")]
+    DelayNotPassed,
+
+    #[msg("The destination supplied is not the one in the pending withdrawal")]
+    WrongDestination,
+
+    #[msg("A fee vault configuration is already pending")]
+    ConfigPending,
+
+    #[msg("There is no pending fee vault configuration to apply or cancel")]
+    NoPendingConfig,
+
+}
+
+#[error_code]
+pub enum CustodyError {
+
+    #[msg("Only the treasury authority or a market can release voting tokens from the treasury")]
+    SignerNotAllowed,
+
+    #[msg("A voting token account must be supplied to deposit or return voting tokens")]
+    TokenAccountMissing,
+
+    #[msg("The amount of voting tokens must be non-zero")]
+    ZeroAmount,
+
+}
+
+#[error_code]
+pub enum IouError {
+
+    #[msg("The account supplied is not an IOU of the treasury")]
+    NotAnIou,
+
+    #[msg("The amount is more than the IOU is owed")]
+    ExceedsIou,
+
+}
+
+#[error_code]
+pub enum MultisigError {
+
+    #[msg("The treasury is already controlled by a multisig")]
+    MultisigAlreadySet,
+
+    #[msg("The treasury is not controlled by a multisig")]
+    MultisigNotSet,
+
+    #[msg("The multisig needs between 1 and the max number of distinct signers, and a threshold no larger than its signers")]
+    InvalidMultisig,
+
+    #[msg("The signer is not one of the multisig signers")]
+    NotASigner,
+
+    #[msg("The signer has already approved this proposal")]
+    AlreadyApproved,
+
+    #[msg("The proposal has already been executed")]
+    AlreadyExecuted,
+
+    #[msg("The proposal does not have enough approvals to be executed")]
+    NotEnoughApprovals,
+
+    #[msg("The recipient supplied is not the one in the proposal")]
+    WrongRecipient,
+
+    #[msg("The treasury does not hold enough SOL above its rent to make the withdrawal")]
+    InsufficientFunds,
+
+    #[msg("An account needed to carry out the proposal was not supplied")]
+    AccountsMissing,
+
 }
@@ -29,16 +29,108 @@ pub mod treasury_program {
         amount: u64,
     ) -> Result<()> {
 
-        ctx.accounts.deposit(amount)
+        ctx.accounts.deposit(&ctx.bumps, amount)
 
     }
 
     pub fn reimburse(
+        ctx: Context<Reimburse>,
+        market: MarketSigner,
+        round: u16,
+        _facet: String,
+        purpose: LedgerPurpose,
+        amount: u64,
+        claims: u64,
+    ) -> Result<()> {
+
+        ctx.accounts.reimburse(&ctx.bumps, market, round, purpose, amount, claims)
+
+    }
+
+    pub fn reimburse_with_reason(
         ctx: Context<Transact>,
         amount: u64,
+        reason: u16,
     ) -> Result<()> {
 
-        ctx.accounts.reimburse(amount)
+        ctx.accounts.reimburse_with_reason(amount, reason)
+
+    }
+
+    pub fn accrue(
+        ctx: Context<Accrue>,
+        market: MarketSigner,
+        round: u16,
+        facet: String,
+        purpose: LedgerPurpose,
+        amount: u64,
+    ) -> Result<()> {
+
+        ctx.accounts.accrue(&ctx.bumps, market, round, facet, purpose, amount)
+
+    }
+
+    pub fn collect_fee(
+        ctx: Context<CollectFee>,
+        market: MarketSigner,
+        round: u16,
+        facet: String,
+        purpose: LedgerPurpose,
+        amount: u64,
+    ) -> Result<()> {
+
+        ctx.accounts.collect_fee(&ctx.bumps, market, round, facet, purpose, amount)
+
+    }
+
+    pub fn fund_subsidy(
+        ctx: Context<Subsidise>,
+        market: MarketSigner,
+        round: u16,
+        facet: String,
+        amount: u64,
+    ) -> Result<()> {
+
+        ctx.accounts.fund_subsidy(&ctx.bumps, market, round, facet, amount)
+
+    }
+
+    pub fn return_subsidy(
+        ctx: Context<Subsidise>,
+        market: MarketSigner,
+        _round: u16,
+        _facet: String,
+        amount: u64,
+    ) -> Result<()> {
+
+        ctx.accounts.return_subsidy(market, amount)
+
+    }
+
+    pub fn reconcile(ctx: Context<Reconcile>) -> Result<u64> {
+
+        ctx.accounts.reconcile()
+
+    }
+
+    pub fn sync_reserve(ctx: Context<SyncReserve>) -> Result<u64> {
+
+        ctx.accounts.sync_reserve(&ctx.bumps)
+
+    }
+
+    pub fn solvency(ctx: Context<Solvency>) -> Result<i64> {
+
+        ctx.accounts.solvency()
+
+    }
+
+    pub fn settle_iou(
+        ctx: Context<SettleIou>,
+        amount: u64,
+    ) -> Result<()> {
+
+        ctx.accounts.settle_iou(amount)
 
     }
 
@@ -51,11 +143,147 @@ pub mod treasury_program {
     }
 
     pub fn get_voting_token_balance(
-        ctx: Context<Transact>,
+        ctx: Context<VotingTokenCustody>,
     ) -> Result<u64> {
 
         ctx.accounts.get_voting_token_balance()
 
     }
 
+    pub fn set_multisig(
+        ctx: Context<SetMultisig>,
+        multisig: Multisig,
+    ) -> Result<()> {
+
+        ctx.accounts.set_multisig(multisig)
+
+    }
+
+    pub fn propose(
+        ctx: Context<Propose>,
+        action: ProposalAction,
+    ) -> Result<()> {
+
+        ctx.accounts.propose(&ctx.bumps, action)
+
+    }
+
+    pub fn approve(ctx: Context<Approve>) -> Result<()> {
+
+        ctx.accounts.approve()
+
+    }
+
+    pub fn execute(ctx: Context<Execute>) -> Result<()> {
+
+        ctx.accounts.execute()
+
+    }
+
+    pub fn propose_authority(
+        ctx: Context<RotateAuthority>,
+        authority: Pubkey,
+        timelock: i64,
+    ) -> Result<()> {
+
+        ctx.accounts.propose_authority(authority, timelock)
+
+    }
+
+    pub fn accept_authority(ctx: Context<RotateAuthority>) -> Result<()> {
+
+        ctx.accounts.accept_authority()
+
+    }
+
+    pub fn cancel_authority(ctx: Context<RotateAuthority>) -> Result<()> {
+
+        ctx.accounts.cancel_authority()
+
+    }
+
+    pub fn configure_fee_vault(
+        ctx: Context<ConfigureFeeVault>,
+        delay: i64,
+        destinations: Vec<Pubkey>,
+    ) -> Result<()> {
+
+        ctx.accounts.configure_fee_vault(&ctx.bumps, delay, destinations)
+
+    }
+
+    pub fn apply_fee_vault_config(ctx: Context<ConfigureFeeVault>) -> Result<()> {
+
+        ctx.accounts.apply_fee_vault_config()
+
+    }
+
+    pub fn cancel_fee_vault_config(ctx: Context<ConfigureFeeVault>) -> Result<()> {
+
+        ctx.accounts.cancel_fee_vault_config()
+
+    }
+
+    pub fn sweep_fees(
+        ctx: Context<SweepFees>,
+        _market: Pubkey,
+        _round: u16,
+        _facet: String,
+    ) -> Result<u64> {
+
+        ctx.accounts.sweep_fees()
+
+    }
+
+    pub fn schedule_fee_withdrawal(
+        ctx: Context<WithdrawFees>,
+        destination: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+
+        ctx.accounts.schedule_fee_withdrawal(destination, amount)
+
+    }
+
+    pub fn execute_fee_withdrawal(ctx: Context<WithdrawFees>) -> Result<()> {
+
+        ctx.accounts.execute_fee_withdrawal()
+
+    }
+
+    pub fn cancel_fee_withdrawal(ctx: Context<WithdrawFees>) -> Result<()> {
+
+        ctx.accounts.cancel_fee_withdrawal()
+
+    }
+
+    pub fn deposit_voting_tokens(
+        ctx: Context<VotingTokenCustody>,
+        amount: u64,
+    ) -> Result<()> {
+
+        ctx.accounts.deposit_voting_tokens(amount)
+
+    }
+
+    pub fn return_voting_tokens(
+        ctx: Context<VotingTokenCustody>,
+        market: Option<MarketSigner>,
+        amount: u64,
+    ) -> Result<()> {
+
+        ctx.accounts.return_voting_tokens(market, amount)
+
+    }
+
+    pub fn burn_voting_tokens(
+        ctx: Context<VotingTokenCustody>,
+        market: Option<MarketSigner>,
+        amount: u64,
+    ) -> Result<()> {
+
+        ctx.accounts.burn_voting_tokens(market, amount)
+
+    }
+
 }
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::{borsh::{BorshSerialize, BorshDeserialize}, *};
+
+use crate::constants::MAX_FEE_DESTINATIONS;
+
+#[account]
+#[derive(InitSpace)]
+pub struct FeeVault {
+    pub bump: u8,                               // Bump
+    pub delay: i64,                             // Wait between scheduling a fee withdrawal and executing it (s)
+    #[max_len(MAX_FEE_DESTINATIONS)]
+    pub destinations: Vec<Pubkey>,              // Only keys to which fees can be withdrawn
+    pub pending: Option<FeeWithdrawal>,         // Withdrawal waiting on its delay, of which there is at most one
+    pub pending_config: Option<FeeVaultConfig>, // New delay and allowlist waiting on the current delay
+}
+
+impl FeeVault {
+    // Accounts created by init_if_needed are zeroed, and a canonical bump is never zero in practice
+    pub fn is_new(&self) -> bool {
+        self.bump == 0
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, InitSpace, PartialEq)]
+pub struct FeeWithdrawal {
+    pub destination: Pubkey,    // Key which receives the fees
+    pub amount: u64,            // Lamports to withdraw from the fee vault
+    pub executable_after: i64,  // Time from which the withdrawal can be executed (s)
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, InitSpace, PartialEq)]
+pub struct FeeVaultConfig {
+    pub delay: i64,                 // Delay which will apply to later withdrawals (s)
+    #[max_len(MAX_FEE_DESTINATIONS)]
+    pub destinations: Vec<Pubkey>,  // Allowlist which will replace the current one
+    pub executable_after: i64,      // Time from which the configuration can be applied (s)
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Iou {
+    pub bump: u8,           // Bump
+    pub caller: Pubkey,     // Market program PDA of the round which could not pay the claim in full
+    pub round: u16,         // Round of the market to which the claim belongs
+    pub coparty: Pubkey,    // Key to which the remainder is owed
+    pub amount: u64,        // Lamports still owed
+}
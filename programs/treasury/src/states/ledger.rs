@@ -0,0 +1,173 @@
+use anchor_lang::prelude::{borsh::{BorshSerialize, BorshDeserialize}, *};
+
+use crate::states::Treasury;
+use crate::constants::MAX_FACET_LENGTH;
+use crate::error::LedgerError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct TreasuryAccount {
+    pub bump: u8,                   // Bump
+    pub market: Pubkey,             // Market PDA to which the lamports belong (default for the reserve and fee vault)
+    #[max_len(MAX_FACET_LENGTH)]
+    pub facet: String,              // Facet of the market to which the lamports belong
+    pub round: u16,                 // Round of the market to which the lamports belong
+    pub purpose: LedgerPurpose,     // What the lamports are held for
+    pub balance: u64,               // Lamports of the treasury's balance which belong to this account (or lent out, for a subsidy)
+    pub claims: u64,                // Total of the claims the round pays out of the ledger once it is resolved (0 until then)
+    pub claimed: u64,               // Claims settled against that total, whether paid out or owed by an IOU
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, InitSpace, PartialEq)]
+pub enum LedgerPurpose {
+    Stakes,     // SOL wagered in a round, owed to its bettors and voters
+    Bonds,      // Appeal bonds posted in a round
+    Fees,       // Fees kept by the treasury from a round, or swept into the fee vault
+    Reserve,    // Funds deposited by the authority outside of any market
+    Subsidy,    // Reserve funds lent to a round's market maker, which are not in the treasury until swept back
+}
+
+impl LedgerPurpose {
+    pub fn seed(&self) -> [u8; 1] {
+        [*self as u8]
+    }
+
+    // A subsidy is held by the market maker rather than the treasury
+    pub fn is_held(&self) -> bool {
+        *self != LedgerPurpose::Subsidy
+    }
+
+    // Stakes and bonds are owed back out to the rounds, while fees and the reserve are the treasury's own
+    pub fn is_owed(&self) -> bool {
+        *self == LedgerPurpose::Stakes || *self == LedgerPurpose::Bonds
+    }
+}
+
+impl TreasuryAccount {
+    // Accounts created by init_if_needed are zeroed, and a canonical bump is never zero in practice
+    pub fn is_new(&self) -> bool {
+        self.bump == 0
+    }
+
+    // Claims are paid in full while what is available covers every claim still outstanding, and in proportion
+    // otherwise. Working it out afresh on each claim means later claims gain from any top-up in between.
+    pub fn payout(&self, amount: u64, available: u64) -> u64 {
+        let outstanding: u64 = self.claims.saturating_sub(self.claimed).max(amount);
+
+        match available >= outstanding {
+            true => amount,
+            false => (u128::from(amount) * u128::from(available) / u128::from(outstanding)) as u64,
+        }
+    }
+
+    pub fn credit(&mut self, treasury: &mut Treasury, amount: u64) {
+        self.balance += amount;
+
+        treasury.add_to_totals(self.purpose, amount);
+    }
+
+    pub fn debit(&mut self, treasury: &mut Treasury, amount: u64) -> Result<()> {
+        require!(self.balance >= amount, LedgerError::InsufficientBalance);
+
+        self.balance -= amount;
+
+        treasury.remove_from_totals(self.purpose, amount);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn treasury() -> Treasury {
+        Treasury {
+            bump: 255,
+            authority: Pubkey::default(),
+            multisig: None,
+            proposal_count: 0,
+            pending_authority: None,
+            pending_after: 0,
+            ledger_total: 0,
+            owed_total: 0,
+            iou_total: 0,
+            subsidy_budget: 0,
+        }
+    }
+
+    fn ledger(purpose: LedgerPurpose) -> TreasuryAccount {
+        TreasuryAccount {
+            bump: 255,
+            market: Pubkey::default(),
+            facet: String::new(),
+            round: 1,
+            purpose,
+            balance: 0,
+            claims: 0,
+            claimed: 0,
+        }
+    }
+
+    #[test]
+    fn credit_and_debit_keep_the_treasury_totals() {
+        let mut treasury: Treasury = treasury();
+        let mut stakes: TreasuryAccount = ledger(LedgerPurpose::Stakes);
+        let mut fees: TreasuryAccount = ledger(LedgerPurpose::Fees);
+
+        stakes.credit(&mut treasury, 500);
+        fees.credit(&mut treasury, 200);
+        stakes.debit(&mut treasury, 300).unwrap();
+
+        assert_eq!(stakes.balance, 200);
+        assert_eq!(treasury.ledger_total, 400);
+        assert_eq!(treasury.owed_total, 200);
+    }
+
+    #[test]
+    fn subsidies_are_not_held_by_the_treasury() {
+        let mut treasury: Treasury = treasury();
+        let mut subsidy: TreasuryAccount = ledger(LedgerPurpose::Subsidy);
+
+        subsidy.credit(&mut treasury, 500);
+
+        assert_eq!(subsidy.balance, 500);
+        assert_eq!(treasury.ledger_total, 0);
+        assert_eq!(treasury.owed_total, 0);
+    }
+
+    #[test]
+    fn debit_rejects_more_than_the_balance() {
+        let mut treasury: Treasury = treasury();
+        let mut bonds: TreasuryAccount = ledger(LedgerPurpose::Bonds);
+
+        bonds.credit(&mut treasury, 100);
+
+        assert_eq!(bonds.debit(&mut treasury, 101), Err(LedgerError::InsufficientBalance.into()));
+        assert_eq!(bonds.balance, 100);
+        assert_eq!(treasury.ledger_total, 100);
+    }
+
+    #[test]
+    fn payout_shares_a_short_round_among_outstanding_claims() {
+        let mut stakes: TreasuryAccount = TreasuryAccount { claims: 1_000, ..ledger(LedgerPurpose::Stakes) };
+
+        assert_eq!(stakes.payout(400, 1_000), 400);
+        assert_eq!(stakes.payout(400, 500), 200);
+
+        // A top-up before the next claim is shared among the claims left
+        stakes.claimed = 400;
+
+        assert_eq!(stakes.payout(600, 300), 300);
+        assert_eq!(stakes.payout(600, 600), 600);
+    }
+
+    #[test]
+    fn payout_without_declared_claims_covers_what_is_available() {
+        let stakes: TreasuryAccount = ledger(LedgerPurpose::Stakes);
+
+        assert_eq!(stakes.payout(400, 1_000), 400);
+        assert_eq!(stakes.payout(400, 100), 100);
+        assert_eq!(stakes.payout(400, 0), 0);
+    }
+}
@@ -1,3 +1,13 @@
 pub mod treasury;
+pub mod proposal;
+pub mod payable;
+pub mod ledger;
+pub mod fee_vault;
+pub mod iou;
 
 pub use treasury::*;
+pub use proposal::*;
+pub use payable::*;
+pub use ledger::*;
+pub use fee_vault::*;
+pub use iou::*;
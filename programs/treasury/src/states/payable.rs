@@ -0,0 +1,25 @@
+use anchor_lang::prelude::{borsh::{BorshSerialize, BorshDeserialize}, *};
+
+// Token and bump of the market PDA calling the treasury, from which its address is rebuilt
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct MarketSigner {
+    pub token: Pubkey,            // Authensus token the market was initialised with
+    pub bump: u8,                 // Bump of the market PDA
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Payable {
+    pub bump: u8,                 // Bump
+    pub caller: Pubkey,           // Market program PDA which accrues and draws on the payable
+    pub round: u16,               // Round of the market to which the payable corresponds
+    pub owed: u64,                // Total amount the round has taken in which can be paid back out
+    pub paid: u64,                // Total amount paid out
+    pub deferred: u64,            // Total amount of claims owed by IOUs rather than paid out, since the round ran short
+}
+
+impl Payable {
+    pub fn remaining(&self) -> u64 {
+        self.owed.saturating_sub(self.paid).saturating_sub(self.deferred)
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::{borsh::{BorshSerialize, BorshDeserialize}, *};
+
+use super::Multisig;
+use crate::constants::{MAX_FEE_DESTINATIONS, MAX_MULTISIG_SIGNERS};
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub bump: u8,                   // Bump
+    pub id: u64,                    // Index of the proposal within the treasury
+    pub proposer: Pubkey,           // Multisig signer who made the proposal
+    pub action: ProposalAction,     // What the treasury will do once the proposal is executed
+    #[max_len(MAX_MULTISIG_SIGNERS)]
+    pub approvals: Vec<Pubkey>,     // Multisig signers who have approved the proposal
+    pub executed: bool,             // Whether the proposal has been carried out
+    pub created_at: i64,            // Time at which the proposal was made
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, InitSpace, PartialEq)]
+pub enum ProposalAction {
+    Withdraw {
+        recipient: Pubkey,              // Account to which the SOL is sent
+        amount: u64,                    // Lamports to withdraw from the treasury
+    },
+    SetAuthority {
+        authority: Pubkey,              // Key which the market program checks as the treasury authority
+        multisig: Option<Multisig>,     // New multisig (None hands control back to the single authority)
+    },
+    ScheduleFeeWithdrawal {
+        destination: Pubkey,            // Allowlisted key to which the fees are sent once the fee vault's delay has passed
+        amount: u64,                    // Lamports to withdraw from the fee vault
+    },
+    CancelFeeWithdrawal,
+    ConfigureFeeVault {
+        delay: i64,                     // Delay which will apply to later fee withdrawals (s)
+        #[max_len(MAX_FEE_DESTINATIONS)]
+        destinations: Vec<Pubkey>,      // Allowlist which will replace the current one once the fee vault's delay has passed
+    },
+    CancelFeeVaultConfig,
+    SettleIou {
+        iou: Pubkey,                    // IOU account to pay down out of the reserve
+        amount: u64,                    // Lamports to pay to the key the IOU is owed to
+    },
+    SetSubsidyBudget {
+        amount: u64,                    // Lamports of the reserve the authority can lend to market makers without a further proposal
+    },
+}
@@ -1,8 +1,165 @@
-use anchor_lang::prelude::*;
+use anchor_lang::prelude::{borsh::{BorshSerialize, BorshDeserialize}, *};
+
+use crate::states::LedgerPurpose;
+use crate::constants::MAX_MULTISIG_SIGNERS;
+use crate::error::{LedgerError, TransactionError};
 
 #[account]
 #[derive(InitSpace)]
 pub struct Treasury {
-    pub bump: u8,           // Bump
-    pub authority: Pubkey,  // Payer of the initialisation and transactions
+    pub bump: u8,                          // Bump
+    pub authority: Pubkey,                 // Payer of the initialisation and transactions
+    pub multisig: Option<Multisig>,        // Keys which must approve withdrawals and authority changes (None while a single key has control)
+    pub proposal_count: u64,               // Number of proposals made, used to derive the next proposal's address
+    pub pending_authority: Option<Pubkey>, // Key proposed to take over as authority, which must accept before it does
+    pub pending_after: i64,                // Time from which the pending authority can accept (s)
+    pub ledger_total: u64,                 // Lamports held across all ledger accounts, kept as they are credited and debited
+    pub owed_total: u64,                   // Lamports of stakes and bonds held across all ledger accounts, which are owed back to rounds
+    pub iou_total: u64,                    // Lamports owed by IOUs to claimants of rounds which ran short
+    pub subsidy_budget: u64,               // Lamports of the reserve which a multisig has approved for subsidising market makers
+}
+
+impl Treasury {
+    // Totals are kept as ledger balances change, so reconciling never needs every ledger account
+    pub fn add_to_totals(&mut self, purpose: LedgerPurpose, amount: u64) {
+        if purpose.is_held() {
+            self.ledger_total += amount;
+        }
+
+        if purpose.is_owed() {
+            self.owed_total += amount;
+        }
+    }
+
+    // Totals only ever fall by what a ledger held, so they cannot go below zero
+    pub fn remove_from_totals(&mut self, purpose: LedgerPurpose, amount: u64) {
+        if purpose.is_held() {
+            self.ledger_total -= amount;
+        }
+
+        if purpose.is_owed() {
+            self.owed_total -= amount;
+        }
+    }
+
+    // A multisig treasury's authority cannot lend alone, so subsidies come out of a budget the signers approved in advance
+    pub fn draw_subsidy_budget(&mut self, amount: u64) -> Result<()> {
+        if self.multisig.is_some() {
+            require!(amount <= self.subsidy_budget, TransactionError::SubsidyBudgetExceeded);
+
+            self.subsidy_budget -= amount;
+        }
+
+        Ok(())
+    }
+
+    // Every lamport the treasury moves goes through a ledger, so anything it holds above rent beyond them
+    // was sent straight to it. Holding less than the ledgers add up to means they no longer reconcile
+    pub fn unrecorded(&self, held: u64) -> Result<u64> {
+        held.checked_sub(self.ledger_total).ok_or(error!(LedgerError::LedgerMismatch))
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, InitSpace, PartialEq)]
+pub struct Multisig {
+    #[max_len(MAX_MULTISIG_SIGNERS)]
+    pub signers: Vec<Pubkey>,   // Keys which can propose and approve
+    pub threshold: u8,          // Number of approvals needed to execute a proposal
+}
+
+impl Multisig {
+    pub fn is_valid(&self) -> bool {
+        let distinct: bool = self.signers.iter().enumerate().all(|(i, signer)| !self.signers[..i].contains(signer));
+
+        distinct
+            && self.signers.len() <= MAX_MULTISIG_SIGNERS.into()
+            && self.threshold > 0
+            && usize::from(self.threshold) <= self.signers.len()
+    }
+
+    // Approvals only count while the approver is still a signer, so removed keys lose their say
+    pub fn approvals(&self, approvals: &[Pubkey]) -> usize {
+        approvals.iter().filter(|approver| self.signers.contains(approver)).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn multisig(signers: usize, threshold: u8) -> Multisig {
+        Multisig {
+            signers: (0..signers).map(|_| Pubkey::new_unique()).collect(),
+            threshold,
+        }
+    }
+
+    #[test]
+    fn multisig_is_valid_within_limits() {
+        assert!(multisig(3, 2).is_valid());
+        assert!(multisig(MAX_MULTISIG_SIGNERS.into(), MAX_MULTISIG_SIGNERS).is_valid());
+        assert!(!multisig(3, 0).is_valid());
+        assert!(!multisig(3, 4).is_valid());
+        assert!(!multisig(usize::from(MAX_MULTISIG_SIGNERS) + 1, 2).is_valid());
+    }
+
+    #[test]
+    fn multisig_rejects_repeated_signers() {
+        let mut repeated: Multisig = multisig(3, 2);
+        repeated.signers[2] = repeated.signers[0];
+
+        assert!(!repeated.is_valid());
+    }
+
+    fn treasury(multisig: Option<Multisig>, ledger_total: u64, subsidy_budget: u64) -> Treasury {
+        Treasury {
+            bump: 255,
+            authority: Pubkey::default(),
+            multisig,
+            proposal_count: 0,
+            pending_authority: None,
+            pending_after: 0,
+            ledger_total,
+            owed_total: 0,
+            iou_total: 0,
+            subsidy_budget,
+        }
+    }
+
+    #[test]
+    fn unrecorded_is_what_is_held_beyond_the_ledgers() {
+        let treasury: Treasury = treasury(None, 1_000, 0);
+
+        assert_eq!(treasury.unrecorded(1_000), Ok(0));
+        assert_eq!(treasury.unrecorded(1_250), Ok(250));
+        assert_eq!(treasury.unrecorded(999), Err(LedgerError::LedgerMismatch.into()));
+    }
+
+    #[test]
+    fn multisig_subsidies_draw_down_the_approved_budget() {
+        let mut controlled: Treasury = treasury(Some(multisig(3, 2)), 0, 1_000);
+
+        assert_eq!(controlled.draw_subsidy_budget(600), Ok(()));
+        assert_eq!(controlled.subsidy_budget, 400);
+        assert_eq!(controlled.draw_subsidy_budget(401), Err(TransactionError::SubsidyBudgetExceeded.into()));
+        assert_eq!(controlled.subsidy_budget, 400);
+    }
+
+    #[test]
+    fn single_authority_subsidies_need_no_budget() {
+        let mut single: Treasury = treasury(None, 0, 0);
+
+        assert_eq!(single.draw_subsidy_budget(1_000), Ok(()));
+        assert_eq!(single.subsidy_budget, 0);
+    }
+
+    #[test]
+    fn approvals_only_count_current_signers() {
+        let multisig: Multisig = multisig(3, 2);
+        let removed: Pubkey = Pubkey::new_unique();
+
+        assert_eq!(multisig.approvals(&[multisig.signers[0], multisig.signers[2]]), 2);
+        assert_eq!(multisig.approvals(&[multisig.signers[0], removed]), 1);
+        assert_eq!(multisig.approvals(&[]), 0);
+    }
 }
@@ -0,0 +1 @@
+pub const MARKET_PROGRAM_ID: &str = "H4jYJQJhPSy7ANZwDZDkvE4Q9x5oQDz1tKaB2GRjrDpY";         // Program whose market PDAs can mint on a recipient's behalf
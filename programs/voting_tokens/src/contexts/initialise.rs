@@ -54,9 +54,9 @@ impl<'info_i> Initialise<'info_i> {
         //  - Symbol is `AUTHVOTE`              |       √
         //  - URI should be empty               |       √
         //  - Decimals should be 9              |       √
-        require!(metadata.name == "AuthensusVotingToken".to_string(), InitError::WrongName);
-        require!(metadata.symbol == "AUTHVOTE".to_string(), InitError::WrongSymbol);
-        require!(metadata.uri == "".to_string(), InitError::WrongUri);
+        require!(metadata.name == "AuthensusVotingToken", InitError::WrongName);
+        require!(metadata.symbol == "AUTHVOTE", InitError::WrongSymbol);
+        require!(metadata.uri.is_empty(), InitError::WrongUri);
         require!(metadata.decimals == 9, InitError::WrongDecimals);
 
         let seeds: &[&[u8]; 2] = &["mint".as_bytes(), &[bumps.mint]];
@@ -0,0 +1,85 @@
+use std::str::FromStr;
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{mint_to, Mint, MintTo, Token, TokenAccount}
+};
+
+use crate::constants::MARKET_PROGRAM_ID;
+use crate::error::MintError;
+
+#[derive(Accounts)]
+pub struct MintTokensTo<'info_mt> {
+    #[account(mut)]
+    pub payer: Signer<'info_mt>,
+    pub caller: Signer<'info_mt>,   // Market PDA of the token passed in, which only the market program can sign for
+    /// CHECK: Only owns the recipient account, and need not sign
+    pub owner: UncheckedAccount<'info_mt>,
+    #[account(
+        mut,
+        seeds = [b"mint"],
+        bump,
+        mint::authority = mint,
+    )]
+    pub mint: Account<'info_mt, Mint>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub recipient: Account<'info_mt, TokenAccount>,
+    pub associated_token_program: Program<'info_mt, AssociatedToken>,
+    pub system_program: Program<'info_mt, System>,
+    pub token_program: Program<'info_mt, Token>,
+    pub rent: Sysvar<'info_mt, Rent>,
+}
+
+impl<'info_mt> MintTokensTo<'info_mt> {
+
+    // As mint_tokens, but the payer can mint into someone else's account, which lets keepers settle on their behalf.
+    // Only a market can do so, as it pays out winnings it has already worked out
+    pub fn mint_tokens_to(
+        &mut self,
+        bumps: &MintTokensToBumps,
+        token: Pubkey,
+        market_bump: u8,
+        amount: u64,
+    ) -> Result<()> {
+
+        // Requirements:                                                    |   Implemented:
+        //  - Caller should be the PDA of the market of the token           |       √
+        require!(is_market_pda(&self.caller, &token, market_bump), MintError::CallerNotMarket);
+
+        let seeds: &[&[u8]; 2] = &["mint".as_bytes(), &[bumps.mint]];
+        let signer: [&[&[u8]]; 1] = [&seeds[..]];
+
+        let mint_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            MintTo {
+                mint: self.mint.to_account_info(),
+                to: self.recipient.to_account_info(),
+                authority: self.mint.to_account_info(),
+            },
+            &signer,
+        );
+
+        mint_to(
+            mint_ctx,
+            amount,
+        )?;
+
+        msg!("Successfully minted tokens to {}.", self.owner.key());
+
+        Ok(())
+
+    }
+
+}
+
+// The market passes its bump, so its address is rebuilt directly rather than searched for
+fn is_market_pda(caller: &AccountInfo, token: &Pubkey, bump: u8) -> bool {
+    let address = Pubkey::create_program_address(&[b"market", token.as_ref(), &[bump]], &Pubkey::from_str(MARKET_PROGRAM_ID).unwrap());
+
+    address.is_ok_and(|address| caller.is_signer && caller.key() == address)
+}
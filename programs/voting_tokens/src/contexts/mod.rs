@@ -1,5 +1,7 @@
 pub mod initialise;
 pub mod mint_tokens;
+pub mod mint_tokens_to;
 
 pub use initialise::*;
 pub use mint_tokens::*;
+pub use mint_tokens_to::*;
@@ -16,3 +16,11 @@ pub enum InitError {
     WrongDecimals,
 
 }
+
+#[error_code]
+pub enum MintError {
+
+    #[msg("Only a market PDA can mint tokens to another account")]
+    CallerNotMarket,
+
+}
@@ -34,4 +34,15 @@ pub mod voting_tokens {
     
     }
 
+    pub fn mint_tokens_to(ctx: Context<MintTokensTo>, token: Pubkey, market_bump: u8, amount: u64) -> Result<()> {
+        
+        ctx.accounts.mint_tokens_to(
+            &ctx.bumps,
+            token,
+            market_bump,
+            amount,
+        )
+    
+    }
+
 }
@@ -1,16 +1,1199 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { BankrunProvider, startAnchor } from "anchor-bankrun";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY, Transaction } from "@solana/web3.js";
+import { expect } from "chai";
 import { Market } from "../target/types/market";
+import { Treasury } from "../target/types/treasury";
+import { VotingTokens } from "../target/types/voting_tokens";
+
+const { ASSOCIATED_PROGRAM_ID, TOKEN_PROGRAM_ID, associatedAddress } = anchor.utils.token;
 
 describe("market", () => {
-  // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let market: Program<Market>;
+  let treasury: Program<Treasury>;
+  let votingTokens: Program<VotingTokens>;
+
+  let treasuryPda: PublicKey;
+  let votingMint: PublicKey;
+  let treasuryVotes: PublicKey;
+
+  const pda = (seeds: (Buffer | Uint8Array)[], programId: PublicKey) =>
+    PublicKey.findProgramAddressSync(seeds, programId)[0];
+
+  // Sends a transaction which should fail, and checks the program logged the expected error
+  async function expectError(builder: { transaction(): Promise<Transaction> }, code: string, signers: Keypair[] = []) {
+    const tx = await builder.transaction();
+    tx.recentBlockhash = context.lastBlockhash;
+    tx.feePayer = context.payer.publicKey;
+    tx.sign(context.payer, ...signers);
+
+    const { result, meta } = await context.banksClient.tryProcessTransaction(tx);
+
+    expect(result, `expected ${code}`).to.not.equal(null);
+    expect(meta.logMessages.join("\n")).to.include(`Error Code: ${code}.`);
+  }
+
+  async function fundedKeypair(sol = 100): Promise<Keypair> {
+    const keypair = Keypair.generate();
+
+    context.setAccount(keypair.publicKey, {
+      lamports: sol * LAMPORTS_PER_SOL,
+      data: Buffer.alloc(0),
+      owner: SystemProgram.programId,
+      executable: false,
+    });
+
+    return keypair;
+  }
+
+  async function tokenBalance(account: PublicKey): Promise<number> {
+    const info = await context.banksClient.getAccount(account);
+
+    return info === null ? 0 : Number(Buffer.from(info.data).readBigUInt64LE(64));
+  }
+
+  async function mintVotes(owner: Keypair, amount: number): Promise<PublicKey> {
+    const recipient = associatedAddress({ mint: votingMint, owner: owner.publicKey });
+
+    await votingTokens.methods
+      .mintTokens(new BN(amount))
+      .accountsPartial({
+        payer: owner.publicKey,
+        mint: votingMint,
+        recipient,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([owner])
+      .rpc();
+
+    return recipient;
+  }
+
+  type StakeAccounts = { mint: PublicKey; account: PublicKey };
+
+  // Token accounts are written directly, as the stake mint needs no authority once the accounts are funded
+  function stakeMint(decimals = 6): PublicKey {
+    const mint = Keypair.generate().publicKey;
+    const data = Buffer.alloc(82);
+    data.writeUInt8(decimals, 44);
+    data.writeUInt8(1, 45);
+
+    context.setAccount(mint, { lamports: LAMPORTS_PER_SOL, data, owner: TOKEN_PROGRAM_ID, executable: false });
+
+    return mint;
+  }
+
+  function stakeAccount(mint: PublicKey, owner: PublicKey, amount: number): StakeAccounts {
+    const account = Keypair.generate().publicKey;
+    const data = Buffer.alloc(165);
+    mint.toBuffer().copy(data, 0);
+    owner.toBuffer().copy(data, 32);
+    data.writeBigUInt64LE(BigInt(amount), 64);
+    data.writeUInt8(1, 108);
+
+    context.setAccount(account, { lamports: LAMPORTS_PER_SOL, data, owner: TOKEN_PROGRAM_ID, executable: false });
+
+    return { mint, account };
+  }
+
+
+  const facet = { truthfulness: {} };
+  const FACET = Buffer.from("truthfulness");
+  const STAKES = Buffer.from([0]);
+  const BONDS = Buffer.from([1]);
+  const FEES = Buffer.from([2]);
+
+  const MIN_TIMEOUT = 24 * 60 * 60 * 1_000;
+  const APPEAL_WINDOW = 24 * 60 * 60;
+  const MIN_VOTE_AMOUNT = 1_000_000;
+  const APPEAL_MIN_VOTE_AMOUNT = 10 * MIN_VOTE_AMOUNT;
+  const MIN_APPEAL_BOND = 10 * LAMPORTS_PER_SOL;
+  const TOKEN_ACCOUNT_RENT = 2_039_280;
+
+  const config = {
+    exitFee: 0,
+    antiSniping: null,
+    minBet: new BN(1),
+    maxBet: null,
+    maxPoolShare: null,
+    outcomeTokens: false,
+    timeWeighting: null,
+    underdogBonus: 10_000,
+  };
+
+  const le16 = (n: number) => {
+    const bytes = Buffer.alloc(2);
+    bytes.writeUInt16LE(n);
+    return bytes;
+  };
+
+  // Accounts of a market and its facet, all derived from the authensus token
+  function marketAccounts(token: PublicKey) {
+    const marketPda = pda([Buffer.from("market"), token.toBuffer()], market.programId);
+    const facetPda = (seed: string, ...extra: Buffer[]) =>
+      pda([Buffer.from(seed), token.toBuffer(), FACET, ...extra], market.programId);
+
+    return {
+      params: { authensusToken: token, facet },
+      market: marketPda,
+      contentScore: pda([Buffer.from("score"), token.toBuffer()], market.programId),
+      escrow: facetPda("escrow"),
+      poll: facetPda("poll"),
+      appeal: (round: number) => facetPda("appeal", le16(round)),
+      vault: (round: number) => facetPda("vault", le16(round)),
+      outcomeMint: (round: number, outcome: number) => facetPda("outcome", le16(round), Buffer.from([outcome])),
+      bettor: (owner: PublicKey) => facetPda("bettor", owner.toBuffer()),
+      voter: (owner: PublicKey) => facetPda("voter", owner.toBuffer()),
+      verdict: (round: number) => facetPda("verdict", le16(round)),
+      payable: (round: number) =>
+        pda([Buffer.from("payable"), marketPda.toBuffer(), le16(round)], treasury.programId),
+      ledger: (round: number, purpose: Buffer) =>
+        pda([Buffer.from("ledger"), marketPda.toBuffer(), FACET, le16(round), purpose], treasury.programId),
+      iou: (round: number, owner: PublicKey) =>
+        pda([Buffer.from("iou"), marketPda.toBuffer(), le16(round), owner.toBuffer()], treasury.programId),
+    };
+  }
+
+  type MarketAccounts = ReturnType<typeof marketAccounts>;
+
+  async function warp(seconds: number) {
+    const clock = await context.banksClient.getClock();
+
+    context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(seconds),
+      ),
+    );
+  }
+
+  async function balance(account: PublicKey): Promise<number> {
+    return Number(await context.banksClient.getBalance(account));
+  }
+
+  async function currentRound(m: MarketAccounts): Promise<number> {
+    return (await market.account.market.fetch(m.market)).round;
+  }
+
+  async function openMarket(mint: PublicKey | null = null, marketConfig = config): Promise<MarketAccounts> {
+    const m = marketAccounts(Keypair.generate().publicKey);
+
+    await market.methods
+      .initialiseMarket(m.params.authensusToken, [facet], new BN(MIN_TIMEOUT), marketConfig)
+      .accountsPartial({
+        admin: provider.wallet.publicKey,
+        market: m.market,
+        contentScore: m.contentScore,
+        stakeMint: mint,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return m;
+  }
+
+  async function startRound(m: MarketAccounts, bettor: Keypair, amount: number, outcome: number, stake: StakeAccounts | null = null) {
+    const round = (await currentRound(m)) + 1;
+
+    await market.methods
+      .startMarket(m.params, new BN(amount), outcome)
+      .accountsPartial({
+        signer: bettor.publicKey,
+        treasuryAuth: provider.wallet.publicKey,
+        market: m.market,
+        escrow: m.escrow,
+        poll: m.poll,
+        initialiser: m.bettor(bettor.publicKey),
+        treasury: treasuryPda,
+        stakeMint: stake?.mint ?? null,
+        vault: stake ? m.vault(round) : null,
+        stakeAccount: stake?.account ?? null,
+        tokenProgram: stake ? TOKEN_PROGRAM_ID : null,
+        payable: m.payable(round),
+        ledger: m.ledger(round, STAKES),
+        treasuryProgram: treasury.programId,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bettor])
+      .rpc();
+  }
+
+  async function wager(m: MarketAccounts, bettor: Keypair, amount: number, outcome: number, stake: StakeAccounts | null = null) {
+    const round = await currentRound(m);
+
+    await market.methods
+      .wager(m.params, new BN(amount), outcome, null)
+      .accountsPartial({
+        treasuryAuth: provider.wallet.publicKey,
+        signer: bettor.publicKey,
+        market: m.market,
+        escrow: m.escrow,
+        bettor: m.bettor(bettor.publicKey),
+        treasury: treasuryPda,
+        stakeMint: stake?.mint ?? null,
+        vault: stake ? m.vault(round) : null,
+        stakeAccount: stake?.account ?? null,
+        tokenProgram: stake ? TOKEN_PROGRAM_ID : null,
+        payable: m.payable(round),
+        ledger: m.ledger(round, STAKES),
+        treasuryProgram: treasury.programId,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bettor])
+      .rpc();
+  }
+
+  async function voteBuilder(m: MarketAccounts, voter: Keypair, amount: number, outcome: number, appealed = false) {
+    const round = await currentRound(m);
+
+    return market.methods
+      .vote(m.params, new BN(amount), outcome, null)
+      .accountsPartial({
+        signer: voter.publicKey,
+        market: m.market,
+        escrow: m.escrow,
+        poll: m.poll,
+        appeal: appealed ? m.appeal(round) : null,
+        voter: m.voter(voter.publicKey),
+        voteEvidence: null,
+        votingTokenAccount: associatedAddress({ mint: votingMint, owner: voter.publicKey }),
+        mint: votingMint,
+        treasuryVotingTokenAccount: treasuryVotes,
+        treasury: treasuryPda,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+      })
+      .signers([voter]);
+  }
+
+  async function vote(m: MarketAccounts, voter: Keypair, amount: number, outcome: number, appealed = false) {
+    await (await voteBuilder(m, voter, amount, outcome, appealed)).rpc();
+  }
+
+  async function wagerResults(m: MarketAccounts, bettor: Keypair, appealed = false, stake: StakeAccounts | null = null) {
+    const round = await currentRound(m);
+
+    return market.methods
+      .wagerResults(m.params)
+      .accountsPartial({
+        treasuryAuth: provider.wallet.publicKey,
+        signer: bettor.publicKey,
+        market: m.market,
+        escrow: m.escrow,
+        bettor: m.bettor(bettor.publicKey),
+        poll: m.poll,
+        appeal: appealed ? m.appeal(round) : null,
+        mint: votingMint,
+        recipient: associatedAddress({ mint: votingMint, owner: bettor.publicKey }),
+        treasury: treasuryPda,
+        stakeMint: stake?.mint ?? null,
+        vault: stake ? m.vault(round) : null,
+        stakeAccount: stake?.account ?? null,
+        treasuryProgram: treasury.programId,
+        payable: m.payable(round),
+        stakesLedger: m.ledger(round, STAKES),
+        bondsLedger: m.ledger(round, BONDS),
+        iou: m.iou(round, bettor.publicKey),
+        votingTokensProgram: votingTokens.programId,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([bettor]);
+  }
+
+  async function settleBettor(m: MarketAccounts, bettor: Keypair, appealed = false, stake: StakeAccounts | null = null) {
+    await (await wagerResults(m, bettor, appealed, stake)).rpc();
+  }
+
+  async function openAppeal(m: MarketAccounts, appellant: Keypair, bond: number, outcome: number) {
+    const round = await currentRound(m);
+
+    return market.methods
+      .openAppeal(m.params, new BN(bond), outcome)
+      .accountsPartial({
+        treasuryAuth: provider.wallet.publicKey,
+        signer: appellant.publicKey,
+        market: m.market,
+        poll: m.poll,
+        appeal: m.appeal(round),
+        treasury: treasuryPda,
+        payable: m.payable(round),
+        ledger: m.ledger(round, BONDS),
+        treasuryProgram: treasury.programId,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([appellant]);
+  }
+
+  async function resolveAppeal(m: MarketAccounts, signer: Keypair, appellant: PublicKey) {
+    const round = await currentRound(m);
+
+    return market.methods
+      .resolveAppeal(m.params)
+      .accountsPartial({
+        treasuryAuth: provider.wallet.publicKey,
+        signer: signer.publicKey,
+        market: m.market,
+        poll: m.poll,
+        escrow: m.escrow,
+        appeal: m.appeal(round),
+        appellant,
+        treasuryVotingTokenAccount: treasuryVotes,
+        treasury: treasuryPda,
+        treasuryProgram: treasury.programId,
+        payable: m.payable(round),
+        ledger: m.ledger(round, BONDS),
+        feeLedger: m.ledger(round, FEES),
+        iou: m.iou(round, appellant),
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer]);
+  }
+
+  async function settleVoter(m: MarketAccounts, voter: Keypair, appealed = false) {
+    const round = await currentRound(m);
+
+    await market.methods
+      .voterResults(m.params)
+      .accountsPartial({
+        treasuryAuth: provider.wallet.publicKey,
+        signer: voter.publicKey,
+        market: m.market,
+        poll: m.poll,
+        appeal: appealed ? m.appeal(round) : null,
+        voter: m.voter(voter.publicKey),
+        votingTokenAccount: associatedAddress({ mint: votingMint, owner: voter.publicKey }),
+        treasuryVotingTokenAccount: treasuryVotes,
+        treasury: treasuryPda,
+        treasuryProgram: treasury.programId,
+        votingTokensProgram: votingTokens.programId,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+        mint: votingMint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  // Rounds staked in SOL have their leftover stakes swept into the round's fees
+  async function callMarket(m: MarketAccounts, appealed = false, solStakes = true): Promise<number> {
+    const round = await currentRound(m);
+
+    await market.methods
+      .callMarket(m.params)
+      .accountsPartial({
+        admin: provider.wallet.publicKey,
+        market: m.market,
+        poll: m.poll,
+        escrow: m.escrow,
+        appeal: appealed ? m.appeal(round) : null,
+        verdict: m.verdict(round),
+        contentScore: m.contentScore,
+        treasury: treasuryPda,
+        treasuryProgram: treasury.programId,
+        payable: solStakes ? m.payable(round) : null,
+        stakesLedger: solStakes ? m.ledger(round, STAKES) : null,
+        feeLedger: solStakes ? m.ledger(round, FEES) : null,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return round;
+  }
+
+  function closeBettor(m: MarketAccounts, bettor: Keypair) {
+    return market.methods
+      .closeBettor(m.params)
+      .accountsPartial({
+        signer: bettor.publicKey,
+        escrow: m.escrow,
+        bettor: m.bettor(bettor.publicKey),
+      })
+      .signers([bettor]);
+  }
+
+  function closeVoter(m: MarketAccounts, voter: Keypair) {
+    return market.methods
+      .closeVoter(m.params)
+      .accountsPartial({
+        signer: voter.publicKey,
+        market: m.market,
+        poll: m.poll,
+        appeal: null,
+        voter: m.voter(voter.publicKey),
+      })
+      .signers([voter]);
+  }
+
+  // The verdict is only passed once the round has been called, and the appeal only with its appellant
+  async function closeRound(m: MarketAccounts, rentPayer: PublicKey, appellant: PublicKey | null = null) {
+    const round = await currentRound(m);
+    const called = (await context.banksClient.getAccount(m.verdict(round))) !== null;
+
+    return market.methods
+      .closeRound(m.params)
+      .accountsPartial({
+        signer: provider.wallet.publicKey,
+        rentPayer,
+        market: m.market,
+        escrow: m.escrow,
+        poll: m.poll,
+        verdict: called ? m.verdict(round) : null,
+        appeal: appellant ? m.appeal(round) : null,
+        appellant,
+      });
+  }
+
+  before(async () => {
+    context = await startAnchor(".", [], []);
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+
+    market = anchor.workspace.Market as Program<Market>;
+    treasury = anchor.workspace.Treasury as Program<Treasury>;
+    votingTokens = anchor.workspace.VotingTokens as Program<VotingTokens>;
+
+    treasuryPda = pda([Buffer.from("treasury")], treasury.programId);
+    votingMint = pda([Buffer.from("mint")], votingTokens.programId);
+    treasuryVotes = associatedAddress({ mint: votingMint, owner: treasuryPda });
+
+    // The voting mint is set up directly rather than through the token metadata program,
+    // with the mint PDA as its own authority as voting_tokens::init leaves it
+    const mintData = Buffer.alloc(82);
+    mintData.writeUInt32LE(1, 0);
+    votingMint.toBuffer().copy(mintData, 4);
+    mintData.writeUInt8(9, 44);
+    mintData.writeUInt8(1, 45);
+
+    context.setAccount(votingMint, {
+      lamports: LAMPORTS_PER_SOL,
+      data: mintData,
+      owner: TOKEN_PROGRAM_ID,
+      executable: false,
+    });
+
+    await treasury.methods
+      .initialise()
+      .accountsPartial({
+        signer: provider.wallet.publicKey,
+        treasury: treasuryPda,
+        votingTokenAccount: treasuryVotes,
+        mint: votingMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  describe("voting token custody", () => {
+    let holder: Keypair;
+    let holderVotes: PublicKey;
+
+    const custody = (signer: PublicKey) => ({
+      signer,
+      treasury: treasuryPda,
+      mint: votingMint,
+      treasuryVotingTokenAccount: treasuryVotes,
+      votingTokenAccount: holderVotes,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+    });
+
+    before(async () => {
+      holder = await fundedKeypair();
+      holderVotes = await mintVotes(holder, 5_000_000);
+    });
+
+    it("holds deposited votes in the treasury PDA's token account", async () => {
+      await treasury.methods
+        .depositVotingTokens(new BN(2_000_000))
+        .accountsPartial(custody(holder.publicKey))
+        .signers([holder])
+        .rpc();
+
+      expect(await tokenBalance(holderVotes)).to.equal(3_000_000);
+      expect(await tokenBalance(treasuryVotes)).to.equal(2_000_000);
+    });
+
+    it("only releases votes to the authority or the market PDA of the token", async () => {
+      const token = Keypair.generate().publicKey;
+      const [, bump] = PublicKey.findProgramAddressSync([Buffer.from("market"), token.toBuffer()], market.programId);
+
+      await expectError(
+        treasury.methods.returnVotingTokens(null, new BN(2_000_000)).accountsPartial(custody(holder.publicKey)),
+        "SignerNotAllowed",
+        [holder],
+      );
+      await expectError(
+        treasury.methods.returnVotingTokens({ token, bump }, new BN(2_000_000)).accountsPartial(custody(holder.publicKey)),
+        "SignerNotAllowed",
+        [holder],
+      );
+      await expectError(
+        treasury.methods.burnVotingTokens({ token, bump }, new BN(2_000_000)).accountsPartial(custody(provider.wallet.publicKey)),
+        "SignerNotAllowed",
+      );
+    });
+
+    it("returns votes released by the authority", async () => {
+      await treasury.methods
+        .returnVotingTokens(null, new BN(2_000_000))
+        .accountsPartial(custody(provider.wallet.publicKey))
+        .rpc();
+
+      expect(await tokenBalance(holderVotes)).to.equal(5_000_000);
+      expect(await tokenBalance(treasuryVotes)).to.equal(0);
+    });
+  });
+
+  describe("settlement", () => {
+    let m: MarketAccounts;
+    let winner: Keypair;
+    let loser: Keypair;
+    let voter: Keypair;
+
+    before(async () => {
+      m = await openMarket();
+      winner = await fundedKeypair();
+      loser = await fundedKeypair();
+      voter = await fundedKeypair();
+
+      await mintVotes(voter, 1_000_000_000);
+
+      await startRound(m, winner, 1_000_000, 1);
+      await wager(m, loser, 500_000, 0);
+
+      await warp(MIN_TIMEOUT + 1);
+      await vote(m, voter, MIN_VOTE_AMOUNT, 1);
+      await warp(APPEAL_WINDOW + 1);
+    });
+
+    it("holds votes in the treasury's custody until they are settled", async () => {
+      const voterVotes = associatedAddress({ mint: votingMint, owner: voter.publicKey });
+
+      expect(await tokenBalance(voterVotes)).to.equal(1_000_000_000 - MIN_VOTE_AMOUNT);
+      expect(await tokenBalance(treasuryVotes)).to.be.at.least(MIN_VOTE_AMOUNT);
+    });
+
+    it("returns a winning bettor's stake and pays their winnings in voting tokens", async () => {
+      const before = await balance(winner.publicKey);
+
+      await settleBettor(m, winner);
+
+      // The losing pool less the fee, and the stake less the rent of the new voting token account
+      expect(await tokenBalance(associatedAddress({ mint: votingMint, owner: winner.publicKey }))).to.equal(475_000);
+      expect((await balance(winner.publicKey)) - before).to.equal(1_000_000 - TOKEN_ACCOUNT_RENT);
+    });
+
+    it("pays a losing bettor nothing", async () => {
+      await settleBettor(m, loser);
+
+      const escrow = await market.account.escrow.fetch(m.escrow);
+
+      expect(await tokenBalance(associatedAddress({ mint: votingMint, owner: loser.publicKey }))).to.equal(0);
+      expect(escrow.bettorsConsolidated.map((bettor) => bettor.toBase58())).to.include(loser.publicKey.toBase58());
+    });
+
+    it("only lets a market mint winnings into someone else's account", async () => {
+      const keeper = await fundedKeypair();
+      const token = m.params.authensusToken;
+      const [, bump] = PublicKey.findProgramAddressSync([Buffer.from("market"), token.toBuffer()], market.programId);
+
+      await expectError(
+        votingTokens.methods.mintTokensTo(token, bump, new BN(1_000_000)).accountsPartial({
+          payer: keeper.publicKey,
+          caller: keeper.publicKey,
+          owner: keeper.publicKey,
+          mint: votingMint,
+          recipient: associatedAddress({ mint: votingMint, owner: keeper.publicKey }),
+          associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        }),
+        "CallerNotMarket",
+        [keeper],
+      );
+    });
+
+    it("returns a winning voter's votes along with their winnings", async () => {
+      await settleVoter(m, voter);
+
+      expect(await tokenBalance(associatedAddress({ mint: votingMint, owner: voter.publicKey }))).to.equal(
+        1_000_000_000 + MIN_VOTE_AMOUNT,
+      );
+    });
+
+    it("records the verdict and the content score when the round is called", async () => {
+      const round = await callMarket(m);
+
+      const verdict = await market.account.verdict.fetch(m.verdict(round));
+      const score = await market.account.contentScore.fetch(m.contentScore);
+
+      expect(verdict.outcome).to.equal(1);
+      expect(verdict.appealed).to.equal(false);
+      expect(verdict.poolOutcomes.map((pool) => pool.toNumber())).to.deep.equal([500_000, 1_000_000]);
+      expect(score.facetScores[0].score).to.equal(10_000);
+    });
+
+    it("keeps the losing stakes left in the round as fees", async () => {
+      const round = await currentRound(m);
+      const stakes = await treasury.account.treasuryAccount.fetch(m.ledger(round, STAKES));
+      const fees = await treasury.account.treasuryAccount.fetch(m.ledger(round, FEES));
+
+      expect(stakes.balance.toNumber()).to.equal(0);
+      expect(fees.balance.toNumber()).to.equal(500_000);
+    });
+  });
+
+  describe("appeals", () => {
+    let m: MarketAccounts;
+    let backer: Keypair;
+    let challenger: Keypair;
+    let voter: Keypair;
+    let appellant: Keypair;
+    let appealVoter: Keypair;
+
+    const votesOf = (owner: Keypair) => tokenBalance(associatedAddress({ mint: votingMint, owner: owner.publicKey }));
+
+    before(async () => {
+      m = await openMarket();
+      backer = await fundedKeypair();
+      challenger = await fundedKeypair();
+      voter = await fundedKeypair();
+      appellant = await fundedKeypair();
+      appealVoter = await fundedKeypair();
+
+      await mintVotes(voter, 1_000_000_000);
+      await mintVotes(appellant, 1_000_000_000);
+      await mintVotes(appealVoter, 1_000_000_000);
+
+      await startRound(m, backer, 1_000_000, 1);
+      await wager(m, challenger, 500_000, 0);
+
+      await warp(MIN_TIMEOUT + 1);
+      await vote(m, voter, MIN_VOTE_AMOUNT, 1);
+    });
+
+    it("holds bettors back while the appeal window is open", async () => {
+      await expectError(await wagerResults(m, challenger), "AppealWindowOpen", [challenger]);
+    });
+
+    it("rejects appeals below the minimum bond or for the poll's own outcome", async () => {
+      await expectError(await openAppeal(m, appellant, MIN_APPEAL_BOND - 1, 0), "BondTooLow", [appellant]);
+      await expectError(await openAppeal(m, appellant, MIN_APPEAL_BOND, 1), "SameOutcomeAsPoll", [appellant]);
+    });
+
+    it("holds the bond in the round's bonds ledger", async () => {
+      await (await openAppeal(m, appellant, MIN_APPEAL_BOND, 0)).rpc();
+
+      const round = await currentRound(m);
+      const ledger = await treasury.account.treasuryAccount.fetch(m.ledger(round, BONDS));
+      const poll = await market.account.poll.fetch(m.poll);
+
+      expect(ledger.balance.toNumber()).to.equal(MIN_APPEAL_BOND);
+      expect(poll.appealed).to.equal(true);
+      await expectError(await openAppeal(m, appellant, MIN_APPEAL_BOND, 0), "AlreadyAppealed", [appellant]);
+    });
+
+    it("keeps the appellant out of the appeal poll", async () => {
+      await expectError(
+        await voteBuilder(m, appellant, APPEAL_MIN_VOTE_AMOUNT, 0, true),
+        "AppellantCannotVote",
+        [appellant],
+      );
+    });
+
+    it("returns the bond when the appeal poll overturns the outcome", async () => {
+      await vote(m, appealVoter, APPEAL_MIN_VOTE_AMOUNT, 0, true);
+
+      const keeper = await fundedKeypair();
+      const before = await balance(appellant.publicKey);
+
+      // Anyone can resolve the appeal, but only to the appellant's benefit
+      await expectError(await resolveAppeal(m, keeper, keeper.publicKey), "WrongAppellant", [keeper]);
+      await (await resolveAppeal(m, keeper, appellant.publicKey)).rpc();
+
+      const appeal = await market.account.appeal.fetch(m.appeal(await currentRound(m)));
+
+      expect(appeal.resolved).to.equal(true);
+      expect(appeal.overturned).to.equal(true);
+      expect((await balance(appellant.publicKey)) - before).to.equal(MIN_APPEAL_BOND);
+    });
+
+    it("settles bettors on the outcome of the appeal", async () => {
+      await settleBettor(m, challenger, true);
+      await settleBettor(m, backer, true);
+
+      expect(await votesOf(challenger)).to.equal(950_000);
+      expect(await votesOf(backer)).to.equal(0);
+    });
+
+    it("rewards the voters who backed the final outcome", async () => {
+      await settleVoter(m, appealVoter, true);
+      await settleVoter(m, voter, true);
+
+      expect(await votesOf(appealVoter)).to.equal(1_000_000_000 + APPEAL_MIN_VOTE_AMOUNT);
+      expect(await votesOf(voter)).to.equal(1_000_000_000 - MIN_VOTE_AMOUNT);
+    });
+
+    it("records the overturned outcome in the verdict", async () => {
+      const round = await callMarket(m, true);
+
+      const verdict = await market.account.verdict.fetch(m.verdict(round));
+
+      expect(verdict.outcome).to.equal(0);
+      expect(verdict.appealed).to.equal(true);
+      expect(verdict.overturned).to.equal(true);
+    });
+
+    it("returns the appeal's rent to the appellant when the round is closed", async () => {
+      const round = await currentRound(m);
+      const rent = await balance(m.appeal(round));
+      const before = await balance(appellant.publicKey);
+
+      await expectError(await closeRound(m, backer.publicKey), "AppealAccountMissing");
+      await expectError(await closeRound(m, backer.publicKey, challenger.publicKey), "WrongAppellant");
+      await (await closeRound(m, backer.publicKey, appellant.publicKey)).rpc();
+
+      expect(await context.banksClient.getAccount(m.appeal(round))).to.equal(null);
+      expect((await balance(appellant.publicKey)) - before).to.equal(rent);
+    });
+  });
+
+  describe("outcome tokens", () => {
+    let m: MarketAccounts;
+    let backer: Keypair;
+    let challenger: Keypair;
+    let voter: Keypair;
+    let appellant: Keypair;
+    let appealVoter: Keypair;
+    let backerStake: StakeAccounts;
+    let challengerStake: StakeAccounts;
+    let round: number;
+
+    const holderAccount = () => associatedAddress({ mint: m.outcomeMint(round, 1), owner: backer.publicKey });
+
+    before(async () => {
+      const mint = stakeMint();
+
+      m = await openMarket(mint, { ...config, outcomeTokens: true });
+      backer = await fundedKeypair();
+      challenger = await fundedKeypair();
+      voter = await fundedKeypair();
+      appellant = await fundedKeypair();
+      appealVoter = await fundedKeypair();
+      backerStake = stakeAccount(mint, backer.publicKey, 1_000_000);
+      challengerStake = stakeAccount(mint, challenger.publicKey, 500_000);
+
+      await mintVotes(voter, 1_000_000_000);
+      await mintVotes(appellant, 1_000_000_000);
+      await mintVotes(appealVoter, 1_000_000_000);
+
+      await startRound(m, backer, 1_000_000, 1, backerStake);
+      await wager(m, challenger, 500_000, 0, challengerStake);
+      round = await currentRound(m);
+    });
+
+    it("moves part of a position into outcome tokens", async () => {
+      await market.methods
+        .tokenisePosition(m.params, new BN(250_000), 1)
+        .accountsPartial({
+          signer: backer.publicKey,
+          market: m.market,
+          escrow: m.escrow,
+          bettor: m.bettor(backer.publicKey),
+          outcomeMint: m.outcomeMint(round, 1),
+          holderAccount: holderAccount(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([backer])
+        .rpc();
+
+      const bettor = await market.account.bettor.fetch(m.bettor(backer.publicKey));
+
+      expect(await tokenBalance(holderAccount())).to.equal(250_000);
+      expect(bettor.totOutcomes.map((tot) => tot.toNumber())).to.deep.equal([0, 750_000]);
+    });
+
+    it("settles the round after a failed appeal", async () => {
+      await warp(MIN_TIMEOUT + 1);
+      await vote(m, voter, MIN_VOTE_AMOUNT, 1);
+
+      await (await openAppeal(m, appellant, MIN_APPEAL_BOND, 0)).rpc();
+      await vote(m, appealVoter, APPEAL_MIN_VOTE_AMOUNT, 1, true);
+      await (await resolveAppeal(m, appellant, appellant.publicKey)).rpc();
+
+      await settleBettor(m, backer, true, backerStake);
+      await settleBettor(m, challenger, true, challengerStake);
+      await settleVoter(m, voter, true);
+      await settleVoter(m, appealVoter, true);
+      await callMarket(m, true, false);
+
+      const verdict = await market.account.verdict.fetch(m.verdict(round));
+
+      expect(verdict.overturned).to.equal(false);
+      expect(verdict.forfeitedBond.toNumber()).to.equal(MIN_APPEAL_BOND);
+    });
+
+    it("returns the stake behind redeemed tokens along with its share of the forfeited bond", async () => {
+      const stakeBefore = await tokenBalance(backerStake.account);
+      const before = await balance(backer.publicKey);
+
+      await market.methods
+        .redeemOutcomeTokens(m.params, round, 1, new BN(250_000))
+        .accountsPartial({
+          signer: backer.publicKey,
+          market: m.market,
+          escrow: m.escrow,
+          verdict: m.verdict(round),
+          outcomeMint: m.outcomeMint(round, 1),
+          holderAccount: holderAccount(),
+          stakeMint: backerStake.mint,
+          vault: m.vault(round),
+          stakeAccount: backerStake.account,
+          mint: votingMint,
+          recipient: associatedAddress({ mint: votingMint, owner: backer.publicKey }),
+          treasury: treasuryPda,
+          treasuryProgram: treasury.programId,
+          payable: m.payable(round),
+          bondsLedger: m.ledger(round, BONDS),
+          iou: m.iou(round, backer.publicKey),
+          votingTokensProgram: votingTokens.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([backer])
+        .rpc();
+
+      // The tokens back a quarter of the winning pool, so they get a quarter of the bond, as a bettor would
+      expect(await tokenBalance(holderAccount())).to.equal(0);
+      expect((await tokenBalance(backerStake.account)) - stakeBefore).to.equal(250_000);
+      expect((await balance(backer.publicKey)) - before).to.equal(MIN_APPEAL_BOND / 4);
+    });
+  });
+
+  describe("treasury ledgers", () => {
+    const reconcile = () => treasury.methods.reconcile().accountsPartial({ treasury: treasuryPda });
+
+    let reserve: PublicKey;
+
+    before(() => {
+      reserve = pda([Buffer.from("ledger"), Buffer.from("reserve")], treasury.programId);
+    });
+
+    it("reconciles while every lamport the treasury holds is on a ledger", async () => {
+      await reconcile().rpc();
+    });
+
+    it("fails to reconcile once lamports are sent straight to the treasury, until they are recorded in the reserve", async () => {
+      const reserveBefore = (await context.banksClient.getAccount(reserve)) === null
+        ? 0
+        : (await treasury.account.treasuryAccount.fetch(reserve)).balance.toNumber();
+
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.transfer({ fromPubkey: provider.wallet.publicKey, toPubkey: treasuryPda, lamports: LAMPORTS_PER_SOL }),
+        ),
+      );
+
+      await expectError(reconcile(), "LedgerMismatch");
+
+      await treasury.methods
+        .syncReserve()
+        .accountsPartial({
+          payer: provider.wallet.publicKey,
+          treasury: treasuryPda,
+          reserve,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await reconcile().rpc();
+      expect((await treasury.account.treasuryAccount.fetch(reserve)).balance.toNumber() - reserveBefore).to.equal(LAMPORTS_PER_SOL);
+    });
+  });
+
+  describe("closing accounts", () => {
+    let m: MarketAccounts;
+    let initialiser: Keypair;
+    let bettor: Keypair;
+    let voter: Keypair;
+
+    before(async () => {
+      m = await openMarket();
+      initialiser = await fundedKeypair();
+      bettor = await fundedKeypair();
+      voter = await fundedKeypair();
+
+      await mintVotes(voter, 1_000_000_000);
+
+      await startRound(m, initialiser, 1_000_000, 1);
+      await wager(m, bettor, 500_000, 0);
+
+      await warp(MIN_TIMEOUT + 1);
+      await vote(m, voter, MIN_VOTE_AMOUNT, 1);
+    });
+
+    it("keeps accounts open until they are settled and the round is called", async () => {
+      await expectError(closeBettor(m, bettor), "BettorNotConsolidated", [bettor]);
+      await expectError(closeVoter(m, voter), "VoterNotConsolidated", [voter]);
+      await expectError(await closeRound(m, initialiser.publicKey), "RoundNotCalled");
+    });
+
+    it("returns a settled bettor's and voter's rent to them", async () => {
+      await warp(APPEAL_WINDOW + 1);
+      await settleBettor(m, initialiser);
+      await settleBettor(m, bettor);
+      await settleVoter(m, voter);
+
+      const bettorRent = await balance(m.bettor(bettor.publicKey));
+      const voterRent = await balance(m.voter(voter.publicKey));
+      const bettorBefore = await balance(bettor.publicKey);
+      const voterBefore = await balance(voter.publicKey);
+
+      await closeBettor(m, bettor).rpc();
+      await closeVoter(m, voter).rpc();
+
+      expect(await context.banksClient.getAccount(m.bettor(bettor.publicKey))).to.equal(null);
+      expect(await context.banksClient.getAccount(m.voter(voter.publicKey))).to.equal(null);
+      expect((await balance(bettor.publicKey)) - bettorBefore).to.equal(bettorRent);
+      expect((await balance(voter.publicKey)) - voterBefore).to.equal(voterRent);
+    });
+
+    it("returns the escrow and poll rent to whoever started the round", async () => {
+      await callMarket(m);
+
+      const rent = (await balance(m.escrow)) + (await balance(m.poll));
+      const before = await balance(initialiser.publicKey);
+
+      await expectError(await closeRound(m, bettor.publicKey), "WrongRentPayer");
+      await (await closeRound(m, initialiser.publicKey)).rpc();
+
+      expect(await context.banksClient.getAccount(m.escrow)).to.equal(null);
+      expect(await context.banksClient.getAccount(m.poll)).to.equal(null);
+      expect((await balance(initialiser.publicKey)) - before).to.equal(rent);
+    });
+  });
+
+  describe("multisig subsidies", () => {
+    const SUBSIDY = Buffer.from([4]);
+    const LIQUIDITY = LAMPORTS_PER_SOL / 100;
+
+    let reserve: PublicKey;
+    let cosigner: Keypair;
+    let initialiser: Keypair;
+
+    const signers = () => [provider.wallet.publicKey, cosigner.publicKey];
+
+    // Proposes an action from the wallet, and has the cosigner approve and execute it
+    async function passProposal(action: object) {
+      const id = (await treasury.account.treasury.fetch(treasuryPda)).proposalCount;
+      const proposal = pda([Buffer.from("proposal"), id.toArrayLike(Buffer, "le", 8)], treasury.programId);
+
+      await treasury.methods
+        .propose(action as any)
+        .accountsPartial({ signer: provider.wallet.publicKey, treasury: treasuryPda, proposal, systemProgram: SystemProgram.programId })
+        .rpc();
+      await treasury.methods
+        .approve()
+        .accountsPartial({ signer: cosigner.publicKey, treasury: treasuryPda, proposal })
+        .signers([cosigner])
+        .rpc();
+      await treasury.methods
+        .execute()
+        .accountsPartial({
+          signer: cosigner.publicKey,
+          treasury: treasuryPda,
+          proposal,
+          recipient: null,
+          reserve: null,
+          feeVault: null,
+          vaultLedger: null,
+          iou: null,
+        })
+        .signers([cosigner])
+        .rpc();
+    }
+
+    async function openLmsr(m: MarketAccounts, liquidity = LIQUIDITY) {
+      const round = await currentRound(m);
+
+      return market.methods
+        .openLmsr(m.params, new BN(liquidity))
+        .accountsPartial({
+          treasuryAuth: provider.wallet.publicKey,
+          market: m.market,
+          pool: pda([Buffer.from("lmsr"), m.params.authensusToken.toBuffer(), FACET, le16(round)], market.programId),
+          treasury: treasuryPda,
+          reserve,
+          subsidyLedger: m.ledger(round, SUBSIDY),
+          treasuryProgram: treasury.programId,
+          systemProgram: SystemProgram.programId,
+        });
+    }
+
+    before(async () => {
+      reserve = pda([Buffer.from("ledger"), Buffer.from("reserve")], treasury.programId);
+      cosigner = await fundedKeypair();
+      initialiser = await fundedKeypair();
+
+      // The reserve needs funds of its own to lend out
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.transfer({ fromPubkey: provider.wallet.publicKey, toPubkey: treasuryPda, lamports: LAMPORTS_PER_SOL }),
+        ),
+      );
+      await treasury.methods
+        .syncReserve()
+        .accountsPartial({ payer: provider.wallet.publicKey, treasury: treasuryPda, reserve, systemProgram: SystemProgram.programId })
+        .rpc();
+
+      await treasury.methods
+        .setMultisig({ signers: signers(), threshold: 2 })
+        .accountsPartial({ signer: provider.wallet.publicKey, treasury: treasuryPda })
+        .rpc();
+    });
+
+    // Later tests expect the wallet to hold the treasury alone again
+    after(async () => {
+      await passProposal({ setAuthority: { authority: provider.wallet.publicKey, multisig: null } });
+    });
+
+    it("only subsidises a market maker out of a budget the multisig has approved", async () => {
+      const m = await openMarket();
+      await startRound(m, initialiser, 1_000_000, 1);
+
+      // A different liquidity keeps the failed transaction from sharing a signature with the one which succeeds
+      await expectError(await openLmsr(m, 2 * LIQUIDITY), "SubsidyBudgetExceeded");
+
+      await passProposal({ setSubsidyBudget: { amount: new BN(LIQUIDITY) } });
+      await (await openLmsr(m)).rpc();
+
+      // b*ln(2) of the budget is lent out, and the rest is left for other makers
+      const subsidy = (await treasury.account.treasuryAccount.fetch(m.ledger(1, SUBSIDY))).balance.toNumber();
+      const budget = (await treasury.account.treasury.fetch(treasuryPda)).subsidyBudget.toNumber();
+
+      expect(subsidy).to.equal(Math.ceil(LIQUIDITY * Math.log(2)));
+      expect(budget).to.equal(LIQUIDITY - subsidy);
+    });
+
+    it("fails once a maker would need more than the rest of the budget", async () => {
+      const m = await openMarket();
+      await startRound(m, initialiser, 1_000_000, 1);
+
+      await expectError(await openLmsr(m), "SubsidyBudgetExceeded");
+    });
+  });
+
+  describe("authority rotation", () => {
+    const TIMELOCK = 2 * 24 * 60 * 60;
+
+    // Each test hands over to a fresh key, so that no transaction is sent twice with the same signature
+    let successor: Keypair;
+
+    const proposeAuthority = (signer: PublicKey, authority: PublicKey, timelock: number) =>
+      treasury.methods.proposeAuthority(authority, new BN(timelock)).accountsPartial({ signer, treasury: treasuryPda });
+    const acceptAuthority = (signer: PublicKey) =>
+      treasury.methods.acceptAuthority().accountsPartial({ signer, treasury: treasuryPda });
+    const cancelAuthority = (signer: PublicKey) =>
+      treasury.methods.cancelAuthority().accountsPartial({ signer, treasury: treasuryPda });
+
+    const authority = async () => (await treasury.account.treasury.fetch(treasuryPda)).authority;
+    const pendingAuthority = async () => (await treasury.account.treasury.fetch(treasuryPda)).pendingAuthority;
+
+    beforeEach(async () => {
+      successor = await fundedKeypair();
+    });
+
+    it("only hands over the treasury to the proposed authority once the timelock has passed", async () => {
+      await expectError(proposeAuthority(successor.publicKey, successor.publicKey, 0), "SignerNotAuthority", [successor]);
+      await proposeAuthority(provider.wallet.publicKey, successor.publicKey, TIMELOCK).rpc();
+
+      const stranger = await fundedKeypair();
+
+      await expectError(acceptAuthority(stranger.publicKey), "SignerNotPendingAuthority", [stranger]);
+      await expectError(acceptAuthority(successor.publicKey), "TimelockNotPassed", [successor]);
+
+      await warp(TIMELOCK);
+      await acceptAuthority(successor.publicKey).signers([successor]).rpc();
+
+      expect((await authority()).toBase58()).to.equal(successor.publicKey.toBase58());
+
+      // Later tests expect the wallet to hold the treasury again
+      await proposeAuthority(successor.publicKey, provider.wallet.publicKey, 0).signers([successor]).rpc();
+      await acceptAuthority(provider.wallet.publicKey).rpc();
+
+      expect((await authority()).toBase58()).to.equal(provider.wallet.publicKey.toBase58());
+    });
+
+    it("lets only the current authority cancel a pending authority", async () => {
+      await proposeAuthority(provider.wallet.publicKey, successor.publicKey, 0).rpc();
+
+      await expectError(cancelAuthority(successor.publicKey), "SignerNotAuthority", [successor]);
+      await cancelAuthority(provider.wallet.publicKey).rpc();
+
+      await expectError(acceptAuthority(successor.publicKey), "NoPendingAuthority", [successor]);
+      expect(await pendingAuthority()).to.equal(null);
+    });
+
+    it("leaves a multisig treasury's authority to its proposals", async () => {
+      const proposal = pda(
+        [Buffer.from("proposal"), (await treasury.account.treasury.fetch(treasuryPda)).proposalCount.toArrayLike(Buffer, "le", 8)],
+        treasury.programId,
+      );
+
+      await proposeAuthority(provider.wallet.publicKey, successor.publicKey, 0).rpc();
+      await treasury.methods
+        .setMultisig({ signers: [provider.wallet.publicKey], threshold: 1 })
+        .accountsPartial({ signer: provider.wallet.publicKey, treasury: treasuryPda })
+        .rpc();
+
+      await expectError(proposeAuthority(provider.wallet.publicKey, successor.publicKey, 1), "MultisigControlled");
+      await expectError(acceptAuthority(successor.publicKey), "MultisigControlled", [successor]);
 
-  const program = anchor.workspace.Market as Program<Market>;
+      // Handing the treasury back to the wallet alone also clears the pending authority
+      await treasury.methods
+        .propose({ setAuthority: { authority: provider.wallet.publicKey, multisig: null } })
+        .accountsPartial({ signer: provider.wallet.publicKey, treasury: treasuryPda, proposal, systemProgram: SystemProgram.programId })
+        .rpc();
+      await treasury.methods
+        .execute()
+        .accountsPartial({
+          signer: provider.wallet.publicKey,
+          treasury: treasuryPda,
+          proposal,
+          recipient: null,
+          reserve: null,
+          feeVault: null,
+          vaultLedger: null,
+          iou: null,
+        })
+        .rpc();
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+      expect((await authority()).toBase58()).to.equal(provider.wallet.publicKey.toBase58());
+      expect(await pendingAuthority()).to.equal(null);
+    });
   });
 });